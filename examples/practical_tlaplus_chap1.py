@@ -71,14 +71,17 @@ ev = timewinder.Evaluator(
     # ],
 )
 
-err = None
-try:
-    ev.evaluate()
-except timewinder.ConstraintError as e:
-    err = e
-
-if err is None:
-    print(ev.stats)
-else:
-    print(f"\nConstraint Violated: {err.name}\n")
-    ev.replay_thunk(err.thunk)
+
+# Run the evaluator when executed as a script; `timewinder check` loads `ev` instead.
+if __name__ == "__main__":
+    err = None
+    try:
+        ev.evaluate()
+    except timewinder.ConstraintError as e:
+        err = e
+
+    if err is None:
+        print(ev.stats)
+    else:
+        print(f"\nConstraint Violated: {err.name}\n")
+        ev.replay_thunk(err.thunk)
@@ -13,6 +13,9 @@ setuptools.setup(
     long_description_content_type="text/markdown",
     url="https://github.com/barakmich/timewinder",
     packages=setuptools.find_packages(),
+    entry_points={
+        "console_scripts": ["timewinder = timewinder.cli:main"],
+    },
     classifiers=[
         "License :: OSI Approved :: GNU General Public License v3 (GPLv3)",
         "Development Status :: 3 - Alpha",
@@ -22,6 +25,7 @@ setuptools.setup(
     install_requires=[
         "dataclasses",
         "msgpack",
+        "tomli; python_version < '3.11'",
         "varname",
     ],
     python_requires=">=3.8",
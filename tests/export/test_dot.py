@@ -0,0 +1,52 @@
+import pytest
+import timewinder
+
+from timewinder.export import to_dot
+
+
+@timewinder.object
+class Counter:
+    def __init__(self):
+        self.n = 0
+
+
+def counter_model(**kwargs):
+    c = Counter()
+
+    @timewinder.process
+    def incr(c):
+        c.n = c.n + 1
+        yield "again"
+        c.n = c.n + 1
+
+    return timewinder.Evaluator(objects=[c], threads=[incr(c), incr(c)], **kwargs)
+
+
+def test_dot_requires_graph():
+    ev = counter_model()
+    ev.evaluate()
+    with pytest.raises(ValueError):
+        to_dot(ev)
+
+
+def test_dot_export():
+    ev = counter_model(record_graph=True)
+    ev.evaluate()
+    dot = to_dot(ev, variables=["c.n"])
+    assert dot.startswith("digraph timewinder {")
+    assert 's0 [label="c.n = 0", peripheries=2];' in dot
+    assert '[label="0: incr@start"]' in dot
+    assert '[label="1: incr@again"]' in dot
+    assert "c.n = 4" in dot
+
+
+def test_dot_limits():
+    ev = counter_model(record_graph=True)
+    ev.evaluate()
+    dot = to_dot(ev, max_nodes=2)
+    assert "// truncated to 2 of" in dot
+    assert "s2" not in dot
+
+    dot = to_dot(ev, variables=["c.n"], node_filter=lambda s: s["c"]["n"] % 2 == 0)
+    assert "c.n = 1" not in dot
+    assert "c.n = 2" in dot
@@ -0,0 +1,61 @@
+import json
+import re
+
+import timewinder
+
+from timewinder.export import to_html
+
+
+@timewinder.object
+class Counter:
+    def __init__(self):
+        self.n = 0
+
+
+@timewinder.predicate
+def below_two(c):
+    return c.n < 2
+
+
+def test_html_report():
+    c = Counter()
+
+    @timewinder.process
+    def incr(c):
+        c.n = c.n + 1
+        yield "bumped"
+        c.n = c.n + 1
+
+    ev = timewinder.Evaluator(
+        objects=[c], threads=[incr(c)], specs=[below_two(c)], record_graph=True
+    )
+    result = ev.check(steps=None)
+    out = to_html(result, ev, title="<counter>")
+    assert out.startswith("<!DOCTYPE html>")
+    assert "<title>&lt;counter&gt;</title>" in out
+    assert "<p class=status-fail>Violation: [](below_two)</p>" in out
+    assert "<td>[](below_two)</td><td>safety</td><td class=status-fail>fail</td>" in out
+    assert "<td>incr@start</td><td class=taken-yes>yes</td>" in out
+    assert "<div id=viewer>" in out
+
+    # The embedded data is the JSON report, and loads back
+    data = re.search(
+        r'<script type="application/json" id="data">(.*?)</script>', out, re.S
+    )
+    doc = json.loads(data.group(1))
+    assert doc["status"] == "fail"
+    assert len(doc["trace"]) == len(result.trace)
+    assert {"thread": 0, "action": "incr@bumped", "taken": True} in doc["coverage"]
+
+
+def test_html_escapes_data():
+    c = Counter()
+
+    @timewinder.process
+    def incr(c):
+        c.n = "</script>"
+
+    ev = timewinder.Evaluator(objects=[c], threads=[incr(c)])
+    out = to_html(ev.check(steps=None))
+    assert out.count("</script>") == 2
+    assert "Coverage" not in out
@@ -0,0 +1,102 @@
+import json
+
+import pytest
+import timewinder
+
+from timewinder.export import replay_itf
+from timewinder.export import to_itf
+from timewinder.export.itf import ReplayError
+from timewinder.export.itf import from_itf_value
+from timewinder.export.itf import to_itf_value
+from timewinder.result import build_trace
+
+
+@timewinder.object
+class Account:
+    def __init__(self, amt):
+        self.acc = amt
+
+
+def bank():
+    alice = Account(5)
+    bob = Account(5)
+
+    @timewinder.process
+    def transfer(sender, reciever, amount):
+        sender.acc = sender.acc - amount
+        yield "deposit"
+        reciever.acc = reciever.acc + amount
+
+    no_overdrafts = timewinder.ForAll(Account, lambda a: a.acc >= 0)
+    return timewinder.Evaluator(
+        objects=[alice, bob],
+        threads=[transfer(alice, bob, 3), transfer(alice, bob, 3)],
+        specs=[no_overdrafts],
+    )
+
+
+def test_itf_values_roundtrip():
+    for v in [1, 2 ** 60, "x", [1, [2]], (1, "a"), {"a": 1}, {1: "a"}]:
+        assert from_itf_value(json.loads(json.dumps(to_itf_value(v)))) == v
+    assert to_itf_value({3, 1}) == {"#set": [1, 3]}
+    assert to_itf_value(None) == {"#unserializable": "None"}
+
+
+def test_itf_export_and_replay():
+    result = bank().check()
+    itf = to_itf(result)
+    assert itf["#meta"]["format"] == "ITF"
+    assert itf["vars"] == ["alice", "bob"]
+    assert itf["states"][0]["alice"] == {"acc": 5}
+    assert itf["states"][1]["#meta"]["action"] == "transfer@start"
+
+    # Replaying through a fresh model reproduces the same behavior
+    ev = bank()
+    thunk = replay_itf(ev, json.loads(json.dumps(itf)))
+    assert [s.state for s in build_trace(ev, thunk)] == [s.state for s in result.trace]
+
+
+def test_itf_replay_mismatch():
+    itf = {
+        "vars": ["alice"],
+        "states": [{"alice": {"acc": 5}}, {"alice": {"acc": 4}}],
+    }
+    with pytest.raises(ReplayError) as e:
+        replay_itf(bank(), itf)
+    assert e.value.step == 1
+
+
+@timewinder.object
+class Tally:
+    def __init__(self):
+        self.total = 5
+        self.entries = []
+
+
+def test_itf_replay_follows_every_match():
+    @timewinder.process
+    def adjust(t, deltas):
+        for d in deltas:
+            t.total = t.total + d
+            t.entries.append([d])
+            yield "adjust"
+
+    def tallies():
+        t = Tally()
+        threads = [adjust(t, [-3, -1]), adjust(t, [-3, 2])]
+        return timewinder.Evaluator(objects=[t], threads=threads)
+
+    # Either thread's first step fits the second state; only the second
+    # thread's can go on to the third
+    states = [(5, []), (2, [[-3]]), (4, [[-3], [2]])]
+    itf = {
+        "vars": ["t"],
+        "states": [
+            {"t": {"total": total, "entries": [{"#tup": e} for e in entries]}}
+            for total, entries in states
+        ],
+    }
+    ev = tallies()
+    thunk = replay_itf(ev, itf)
+    assert thunk.trace == [1, 1]
+    assert [s.state["t"]["total"] for s in build_trace(ev, thunk)] == [5, 2, 4]
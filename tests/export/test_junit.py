@@ -0,0 +1,50 @@
+import xml.etree.ElementTree as ET
+
+import timewinder
+
+from timewinder.export import to_junit_xml
+
+
+@timewinder.object
+class Account:
+    def __init__(self, amt):
+        self.acc = amt
+
+
+def bank(amount):
+    alice = Account(5)
+
+    @timewinder.process
+    def withdraw(sender, amount):
+        sender.acc = sender.acc - amount
+
+    no_overdrafts = timewinder.ForAll(Account, lambda a: a.acc >= 0)
+    under_ten = timewinder.ForAll(Account, lambda a: a.acc < 10)
+    return timewinder.Evaluator(
+        objects=[alice],
+        threads=[withdraw(alice, amount)],
+        specs=[no_overdrafts, under_ten],
+    )
+
+
+def test_junit_pass():
+    root = ET.fromstring(to_junit_xml(bank(1).check()))
+    assert root.tag == "testsuite"
+    assert root.get("tests") == "2"
+    assert root.get("failures") == "0"
+    cases = root.findall("testcase")
+    assert len(cases) == 2
+    assert all(len(c) == 0 for c in cases)
+    assert cases[0].get("file") == __file__
+
+
+def test_junit_failure():
+    xml = to_junit_xml(bank(6).check(), suite="bank", artifact="out/trace.json")
+    root = ET.fromstring(xml)
+    assert root.get("name") == "bank"
+    assert root.get("failures") == "1"
+    assert root.get("skipped") == "1"
+    failing, unknown = root.findall("testcase")
+    assert "Error: The behavior up to this point is:" in failing.find("failure").text
+    assert failing.find("system-out").text == "[[ATTACHMENT|out/trace.json]]"
+    assert unknown.find("skipped") is not None
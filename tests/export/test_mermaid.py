@@ -0,0 +1,45 @@
+import timewinder
+
+from timewinder.export import to_mermaid
+
+
+@timewinder.object
+class Channel:
+    def __init__(self):
+        self.queue = []
+        self.received = 0
+
+
+@timewinder.predicate
+def nothing_received(c):
+    return c.received == 0
+
+
+def test_mermaid_sequence():
+    c = Channel()
+
+    @timewinder.process
+    def sender(c):
+        c.queue.append("ping")
+        yield "done"
+
+    @timewinder.process
+    def reciever(c):
+        while len(c.queue) == 0:
+            yield "wait"
+        c.queue = c.queue[1:]
+        c.received = c.received + 1
+
+    ev = timewinder.Evaluator(
+        objects=[c],
+        threads=[sender(c), reciever(c)],
+        specs=[nothing_received(c)],
+    )
+    out = to_mermaid(ev.check(steps=None))
+    lines = out.splitlines()
+    assert lines[0] == "sequenceDiagram"
+    assert "    participant T0 as sender 0" in lines
+    assert "    participant T1 as reciever 1" in lines
+    assert "    participant c" in lines
+    assert '    T0->>c: sender@start: queue = ["ping"]' in lines
+    assert "    T1->>c: reciever@start: queue = [], received = 1" in lines
@@ -0,0 +1,63 @@
+import pytest
+
+import timewinder
+
+from timewinder.conformance import SystemUnderTest
+from timewinder.export import to_pytest
+
+
+@timewinder.object
+class Account:
+    def __init__(self, amt):
+        self.acc = amt
+
+
+def overdraft_result():
+    @timewinder.process
+    def withdraw(sender, amount):
+        sender.acc = sender.acc - amount
+        yield "again"
+        sender.acc = sender.acc - amount
+
+    alice = Account(5)
+    no_overdrafts = timewinder.ForAll(Account, lambda a: a.acc >= 0)
+    ev = timewinder.Evaluator(
+        objects=[alice], threads=[withdraw(alice, 3)], specs=[no_overdrafts]
+    )
+    return ev.check()
+
+
+class Bank(SystemUnderTest):
+    def __init__(self, checked):
+        self.checked = checked
+        self.acc = 5
+
+    def apply(self, thread_id, name):
+        if self.acc >= 3 or not self.checked:
+            self.acc -= 3
+
+    def observe(self):
+        return {"alice": {"acc": self.acc}}
+
+
+def test_to_pytest():
+    code = to_pytest(overdraft_result(), sut="bank.fixtures:make_bank")
+    lines = code.splitlines()
+    assert lines[0].startswith(
+        "# Generated by timewinder from a counterexample to [](no_"
+    )
+    assert lines[1] == "from bank.fixtures import make_bank"
+    assert "def test_no_overdrafts():" in lines
+    assert "    system.apply(0, 'withdraw@start')" in lines
+    assert "    system.apply(0, 'withdraw@again')" in lines
+
+    code = to_pytest(overdraft_result())
+    assert "import" not in code
+    for checked in (True, False):
+        env = {"make_sut": lambda: Bank(checked)}
+        exec(code, env)
+        if checked:
+            env["test_no_overdrafts"]()
+        else:
+            with pytest.raises(AssertionError, match="implementation violates"):
+                env["test_no_overdrafts"]()
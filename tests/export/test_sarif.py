@@ -0,0 +1,50 @@
+import os
+
+import timewinder
+
+from timewinder.export import to_sarif
+
+
+@timewinder.object
+class Account:
+    def __init__(self, amt):
+        self.acc = amt
+
+
+def test_sarif_violation():
+    alice = Account(5)
+
+    @timewinder.process
+    def withdraw(sender, amount):
+        sender.acc = sender.acc - amount
+
+    no_overdrafts = timewinder.ForAll(Account, lambda a: a.acc >= 0)
+    ev = timewinder.Evaluator(
+        objects=[alice], threads=[withdraw(alice, 6)], specs=[no_overdrafts]
+    )
+    base = os.path.dirname(os.path.dirname(__file__))
+    log = to_sarif(ev.check(), base=base, artifact=os.path.join(base, "trace.json"))
+
+    assert log["version"] == "2.1.0"
+    run = log["runs"][0]
+    (rule,) = run["tool"]["driver"]["rules"]
+    assert rule["id"] == "[](no_overdrafts:ForAll(<lambda>))"
+    (result,) = run["results"]
+    loc = result["locations"][0]["physicalLocation"]
+    assert loc["artifactLocation"]["uri"] == "export/test_sarif.py"
+    assert loc["region"]["startLine"] > 0
+    (step,) = result["codeFlows"][0]["threadFlows"][0]["locations"]
+    text = step["location"]["message"]["text"]
+    assert text == "Step 1: thread 0 runs withdraw@start; alice = [acc |-> -1]"
+    assert result["attachments"][0]["artifactLocation"]["uri"] == "trace.json"
+
+
+def test_sarif_pass():
+    alice = Account(5)
+
+    @timewinder.process
+    def withdraw(sender, amount):
+        sender.acc = sender.acc - amount
+
+    ev = timewinder.Evaluator(objects=[alice], threads=[withdraw(alice, 1)])
+    assert to_sarif(ev.check())["runs"][0]["results"] == []
@@ -0,0 +1,80 @@
+import sqlite3
+
+import timewinder
+
+from timewinder.export import archive_run
+
+
+@timewinder.object
+class Lock:
+    def __init__(self):
+        self.holder = None
+
+
+def lock_model():
+    lock = Lock()
+
+    @timewinder.process
+    def worker(lock, id):
+        lock.holder = id
+        yield "release"
+        lock.holder = None
+
+    return timewinder.Evaluator(
+        objects=[lock],
+        threads=[worker(lock, 1), worker(lock, 2)],
+        record_graph=True,
+    )
+
+
+def test_archive_run(tmp_path):
+    path = str(tmp_path / "runs.db")
+    ev = lock_model()
+    run_id = archive_run(path, ev, ev.check(steps=None))
+
+    conn = sqlite3.connect(path)
+    status, bounded = conn.execute(
+        "SELECT status, bounded FROM runs WHERE id = ?", (run_id,)
+    ).fetchone()
+    assert status == "pass"
+    assert bounded == 0
+
+    (n_states,) = conn.execute(
+        "SELECT count(*) FROM states WHERE run_id = ?", (run_id,)
+    ).fetchone()
+    assert n_states == ev.stats.states
+
+    (held_by_2,) = conn.execute(
+        "SELECT count(*) FROM states "
+        "WHERE run_id = ? AND json_extract(state, '$.lock.holder') = 2",
+        (run_id,),
+    ).fetchone()
+    assert held_by_2 > 0
+
+    (n_transitions,) = conn.execute(
+        "SELECT count(*) FROM transitions WHERE run_id = ?", (run_id,)
+    ).fetchone()
+    assert n_transitions == len(list(ev.graph.all_edges()))
+
+
+def test_archive_counterexample():
+    conn = sqlite3.connect(":memory:")
+    lock = Lock()
+
+    @timewinder.process
+    def worker(lock, id):
+        lock.holder = id
+
+    unlocked = timewinder.ForAll(Lock, lambda l: l.holder is None)
+    ev = timewinder.Evaluator(
+        objects=[lock],
+        threads=[worker(lock, 1)],
+        specs=[unlocked],
+    )
+    run_id = archive_run(conn, ev, ev.check())
+    rows = conn.execute(
+        "SELECT step, thread, action FROM counterexamples "
+        "WHERE run_id = ? ORDER BY step",
+        (run_id,),
+    ).fetchall()
+    assert rows == [(0, None, None), (1, 0, "worker@start")]
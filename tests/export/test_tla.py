@@ -0,0 +1,57 @@
+import timewinder
+
+from timewinder.export import to_tla_module
+from timewinder.generators import Set
+
+
+@timewinder.object
+class Account:
+    def __init__(self, name, amt):
+        self.name = name
+        self.acc = amt
+
+
+@timewinder.predicate
+def consistent_total(a, b):
+    return a.acc + b.acc == 10
+
+
+def test_tla_module():
+    alice = Account("alice", 5)
+    bob = Account("bob", Set([4, 5]))
+
+    @timewinder.process
+    def transfer(sender, reciever, amount):
+        sender.acc = sender.acc - amount
+        yield "deposit"
+        reciever.acc = reciever.acc + amount
+
+    no_overdrafts = timewinder.ForAll(Account, lambda a: a.acc >= 0)
+
+    ev = timewinder.Evaluator(
+        objects=[alice, bob],
+        threads=[transfer(alice, bob, 3)],
+        specs=[
+            no_overdrafts,
+            timewinder.Eventually(timewinder.Always(consistent_total(alice, bob))),
+        ],
+    )
+    out = to_tla_module(ev, "Transfer")
+    lines = out.splitlines()
+    assert lines[0] == "---- MODULE Transfer ----"
+    assert "VARIABLES alice, bob, pc" in lines
+    assert '    /\\ alice = [acc |-> 5, name |-> "alice"]' in lines
+    bob = '[acc |-> 4, name |-> "bob"], [acc |-> 5, name |-> "bob"]'
+    assert "    /\\ bob \\in {" + bob + "}" in lines
+    assert '    /\\ pc = <<"transfer@start">>' in lines
+    assert "transfer_start_0 ==" in lines
+    assert '    /\\ pc[1] = "transfer@deposit"' in lines
+    assert "    \\/ transfer_deposit_0" in lines
+    assert "Invariant1 == no_overdrafts" in lines
+    assert "Property2 == <>([](consistent_total))" in lines
+    assert "\\* INVARIANT Invariant1" in lines
+    assert "\\* PROPERTY Property2" in lines
+    assert lines[-1] == "===="
+
+    # The model's nondeterministic initial state is left intact
+    assert len(list(ev.state_controller.commit())) == 2
@@ -0,0 +1,57 @@
+import timewinder
+
+from timewinder.export import format_tlc
+from timewinder.export.tlc import tla_value
+
+
+@timewinder.object
+class Account:
+    def __init__(self, name, amt):
+        self.name = name
+        self.acc = amt
+
+
+def test_tla_value():
+    assert tla_value(True) == "TRUE"
+    assert tla_value("a\"b") == '"a\\"b"'
+    assert tla_value("a\\b\n\tc") == '"a\\\\b\\n\\tc"'
+    assert tla_value([1, "x"]) == '<<1, "x">>'
+    assert tla_value({"b": 2, "a": None}) == "[a |-> NULL, b |-> 2]"
+    assert tla_value({1: "x", 2: "y"}) == '(1 :> "x" @@ 2 :> "y")'
+
+
+def test_format_tlc_violation():
+    alice = Account("alice", 5)
+
+    @timewinder.process
+    def withdraw(sender, amount):
+        sender.acc = sender.acc - amount
+
+    no_overdrafts = timewinder.ForAll(Account, lambda a: a.acc >= 0)
+    ev = timewinder.Evaluator(
+        objects=[alice],
+        threads=[withdraw(alice, 6)],
+        specs=[no_overdrafts],
+    )
+    out = format_tlc(ev.check())
+    lines = out.splitlines()
+    assert lines[0].startswith("Error: Invariant [](no_overdrafts")
+    assert lines[1] == "Error: The behavior up to this point is:"
+    assert lines[2] == "State 1: <Initial predicate>"
+    assert lines[3] == '/\\ alice = [acc |-> 5, name |-> "alice"]'
+    assert lines[5].startswith("State 2: <withdraw@start line ")
+    assert lines[5].endswith(" of module test_tlc>")
+    assert lines[6] == '/\\ alice = [acc |-> -1, name |-> "alice"]'
+    assert lines[-1] == "2 distinct states found."
+
+
+def test_format_tlc_pass():
+    alice = Account("alice", 5)
+
+    @timewinder.process
+    def withdraw(sender, amount):
+        sender.acc = sender.acc - amount
+
+    ev = timewinder.Evaluator(objects=[alice], threads=[withdraw(alice, 1)])
+    out = format_tlc(ev.check())
+    assert out.startswith("Model checking completed. No error has been found.")
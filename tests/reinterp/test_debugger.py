@@ -0,0 +1,92 @@
+import inspect
+
+import timewinder
+
+from timewinder.pause import PauseReason
+from timewinder.reinterp.debugger import DebugSession
+
+
+@timewinder.object
+class Account:
+    def __init__(self, amt):
+        self.acc = amt
+
+
+def setup():
+    alice = Account(5)
+
+    @timewinder.process
+    def withdraw(sender, amount):
+        total = amount + amount
+        sender.acc = sender.acc - total
+        yield "second"
+        sender.acc = sender.acc - amount
+
+    ev = timewinder.Evaluator(objects=[alice], threads=[withdraw(alice, 1)])
+    sc = ev.state_controller
+    sc.restore(list(sc.commit())[0])
+    first = inspect.getsourcelines(withdraw.func)[1] + 1
+    return DebugSession(ev.threads[0], sc), alice, first
+
+
+def test_step_line():
+    dbg, alice, first = setup()
+    assert dbg.line() == first + 1
+    dbg.step_line()
+    assert dbg.line() == first + 2
+    assert dbg.variables()["total"] == 2
+    assert dbg.variables()["sender"] is alice
+
+
+def test_breakpoint_and_watch():
+    dbg, alice, first = setup()
+    dbg.watch("sender.acc")
+    dbg.watch("missing")
+    dbg.add_breakpoint(first + 2)
+    cont = dbg.cont()
+    assert cont.kind == PauseReason.NORMAL
+    assert dbg.line() == first + 2
+    values = dbg.watch_values()
+    assert values["sender.acc"] == 5
+    assert isinstance(values["missing"], NameError)
+
+    cont = dbg.cont()
+    assert cont.kind == PauseReason.YIELD
+    assert dbg.process.name == "withdraw@second"
+    assert dbg.evaluate("sender.acc + 1") == 4
+
+    while not dbg.done:
+        dbg.step()
+    assert alice.acc == 2
+    assert dbg.step().kind == PauseReason.DONE
+
+
+def test_step_back():
+    dbg, alice, first = setup()
+    assert not dbg.step_back()
+
+    dbg.step_line()
+    dbg.step_line()
+    assert alice.acc == 3
+    stack = dbg.stack()
+    pc = dbg.interp.pc
+
+    dbg.step()
+    assert dbg.step_back()
+    assert dbg.interp.pc == pc
+    assert dbg.stack() == stack
+
+    assert dbg.cont().kind == PauseReason.YIELD
+    assert dbg.process.name == "withdraw@second"
+
+    # Back over the yield and the write to the object
+    while dbg.line() != first + 1 or dbg.interp.pc != 0:
+        assert dbg.step_back()
+    assert alice.acc == 5
+    assert dbg.variables() == {"sender": alice, "amount": 1}
+    assert dbg.process.name == "withdraw@start"
+    assert not dbg.step_back()
+
+    while not dbg.done:
+        dbg.step()
+    assert alice.acc == 2
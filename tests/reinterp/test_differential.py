@@ -0,0 +1,140 @@
+from hypothesis import given
+from hypothesis import settings
+from hypothesis.strategies import integers
+from hypothesis.strategies import one_of
+from hypothesis.strategies import sampled_from
+from hypothesis.strategies import tuples
+
+from timewinder.reinterp.interpreter import Interpreter
+
+
+def run_interpreted(func, *args):
+    interp = Interpreter(func, list(args))
+    # Yields only mark atomic steps, so run straight through them
+    for _ in range(10000):
+        if interp.pc < 0:
+            return interp.return_val
+        interp.interpret_instruction()
+    raise AssertionError(f"{func.__name__} didn't return")
+
+
+def outcome(run, func, *args):
+    try:
+        return ("value", run(func, *args))
+    except Exception as e:
+        return ("raised", type(e))
+
+
+def native(func, *args):
+    return func(*args)
+
+
+def assert_same(func, *args):
+    expected = outcome(native, func, *args)
+    got = outcome(run_interpreted, func, *args)
+    assert got == expected, f"{func.__name__}{args}: expected {expected}, got {got}"
+
+
+def arithmetic(a, b):
+    c = a + b
+    d = c - a
+    return d - b + 2
+
+
+def compare(a, b):
+    if a < b:
+        return "less"
+    if a >= b + 10:
+        return "much more"
+    return a == b
+
+
+def loop(a, b):
+    total = 0
+    while total < a:
+        total = total + b
+    return total
+
+
+def for_loop(a, b):
+    total = 0
+    for i in range(a, b):
+        total = total + i
+    for c in str(a):
+        total = total + len(c)
+    return total
+
+
+def subscript(a, b):
+    xs = "abcdefgh"
+    return xs[a:b] + xs[a]
+
+
+def method(a, b):
+    return str(a).join("xy").upper().rjust(b)
+
+
+def functions(a, b):
+    def scale(x, by=2):
+        return x * by + a
+
+    pick = max if a < b else min
+    return pick(scale(b), scale(a, by=b), key=lambda x: -x)
+
+
+def collections(a, b):
+    pairs = {"lo": min(a, b), "hi": max(a, b)}
+    steps = [i for i in range(pairs["lo"], pairs["hi"])]
+    return [a, b, *steps], {a: b}, (pairs["lo"], len(steps))
+
+
+def raises(a, b):
+    return a - "b"
+
+
+PROGRAMS = [
+    arithmetic,
+    compare,
+    loop,
+    for_loop,
+    subscript,
+    method,
+    functions,
+    collections,
+    raises,
+]
+
+
+def test_programs():
+    for func in PROGRAMS:
+        for a, b in [(0, 0), (1, 2), (5, 3), (2, 20), (-1, 1)]:
+            if func is loop and b <= 0:
+                continue
+            assert_same(func, a, b)
+
+
+# Expressions built only from what the interpreter supports so far
+atoms = one_of(sampled_from(["a", "b"]), integers(-5, 5).map(str))
+
+
+def combine(t):
+    op, lhs, rhs, cond = t
+    if op == "if":
+        return f"({lhs} if {cond} else {rhs})"
+    return f"({lhs} {op} {rhs})"
+
+
+def expressions(depth):
+    if depth == 0:
+        return atoms
+    inner = expressions(depth - 1)
+    ops = sampled_from(["+", "-", "==", "!=", "<", "<=", ">", ">=", "if"])
+    return one_of(atoms, tuples(ops, inner, inner, inner).map(combine))
+
+
+@given(expressions(3), integers(-10, 10), integers(-10, 10))
+@settings(max_examples=300)
+def test_random_expressions(expr, a, b):
+    func = eval(f"lambda a, b: {expr}")
+    func.__name__ = expr
+    assert_same(func, a, b)
@@ -0,0 +1,102 @@
+import dis
+
+import pytest
+
+from hypothesis import given
+from hypothesis import settings
+from hypothesis.strategies import integers
+from hypothesis.strategies import lists
+from hypothesis.strategies import one_of
+from hypothesis.strategies import sampled_from
+from hypothesis.strategies import tuples
+
+from timewinder.reinterp.interpreter import Interpreter
+from timewinder.reinterp.opcodes import InterpreterError
+from timewinder.reinterp.opcodes import OpcodeInterpreter
+from timewinder.reinterp.opcodes import UnsupportedInstruction
+
+
+# Opcodes whose operands are all ints and bools, so the only failures left
+# are malformed bytecode.
+NO_ARG = [
+    "NOP",
+    "POP_TOP",
+    "DUP_TOP",
+    "ROT_TWO",
+    "BINARY_ADD",
+    "BINARY_SUBTRACT",
+    "RETURN_VALUE",
+    "YIELD_VALUE",
+]
+JUMPS = ["POP_JUMP_IF_FALSE", "POP_JUMP_IF_TRUE", "JUMP_ABSOLUTE"]
+
+
+def make(opname, argval=None):
+    arg = None if argval is None else 0
+    return (opname, arg, argval)
+
+
+ops = one_of(
+    sampled_from(NO_ARG).map(make),
+    tuples(sampled_from(JUMPS), integers(-2, 40)).map(lambda t: make(*t)),
+    integers(-3, 3).map(lambda v: make("LOAD_CONST", v)),
+    sampled_from(["a", "b"]).map(lambda v: make("LOAD_FAST", v)),
+    sampled_from(["a", "b"]).map(lambda v: make("STORE_FAST", v)),
+    sampled_from(["==", "<", ">=", "in"]).map(lambda v: make("COMPARE_OP", v)),
+)
+
+
+def build(program):
+    def f():
+        pass
+
+    interp = Interpreter(f)
+    interp.state = {"a": 1}
+    insts = [
+        dis.Instruction(op, dis.opmap[op], arg, argval, "", 2 * i, None, False)
+        for i, (op, arg, argval) in enumerate(program)
+    ]
+    interp.ops = OpcodeInterpreter(interp, insts)
+    return interp
+
+
+@given(lists(ops, min_size=0, max_size=20))
+@settings(max_examples=500)
+def test_malformed_bytecode_raises_interpreter_error(program):
+    interp = build(program)
+    try:
+        for _ in range(100):
+            if interp.pc < 0:
+                break
+            interp.interpret_instruction()
+    except InterpreterError:
+        pass
+    except LookupError:
+        # Reading a local that was never stored is an error in the model
+        pass
+
+
+def test_errors():
+    interp = build([make("POP_TOP")])
+    with pytest.raises(InterpreterError, match="empty stack"):
+        interp.interpret_instruction()
+
+    interp = build([make("JUMP_ABSOLUTE", 7)])
+    with pytest.raises(InterpreterError, match="jump to 7"):
+        interp.interpret_instruction()
+
+    interp = build([make("LOAD_CONST", 1), make("BUILD_SLICE", 4)])
+    interp.interpret_instruction()
+    with pytest.raises(InterpreterError, match="BUILD_SLICE of 4"):
+        interp.interpret_instruction()
+
+    interp = build([make("NOP")])
+    interp.interpret_instruction()
+    with pytest.raises(InterpreterError, match="no instruction"):
+        interp.interpret_instruction()
+
+    interp = build([make("SETUP_FINALLY", 0)])
+    with pytest.raises(NotImplementedError):
+        interp.interpret_instruction()
+    with pytest.raises(UnsupportedInstruction):
+        interp.interpret_instruction()
@@ -1,8 +1,14 @@
+import itertools
+
 import pytest
+import timewinder
 
 from timewinder.reinterp.process import BytecodeProcess
 from timewinder.closure import Closure
 from timewinder.functions import Await
+from timewinder.process import ProcessException
+from timewinder.result import Status
+from timewinder.statetree import SavedModule
 
 
 def bytecodeClosure(func):
@@ -78,3 +84,382 @@ def test_yield():
     proc.execute(None)
     assert proc.name == "f@Step 2"
     proc.execute(None)
+
+
+def test_disassemble():
+    def f(x):
+        y = x + 1
+        return y
+
+    proc = bytecodeClosure(f)(2)
+    listing = proc.interp.disassemble().splitlines()
+    assert "-->" in listing[0]
+    assert "LOAD_FAST" in listing[0]
+    assert listing[0].split()[0] == str(f.__code__.co_firstlineno + 1)
+
+    with_source = proc.interp.disassemble(with_source=True)
+    assert "y = x + 1" in with_source
+    assert "return y" in with_source
+
+
+def test_for_loops():
+    def f(xs, d, s):
+        total = 0
+        for x in xs:
+            total = total + x
+        for k in d:
+            total = total + d[k]
+        for i in range(3):
+            total = total + i
+        out = ""
+        for c in s:
+            out = c + out
+        return str(total) + out
+
+    proc = bytecodeClosure(f)([1, 2], {"a": 10, "b": 20}, "abc")
+    proc.execute(None)
+    assert proc.interp.return_val == f([1, 2], {"a": 10, "b": 20}, "abc")
+
+
+def test_for_loop_across_yields():
+    @timewinder.object
+    class Counter:
+        def __init__(self):
+            self.seen = 0
+
+    c = Counter()
+
+    @timewinder.process
+    def visit(counter, names):
+        for name in names:
+            yield name
+            counter.seen = counter.seen + 1
+
+    ev = timewinder.Evaluator(objects=[c], threads=[visit(c, ["a", "b", "c"])])
+    ev.evaluate(steps=None)
+    # The initial state, paused at each name, then finished
+    assert ev.stats.states == 5
+    assert c.seen == 3
+
+
+def test_iterator_values():
+    def f(d):
+        keys = [k for k in d]
+        return keys, len(d)
+
+    # A dict of the model's own is never taken for an iterator
+    d = {"__iterator__": [1, 2]}
+    proc = bytecodeClosure(f)(d)
+    proc.execute(None)
+    assert proc.interp.return_val == (["__iterator__"], 1)
+
+    def g():
+        for i in itertools.count():
+            if i > 5:
+                return i
+
+    proc = bytecodeClosure(g)()
+    with pytest.raises(ProcessException, match="at most 10000 values"):
+        proc.execute(None)
+
+
+def test_functions():
+    def f(xs, offset):
+        def shifted(x, by=1):
+            return x + offset + by
+
+        def neg(x):
+            return -x
+
+        table = dict()
+        table["shift"] = shifted
+        table["neg"] = neg
+        total = table["shift"](1) + table["shift"](1, by=0)
+        ordered = sorted(xs, key=table["neg"])
+        return total, ordered, list(map(lambda x: x * offset, xs))
+
+    proc = bytecodeClosure(f)([2, 3, 1], 10)
+    proc.execute(None)
+    assert proc.interp.return_val == f([2, 3, 1], 10)
+
+    def g(d):
+        return d["__function__"], len(d)
+
+    # Nor is one taken for a function
+    proc = bytecodeClosure(g)({"__function__": 0, "name": "h"})
+    proc.execute(None)
+    assert proc.interp.return_val == (0, 2)
+
+
+def test_functions_across_yields():
+    @timewinder.object
+    class Account:
+        def __init__(self):
+            self.acc = 5
+
+    a = Account()
+
+    @timewinder.process
+    def withdraw(account, amount):
+        def take(acc):
+            return acc - amount
+
+        def update():
+            account.acc = take(account.acc)
+
+        yield "take"
+        update()
+
+    ev = timewinder.Evaluator(objects=[a], threads=[withdraw(a, 2)])
+    ev.evaluate(steps=None)
+    assert a.acc == 3
+
+
+def test_collections():
+    def f(a, b):
+        xs = [a, b]
+        ys = [1, 2, 3, a]
+        named = {"a": a, "b": b}
+        keyed = {a: "a", b: "b"}
+        squares = [x * x for x in ys if x > 1]
+        inverted = {v: k for k, v in named.items()}
+        spliced = [*xs, *ys]
+        merged = {**named, "c": a}
+        return xs + ys, named, keyed, squares, inverted, (a, *xs), spliced, merged
+
+    proc = bytecodeClosure(f)(4, 5)
+    proc.execute(None)
+    assert proc.interp.return_val == f(4, 5)
+
+
+def test_strings():
+    def f(a):
+        escaped = "a\tb\n\\ \"q\" \x41"
+        raw = r"C:\new\t \d+"
+        unicode = "caf\u00e9 \N{SNOWMAN} \U0001F600"
+        data = b"\x00\xff"
+        formatted = f"{a}:{a!r:>5}|{len(raw):03d}"
+        multi = """two
+lines"""
+        return escaped, raw, unicode, data, formatted, multi
+
+    proc = bytecodeClosure(f)("x")
+    proc.execute(None)
+    assert proc.interp.return_val == f("x")
+    assert proc.interp.return_val[1] == "C:\\new\\t \\d+"
+    assert proc.interp.return_val[4] == "x:  'x'|012"
+
+
+def test_pretty():
+    @timewinder.object
+    class Account:
+        def __init__(self):
+            self.acc = 5
+
+    a = Account()
+
+    @timewinder.process
+    def withdraw(account, amounts):
+        total = 0
+        yield "withdraw"
+        account.acc = account.acc - total
+
+    ev = timewinder.Evaluator(objects=[a], threads=[withdraw(a, {"x": [1] * 30})])
+    proc = ev.threads[0]
+    proc.execute(ev.state_controller)
+    lines = proc.pretty().splitlines()
+    first = withdraw.func.__code__.co_firstlineno
+    assert lines[0].startswith("withdraw@withdraw at ")
+    assert lines[0].endswith(f":{first + 3}: yield \"withdraw\"")
+    assert lines[1] == f"  account = {a.name}"
+    # Long values wrap, indented under their name
+    assert lines[2].startswith("  amounts = {'x': [1,")
+    assert lines[3] == " " * 19 + "1,"
+    assert "  total = 0" in lines
+    assert lines[-1] == "  stack: ['withdraw']"
+
+    proc.execute(ev.state_controller)
+    assert proc.pretty().startswith("withdraw@withdraw (finished)")
+
+
+LIMIT = 3
+UNSET = None
+
+
+def test_scopes():
+    step = 2
+
+    def f(x):
+        total = x + step
+        if not UNSET:
+            total = total + LIMIT
+        return min(total, 100)
+
+    proc = bytecodeClosure(f)(1)
+    proc.execute(None)
+    assert proc.interp.return_val == 6
+
+    def g():
+        return missing_name  # noqa: F821
+
+    proc = bytecodeClosure(g)()
+    with pytest.raises(ProcessException, match="Couldn.t find global missing_name"):
+        proc.execute(None)
+
+    # Defined after the process was made
+    proc = bytecodeClosure(g)()
+    globals()["missing_name"] = 1
+    try:
+        proc.execute(None)
+    finally:
+        del globals()["missing_name"]
+    assert proc.interp.return_val == 1
+
+
+def test_store_global():
+    def f():
+        global LIMIT
+        LIMIT = 4
+
+    proc = bytecodeClosure(f)()
+    with pytest.raises(ProcessException, match="can.t assign global LIMIT"):
+        proc.execute(None)
+    assert LIMIT == 3
+
+
+def test_import_math():
+    @timewinder.object
+    class Link:
+        def __init__(self):
+            self.latency = 0.0
+
+    link = Link()
+
+    @timewinder.process
+    def measure(link, samples):
+        import math
+        from math import sqrt
+
+        yield "measure"
+        total = math.fsum(samples)
+        link.latency = math.floor(sqrt(total) + math.pi)
+
+    ev = timewinder.Evaluator(objects=[link], threads=[measure(link, [1.0, 3.0])])
+    proc = ev.threads[0]
+    proc.execute(ev.state_controller)
+    assert proc.interp.state["math"] == SavedModule("math")
+    proc.execute(ev.state_controller)
+    assert ev.state_controller.tree[link.name].latency == 5
+
+    def f():
+        import os
+
+        return os.getcwd()
+
+    proc = bytecodeClosure(f)()
+    with pytest.raises(ProcessException, match="processes can't import os"):
+        proc.execute(None)
+
+    def g(d):
+        return d["__module__"], len(d)
+
+    # A dict of the model's own is never taken for a module
+    proc = bytecodeClosure(g)({"__module__": "math", "attr": "pi"})
+    proc.execute(None)
+    assert proc.interp.return_val == ("math", 2)
+
+
+def test_import_json():
+    @timewinder.object
+    class Mailbox:
+        def __init__(self):
+            self.messages = []
+
+    box = Mailbox()
+
+    @timewinder.process
+    def send(box, payload):
+        from json import dumps
+
+        message = dumps(payload, sort_keys=True)
+        yield "send"
+        box.messages.append(message)
+        yield "receive"
+        import json
+
+        received = json.loads(box.messages[0])
+
+    ev = timewinder.Evaluator(
+        objects=[box], threads=[send(box, {"b": [1, 2], "a": None})]
+    )
+    proc = ev.threads[0]
+    for _ in range(3):
+        proc.execute(ev.state_controller)
+    assert ev.state_controller.tree[box.name].messages == ['{"a": null, "b": [1, 2]}']
+    assert proc.interp.state["received"] == {"a": None, "b": [1, 2]}
+
+
+def test_higher_order_builtins():
+    def f(xs):
+        from functools import reduce
+
+        doubled = list(map(lambda x: x + x, xs))
+        odd = [x for x in filter(lambda x: x % 2, xs)]
+        total = reduce(lambda a, b: a + b, doubled, 0)
+        return doubled, odd, total, sorted(map(lambda a, b: a - b, xs, doubled))
+
+    proc = bytecodeClosure(f)([1, 2, 3])
+    proc.execute(None)
+    assert proc.interp.return_val == f([1, 2, 3])
+
+    @timewinder.process
+    def pending(xs):
+        evens = filter(lambda x: x % 2 == 0, xs)
+        yield "split"
+        for x in evens:
+            yield "take"
+
+    ev = timewinder.Evaluator(threads=[pending([1, 2, 3, 4])])
+    res = ev.check(steps=None)
+    assert res.status == Status.PASS
+    assert res.stats.final_states == 1
+
+
+def ratio(a, b):
+    return a / b
+
+
+def average(xs):
+    return ratio(sum(xs), len(xs))
+
+
+def test_backtrace():
+    @timewinder.process
+    def report(xs):
+        yield "report"
+        mean = average(xs)
+
+    ev = timewinder.Evaluator(threads=[report([])])
+    res = ev.check()
+    assert res.status == Status.ERROR
+    assert "division by zero" in res.error
+
+    e = None
+    proc = report([])
+    proc.execute(None)
+    try:
+        proc.execute(None)
+    except ProcessException as caught:
+        e = caught
+    assert e is not None
+    names = [(name, line) for name, _, line in e.backtrace]
+    first = report.func.__code__.co_firstlineno
+    assert names == [
+        ("report", first + 3),
+        ("average", average.__code__.co_firstlineno + 1),
+        ("ratio", ratio.__code__.co_firstlineno + 1),
+    ]
+    assert all(filename == __file__ for _, filename, _ in e.backtrace)
+    assert repr(e).endswith(
+        f"  ratio at {__file__}:{ratio.__code__.co_firstlineno + 1}"
+    )
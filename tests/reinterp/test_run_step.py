@@ -0,0 +1,86 @@
+import timewinder
+
+from timewinder.functions import Await
+from timewinder.functions import Either
+from timewinder.reinterp.process import BytecodeProcess
+from timewinder.reinterp.process import StepKind
+from timewinder.closure import Closure
+
+
+def test_fuel():
+    def f(n):
+        total = 0
+        for i in range(n):
+            total = total + i
+        yield "counted"
+        return total
+
+    proc = Closure(f, BytecodeProcess)(10)
+    out = proc.run_step(None, fuel=5)
+    assert out.kind == StepKind.OUT_OF_FUEL
+    out = proc.run_step(None, fuel=5)
+    assert out.kind == StepKind.OUT_OF_FUEL
+    out = proc.run_step(None)
+    assert out.kind == StepKind.YIELDED
+    assert out.label == "counted"
+    assert proc.run_step(None).kind == StepKind.DONE
+    assert proc.interp.return_val == 45
+
+
+def test_host_call():
+    def f():
+        if Either():
+            yield "left"
+        else:
+            yield "right"
+
+    proc = Closure(f, BytecodeProcess)()
+    out = proc.run_step(None)
+    assert out.kind == StepKind.HOST_CALL
+    assert out.call.tag == "either"
+    proc.resume_host_call(False)
+    out = proc.run_step(None)
+    assert out.kind == StepKind.YIELDED
+    assert out.label == "right"
+
+
+@timewinder.object
+class Flag:
+    def __init__(self):
+        self.set = False
+        self.seen = False
+
+
+def test_await_blocks():
+    flag = Flag()
+
+    @timewinder.process
+    def setter(flag):
+        yield "set"
+        flag.set = True
+
+    @timewinder.process
+    def waiter(flag):
+        Await(flag.set)
+        flag.seen = True
+
+    seen_only_when_set = timewinder.ForAll(Flag, lambda f: f.set or not f.seen)
+    ev = timewinder.Evaluator(
+        objects=[flag],
+        threads=[setter(flag), waiter(flag)],
+        specs=[seen_only_when_set],
+    )
+    ev.evaluate(steps=None)
+    # The waiter stays blocked until the setter has run
+    assert ev.stats.final_states == 1
+
+    other = Flag()
+    ev = timewinder.Evaluator(objects=[other], threads=[waiter(other)])
+    proc = ev.threads[0]
+    for _ in range(2):
+        out = proc.run_step(ev.state_controller)
+        assert out.kind == StepKind.BLOCKED
+        assert out.guard[1] == waiter.func.__code__.co_firstlineno + 2
+    other.set = True
+    assert proc.run_step(ev.state_controller).kind == StepKind.DONE
+    assert other.seen
@@ -0,0 +1,140 @@
+import dis
+import warnings
+
+import pytest
+import timewinder
+
+from timewinder.closure import Closure
+from timewinder.reinterp.opcodes import UnsupportedInstruction
+from timewinder.reinterp.process import BytecodeProcess
+from timewinder.reinterp.verify import TimewinderWarning
+from timewinder.reinterp.verify import VerificationError
+from timewinder.reinterp.verify import check_names
+from timewinder.reinterp.verify import lint
+from timewinder.reinterp.verify import verify
+
+
+def insts(*program):
+    out = []
+    for i, (op, argval) in enumerate(program):
+        arg = None if dis.opmap[op] < dis.HAVE_ARGUMENT else 0
+        out.append(
+            dis.Instruction(op, dis.opmap[op], arg, argval, "", 2 * i, None, False)
+        )
+    return out
+
+
+def test_verify_compiled():
+    def f(xs):
+        total = 0
+        for x in xs:
+            if x > 1:
+                total = total + x
+        return total
+
+    assert verify(dis.get_instructions(f)) == []
+
+
+def test_verify_errors():
+    (p,) = verify(insts(("LOAD_CONST", 1), ("JUMP_ABSOLUTE", 7)))
+    assert (p.pc, p.msg) == (1, "jump to 7, which isn't an instruction")
+
+    (p,) = verify(insts(("LOAD_CONST", 1), ("POP_TOP", None), ("POP_TOP", None)))
+    assert (p.pc, p.msg) == (2, "POP_TOP pops from an empty stack")
+
+    # One path pushes a value before the join, the other doesn't
+    program = insts(
+        ("LOAD_CONST", True),
+        ("POP_JUMP_IF_FALSE", 8),
+        ("LOAD_CONST", 1),
+        ("NOP", None),
+        ("LOAD_CONST", None),
+        ("RETURN_VALUE", None),
+    )
+    (p,) = verify(program)
+    assert p.pc == 4
+    assert p.msg.startswith("reached with")
+
+    problems = verify(insts(("SETUP_FINALLY", 2), ("NOP", None)))
+    assert isinstance(problems[0], UnsupportedInstruction)
+
+
+def test_process_verified_when_created():
+    def f(x):
+        yield "check"
+        with x:
+            return 0
+
+    with pytest.raises(VerificationError) as e:
+        Closure(f, BytecodeProcess)(1)
+    line = f.__code__.co_firstlineno + 2
+    assert f"{__file__}:{line} (pc 4): SETUP_WITH isn't supported yet" in str(e.value)
+
+
+def test_check_names():
+    def f(x, *args, flag=False, **kwargs):
+        if x:
+            y = 1
+        else:
+            # Only some paths delete x, so reading it later may be fine
+            del x
+        total = y + len(args) + len(kwargs) + flag
+        print(z)  # noqa: F821
+        z = total
+        return x + missing  # noqa: F821
+
+    problems = check_names(f)
+    assert [p.msg for p in problems] == [
+        "z is read before it's assigned",
+        "missing isn't defined",
+    ]
+
+
+def test_globals_verified_when_evaluated():
+    @timewinder.process
+    def f():
+        yield "read"
+        return LATE + 1  # noqa: F821
+
+    # Made before the global is defined, as a module may do
+    ev = timewinder.Evaluator(threads=[f()])
+    with pytest.raises(VerificationError, match="LATE isn't defined"):
+        ev.check()
+    globals()["LATE"] = 1
+    try:
+        assert ev.check().error is None
+    finally:
+        del globals()["LATE"]
+
+
+def test_lint():
+    def f(xs):
+        len = 0
+        _scratch = 1
+        while True:
+            for x in xs:
+                len = len + 1
+            yield "loop"
+        return len
+
+    assert [(d.rule, d.msg) for d in lint(f)] == [
+        ("shadowing", "len shadows a global of the same name"),
+        (
+            "unused-variable",
+            "x is assigned but never read, though it still makes states distinct",
+        ),
+        ("unreachable", "this code can never run"),
+    ]
+
+    with warnings.catch_warnings(record=True) as caught:
+        warnings.simplefilter("always")
+        Closure(f, BytecodeProcess)([])
+    # Only lint reports unused variables, since specs may read them
+    assert [w.category for w in caught] == [TimewinderWarning] * 2
+    assert caught[0].lineno == f.__code__.co_firstlineno + 1
+
+    # Once per function, however many processes are built from it
+    with warnings.catch_warnings(record=True) as caught:
+        warnings.simplefilter("always")
+        Closure(f, BytecodeProcess)([])
+    assert caught == []
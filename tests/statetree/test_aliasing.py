@@ -0,0 +1,54 @@
+import copy
+
+import timewinder
+
+
+@timewinder.object
+class Queue:
+    def __init__(self, items):
+        self.items = items
+
+
+def test_references_end_at_yields():
+    q = Queue([])
+
+    @timewinder.process
+    def push(queue):
+        items = queue.items
+        items.append("within a step")
+        yield "later"
+        items.append("after a yield")
+
+    ev = timewinder.Evaluator(objects=[q], threads=[push(q)])
+    ev.evaluate(steps=None)
+    assert q.items == ["within a step"]
+
+
+def test_shared_values_are_copied():
+    shared = [1]
+    a = Queue(shared)
+    b = Queue(shared)
+
+    @timewinder.process
+    def grow(queue):
+        yield "grow"
+        queue.items.append(2)
+
+    ev = timewinder.Evaluator(objects=[a, b], threads=[grow(a)])
+    ev.evaluate(steps=None)
+    assert a.items == [1, 2]
+    assert b.items == [1]
+
+
+def test_deepcopy_in_processes():
+    q = Queue([[1]])
+
+    @timewinder.process
+    def snapshot(queue):
+        before = copy.deepcopy(queue.items)
+        queue.items[0].append(2)
+        queue.items.append(before)
+
+    ev = timewinder.Evaluator(objects=[q], threads=[snapshot(q)])
+    ev.evaluate(steps=None)
+    assert q.items == [[1, 2], [[1]]]
@@ -0,0 +1,58 @@
+import timewinder
+
+from timewinder.result import Status
+from timewinder.statetree import Canonicalizer
+
+
+@timewinder.object
+class Lock:
+    def __init__(self):
+        self.held = False
+
+
+def model(canonicalizer=None):
+    lock = Lock()
+
+    @timewinder.process
+    def worker(lock):
+        attempts = 0
+        while lock.held:
+            # Spinning makes the state space unbounded, unless attempts is ignored
+            attempts = attempts + 1
+            yield "spin"
+        lock.held = True
+        yield "release"
+        lock.held = False
+
+    return timewinder.Evaluator(
+        objects=[lock],
+        threads=[worker(lock), worker(lock)],
+        canonicalizer=canonicalizer,
+    )
+
+
+def test_canonical():
+    c = Canonicalizer(ignore=["_thread_*.state.attempts", "*.skip"])
+    state = {
+        "b": {"skip": 1, "x": [{"skip": 2}]},
+        "_thread_0": {"pc": 3, "state": {"attempts": 2, "i": 1}},
+        "a": {"skip": 3},
+    }
+    out = c.canonical(state)
+    assert out == {
+        "_thread_0": {"pc": 3, "state": {"i": 1}},
+        "a": {},
+        "b": {"x": [{"skip": 2}]},
+    }
+    assert list(out) == ["_thread_0", "a", "b"]
+
+
+def test_ignored_values_merge_states():
+    full = model()
+    result = full.check(steps=10)
+    assert result.status == Status.PASS and result.bounded
+
+    ignoring = model(Canonicalizer(ignore=["_thread_*.state.attempts"]))
+    result = ignoring.check()
+    assert result.status == Status.PASS and not result.bounded
+    assert ignoring.stats.states < full.stats.states
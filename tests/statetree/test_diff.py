@@ -0,0 +1,45 @@
+import timewinder
+
+from timewinder.statetree import diff_states
+
+
+@timewinder.object
+class Account:
+    def __init__(self, amt):
+        self.acc = amt
+
+
+def test_diff_states():
+    old = {"a": {"x": 1, "gone": 2, "nested": {"k": 1, "same": 0}}, "b": {"y": 1}}
+    new = {"a": {"x": 3, "new": [1], "nested": {"k": 2, "same": 0}}, "b": {"y": 1}}
+    d = diff_states(old, new)
+    assert list(d.envs) == ["a"]
+    a = d.envs["a"]
+    assert a.added == {"new": [1]}
+    assert a.removed == {"gone": 2}
+    assert a.changed == {"x": (1, 3), "nested.k": (1, 2)}
+    assert d.paths() == ["a.gone", "a.nested.k", "a.new", "a.x"]
+    assert not diff_states(old, old)
+
+
+def test_controller_diff():
+    alice = Account(5)
+
+    @timewinder.process
+    def withdraw(sender, amount):
+        total = amount
+        sender.acc = sender.acc - total
+        yield "done"
+
+    ev = timewinder.Evaluator(objects=[alice], threads=[withdraw(alice, 2)])
+    sc = ev.state_controller
+    before = list(sc.commit())[0]
+    ev.threads[0].execute(sc)
+    after = list(sc.commit())[0]
+
+    d = sc.diff(before, after)
+    assert d.envs[alice.name].changed == {"acc": (5, 3)}
+    thread = d.envs["_thread_0"]
+    assert thread.added == {"state.total": 2}
+    assert thread.changed["_stepname"] == ("start", "done")
+    assert f"  {alice.name}.acc: 5 -> 3" in str(d).splitlines()
@@ -0,0 +1,53 @@
+import copy
+import pickle
+
+import pytest
+import timewinder
+
+from timewinder.export import trace_to_itf
+from timewinder.result import Status
+from timewinder.statetree import Record
+
+
+def test_record():
+    pos = Record(y=2, x=1)
+    assert (pos.x, pos.y) == (1, 2)
+    assert pos == Record(x=1, y=2) and pos != Record(x=1, y=3)
+    assert hash(pos) == hash(Record(x=1, y=2))
+    assert pos.replace(x=5) == Record(x=5, y=2)
+    assert pos.x == 1
+    assert repr(pos) == "Record(x=1, y=2)"
+    assert pickle.loads(pickle.dumps(pos)) == pos
+    assert copy.deepcopy(pos) == pos
+    with pytest.raises(AttributeError, match="immutable"):
+        pos.x = 2
+    with pytest.raises(AttributeError, match="no field z"):
+        pos.replace(z=1)
+    with pytest.raises(AttributeError, match="no field z"):
+        pos.z
+
+
+@timewinder.object
+class Robot:
+    def __init__(self):
+        self.pos = Record(x=0, y=0)
+
+
+@timewinder.process
+def move(robot):
+    robot.pos = robot.pos.replace(x=robot.pos.x + 1)
+    yield "up"
+    robot.pos = robot.pos.replace(y=robot.pos.y + 1)
+
+
+def test_records_in_states():
+    robot = Robot()
+    on_grid = timewinder.ForAll(Robot, lambda r: r.pos.x <= 2 and r.pos.y <= 1)
+    ev = timewinder.Evaluator(
+        objects=[robot], threads=[move(robot), move(robot)], specs=[on_grid]
+    )
+    result = ev.check(steps=None)
+    assert result.status == Status.FAIL
+    assert result.trace[-1].state["robot"]["pos"] == Record(x=2, y=2)
+    itf = trace_to_itf(result.trace)
+    assert itf["states"][-1]["robot"]["pos"] == {"x": 2, "y": 2}
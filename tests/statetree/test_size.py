@@ -0,0 +1,57 @@
+import sys
+
+import timewinder
+
+from timewinder.statetree import Hash
+from timewinder.statetree import MemoryCAS
+from timewinder.statetree import StateController
+from timewinder.statetree import approx_size_bytes
+
+
+def test_approx_size_bytes():
+    assert approx_size_bytes(1) == sys.getsizeof(1)
+    assert approx_size_bytes([1, 2]) == sys.getsizeof([1, 2]) + 2 * sys.getsizeof(1)
+    assert approx_size_bytes({"a": [1]}) > approx_size_bytes({"a": 1})
+    # Only a reference to a node stored elsewhere
+    assert approx_size_bytes(Hash(bytes(32))) == 8
+
+
+@timewinder.object
+class Buffer:
+    def __init__(self, items):
+        self.items = items
+
+
+def test_cas_accounting():
+    small = Buffer([1])
+    big = Buffer(list(range(100)))
+    sc = StateController(MemoryCAS())
+    sc.mount("small", small)
+    sc.mount("big", big)
+    (h,) = sc.commit()
+    cas = sc.cas
+    root = cas.get(h)
+    assert cas.tree_size(root["big"]) > cas.tree_size(root["small"])
+    assert cas.tree_size(h) > cas.tree_size(root["big"]) + cas.tree_size(root["small"])
+    assert cas.bytes_used() == cas.tree_size(h)
+    used = cas.bytes_used()
+    sc.commit()
+    assert cas.bytes_used() == used
+
+
+def test_largest_states():
+    buf = Buffer([])
+
+    @timewinder.process
+    def fill(buf):
+        buf.items.append(1)
+        yield "more"
+        buf.items.append(2)
+
+    ev = timewinder.Evaluator(objects=[buf], threads=[fill(buf)])
+    ev.evaluate(steps=None)
+    sizes = [size for _, size in ev.largest_states]
+    assert len(sizes) == 3
+    assert sizes == sorted(sizes, reverse=True)
+    assert ev.stats.largest_state_bytes == sizes[0]
+    assert ev.stats.cas_bytes > sizes[0]
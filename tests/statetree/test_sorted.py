@@ -0,0 +1,65 @@
+import pickle
+
+import timewinder
+
+from timewinder.statetree import Canonicalizer
+from timewinder.statetree import SortedMap
+from timewinder.statetree import SortedSet
+
+
+def test_sorted_set():
+    s = SortedSet([3, 1, 2])
+    assert list(s) == [1, 2, 3]
+    assert s[0] == 1 and s[-1] == 3
+    assert s.including(0).excluding(3) == {0, 1, 2}
+    assert isinstance(s | {4}, SortedSet)
+    assert isinstance(s - {1}, SortedSet)
+    assert list(s & {3, 2}) == [2, 3]
+    assert repr(s) == "SortedSet([1, 2, 3])"
+    assert list(pickle.loads(pickle.dumps(s))) == [1, 2, 3]
+    mixed = SortedSet([timewinder.Symbol("b"), timewinder.Symbol("a")])
+    assert repr(mixed) == "SortedSet([a, b])"
+
+
+def test_sorted_map():
+    m = SortedMap()
+    m["b"] = 2
+    m["a"] = 1
+    assert list(m) == ["a", "b"]
+    assert m.items() == [("a", 1), ("b", 2)]
+    assert repr(m) == "SortedMap({'a': 1, 'b': 2})"
+    canonical = Canonicalizer().canonical({"y": {"b": 1, "a": 2}, "x": 0})
+    assert isinstance(canonical["y"], SortedMap)
+    assert list(canonical) == ["x", "y"]
+
+
+@timewinder.object
+class Registry:
+    def __init__(self):
+        self.seen = SortedSet()
+        self.order = SortedMap()
+        self.log = []
+
+
+@timewinder.process
+def register(reg, name):
+    reg.seen = reg.seen.including(name)
+    reg.order[name] = len(reg.order)
+    yield "log"
+    for k in reg.order:
+        reg.log.append(k)
+
+
+def test_sorted_values_in_states():
+    reg = Registry()
+    ev = timewinder.Evaluator(
+        objects=[reg],
+        threads=[register(reg, "b"), register(reg, "a")],
+        record_graph=True,
+    )
+    ev.evaluate(steps=None)
+    finals = [ev.state_controller.cas.restore(h)["reg"] for h in ev.graph.nodes]
+    logs = {tuple(r["log"]) for r in finals if len(r["log"]) == 4}
+    # However the names were registered, they're logged in sorted order
+    assert logs == {("a", "b", "a", "b")}
+    assert all(isinstance(r["seen"], SortedSet) for r in finals)
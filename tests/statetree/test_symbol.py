@@ -0,0 +1,63 @@
+import copy
+import pickle
+
+import pytest
+import timewinder
+
+from timewinder.loader import parse_constant
+from timewinder.statetree import Canonicalizer
+from timewinder.statetree import MemoryCAS
+from timewinder.statetree import StateController
+
+
+def test_symbols_are_interned_atoms():
+    r1, r2 = timewinder.symbols("r1, r2")
+    assert r1 is timewinder.Symbol("r1")
+    assert r1 == r1 and r1 != r2 and r1 != "r1"
+    assert copy.deepcopy([r1])[0] is r1
+    assert pickle.loads(pickle.dumps(r1)) is r1
+    assert repr([r1, r2]) == "[r1, r2]"
+    with pytest.raises(TypeError):
+        r1 + 1
+    with pytest.raises(TypeError):
+        r1 < r2
+    with pytest.raises(ValueError):
+        timewinder.Symbol("not one")
+
+
+def test_constants():
+    r1 = timewinder.Symbol("r1")
+    assert parse_constant("owner=@r1") == ("owner", r1)
+    assert parse_constant("res=@r1, @r2") == ("res", (r1, timewinder.Symbol("r2")))
+    assert parse_constant("name=r1") == ("name", "r1")
+
+
+NOBODY = timewinder.Symbol("nobody")
+
+
+@timewinder.object
+class Lock:
+    def __init__(self):
+        self.owner = NOBODY
+
+
+def test_symbols_in_states():
+    p1, p2 = timewinder.symbols("p1 p2")
+    lock = Lock()
+
+    @timewinder.process
+    def take(lock, me):
+        if lock.owner == NOBODY:
+            lock.owner = me
+
+    sc = StateController(MemoryCAS())
+    sc.mount("lock", lock)
+    lock.owner = p1
+    (h,) = sc.commit()
+    assert sc.cas.restore(h)["lock"]["owner"] is p1
+    assert Canonicalizer(ignore=["x"]).fingerprint(sc.cas, h) != h.bytes
+
+    lock = Lock()
+    ev = timewinder.Evaluator(objects=[lock], threads=[take(lock, p1), take(lock, p2)])
+    ev.evaluate(steps=None)
+    assert ev.stats.final_states == 2
@@ -0,0 +1,80 @@
+import timewinder
+
+from timewinder import checks
+from timewinder.result import Status
+
+
+@timewinder.object
+class Lock:
+    def __init__(self):
+        self.held = False
+
+
+@timewinder.object
+class Queue:
+    def __init__(self):
+        self.items = []
+
+
+def worker(lock, locked):
+    if locked:
+        while lock.held:
+            yield "wait"
+        lock.held = True
+    held = 1
+    yield "critical"
+    held = 0
+    lock.held = False
+
+
+def workers_model(locked, spec):
+    lock = Lock()
+    threads = [timewinder.process(worker)(lock, locked) for _ in range(2)]
+    return timewinder.Evaluator(objects=[lock], threads=threads, specs=[spec(threads)])
+
+
+def test_mutual_exclusion():
+    def spec(threads):
+        return checks.mutual_exclusion(threads, ["worker@critical"])
+
+    result = workers_model(False, spec).check()
+    assert result.status == Status.FAIL
+    assert result.violation == "[](mutual_exclusion)"
+    assert workers_model(True, spec).check().status == Status.PASS
+
+
+def test_aggregate():
+    def spec(threads):
+        return checks.aggregate(
+            threads, "held", lambda held: sum(held) <= 1, name="one_holder"
+        )
+
+    assert workers_model(False, spec).check().status == Status.FAIL
+    assert workers_model(True, spec).check().status == Status.PASS
+
+
+def test_bounded_and_no_duplicates():
+    @timewinder.process
+    def produce(q, item):
+        q.items.append(item)
+        yield "again"
+        q.items.append(item)
+
+    q = Queue()
+    threads = [produce(q, 1), produce(q, 2)]
+    spec = checks.bounded(Queue, "items", 3)
+    ev = timewinder.Evaluator(objects=[q], threads=threads, specs=[spec])
+    result = ev.check()
+    assert result.status == Status.FAIL
+    assert result.violation == "[](bounded_items)"
+
+    q = Queue()
+    ev = timewinder.Evaluator(
+        objects=[q],
+        threads=[produce(q, 1)],
+        specs=[checks.no_duplicates(Queue, "items")],
+    )
+    result = ev.check()
+    assert result.status == Status.FAIL
+    assert result.violation == "[](no_duplicate_items)"
+    assert len(result.trace) == 3
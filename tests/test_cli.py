@@ -0,0 +1,499 @@
+import contextlib
+import io
+import json
+import os
+
+from timewinder.cli import EXIT_BOUNDED
+from timewinder.cli import EXIT_ERROR
+from timewinder.cli import EXIT_PASS
+from timewinder.cli import EXIT_VIOLATION
+from timewinder.cli import _parser
+from timewinder.cli import main
+from timewinder.cli import watch_loop
+from timewinder.serve import Archive
+
+
+EXAMPLES = os.path.join(os.path.dirname(os.path.dirname(__file__)), "examples")
+
+MODEL = """
+import timewinder
+
+
+@timewinder.object
+class Account:
+    def __init__(self, amt):
+        self.acc = amt
+
+
+@timewinder.process
+def withdraw(sender, amount):
+    sender.acc = sender.acc - amount
+
+
+def model(amount=1):
+    alice = Account(5)
+    no_overdrafts = timewinder.ForAll(Account, lambda a: a.acc >= 0)
+    return timewinder.Evaluator(
+        objects=[alice],
+        threads=[withdraw(alice, amount)],
+        specs=[no_overdrafts],
+    )
+"""
+
+
+def run(*argv):
+    out = io.StringIO()
+    with contextlib.redirect_stdout(out):
+        code = main(list(argv))
+    return code, out.getvalue()
+
+
+def write_model(tmp_path):
+    path = tmp_path / "bank.py"
+    path.write_text(MODEL)
+    return str(path)
+
+
+def test_check_example():
+    path = os.path.join(EXAMPLES, "practical_tlaplus_chap1.py")
+    code, out = run("check", "-q", path)
+    assert code == EXIT_VIOLATION
+    assert out.startswith("Violation: [](no_overdrafts")
+
+
+def test_check_constants(tmp_path):
+    path = write_model(tmp_path)
+    code, out = run("check", "-q", path)
+    assert code == EXIT_PASS
+    assert out.startswith("No violations found.")
+
+    code, out = run("check", "-q", "-D", "amount=6", "--format", "json", path)
+    assert code == EXIT_VIOLATION
+    assert json.loads(out)["status"] == "fail"
+
+
+def test_exit_codes(tmp_path):
+    path = write_model(tmp_path)
+    code, out = run("check", "-q", "--format", "json", path)
+    assert code == EXIT_PASS
+    assert json.loads(out)["bound"] is None
+    code, out = run("check", "-q", "--steps", "1", "--format", "json", path)
+    assert code == EXIT_BOUNDED
+    doc = json.loads(out)
+    assert (doc["bounded"], doc["bound"]) == (True, "steps")
+    assert doc["properties"][0]["status"] == "bounded"
+    code, out = run("simulate", "-q", "--runs", "2", "--format", "json", path)
+    assert code == EXIT_BOUNDED
+    assert json.loads(out)["bound"] == "simulation"
+    with contextlib.redirect_stderr(io.StringIO()):
+        code, _ = run("check", "-q", str(tmp_path / "missing.py"))
+    assert code == EXIT_ERROR
+
+
+def test_check_output_file(tmp_path):
+    path = write_model(tmp_path)
+    report = str(tmp_path / "out.dot")
+    code, out = run("check", "-q", "--format", "dot", "-o", report, path)
+    assert code == EXIT_PASS
+    assert out == ""
+    with open(report) as f:
+        assert f.read().startswith("digraph")
+
+
+def test_simulate_and_run(tmp_path):
+    path = write_model(tmp_path)
+    err = io.StringIO()
+    with contextlib.redirect_stderr(err):
+        options = ["--depth", "50", "--entry", "model", "-D", "amount=6"]
+        options += ["--fairness", "weak"]
+        code, out = run("simulate", "-q", "--seed", "1", *options, path)
+    assert code == EXIT_VIOLATION
+    command = err.getvalue().splitlines()[-1].split()
+    assert command[:4] == ["Replay", "with:", "timewinder", "run"]
+    assert command[6:] == options + [path]
+    code, out = run(*command[3:], "-q")
+    assert code == EXIT_VIOLATION
+    assert "alice: {'acc': -1}" in out
+    assert "Violated: [](no_overdrafts" in out
+    with contextlib.redirect_stderr(io.StringIO()):
+        code, _ = run("run", "-q", "--replay", "0,5", path)
+    assert code == EXIT_ERROR
+
+    code, out = run("run", "-q", path)
+    assert code == EXIT_PASS
+    assert "Step 1, thread 0 executes withdraw@start" in out
+    assert "alice: {'acc': 4}" in out
+
+    err = io.StringIO()
+    with contextlib.redirect_stderr(err):
+        code, out = run("run", "-q", "--trace-instructions", path)
+    assert code == EXIT_PASS
+    assert "withdraw@start pc 0 " in err.getvalue()
+    assert "STORE_ATTR acc" in err.getvalue()
+
+
+def test_compile(tmp_path):
+    code, out = run("compile", write_model(tmp_path))
+    assert code == EXIT_PASS
+    assert "Thread 0: withdraw@start" in out
+
+
+def test_load_errors(tmp_path):
+    code, _ = run("check", "-q", "--entry", "nope", write_model(tmp_path))
+    assert code == EXIT_ERROR
+
+
+def test_debug(tmp_path, monkeypatch):
+    path = write_model(tmp_path)
+    commands = "back\ns\nstack\np sender.acc\nc\nl\nc\nq\n"
+    monkeypatch.setattr("sys.stdin", io.StringIO(commands))
+    code, out = run("debug", "-q", path)
+    assert code == EXIT_PASS
+    assert "bank.py:13: sender.acc = sender.acc - amount" in out
+    assert "  -> 2 LOAD_ATTR acc" in out
+    assert "(tw) 5\n" in out
+    assert "withdraw@start: finished" in out
+    assert "sender = alice: {'acc': 4}" in out
+    assert "Process has finished" in out
+    assert "Nothing to undo" in out
+
+
+def test_trace_view(tmp_path):
+    path = os.path.join(EXAMPLES, "practical_tlaplus_chap1.py")
+    report = str(tmp_path / "out.json")
+    code, _ = run("check", "-q", "--format", "json", "-o", report, path)
+    assert code == EXIT_VIOLATION
+    code, out = run("trace", "view", report)
+    assert code == EXIT_PASS
+    assert out.startswith("Initial state (step 0/")
+    assert "thread 0 executes withdraw@start" in out
+    assert "*     acc: -1" in out
+
+
+def test_dis(tmp_path):
+    path = write_model(tmp_path)
+    code, out = run("dis", "--with-source", path)
+    assert code == EXIT_PASS
+    assert out.startswith("Thread 0 (withdraw@start):")
+    assert "  13     sender.acc = sender.acc - amount" in out
+    assert "STORE_ATTR             0 (acc)" in out
+    code, _ = run("dis", "--thread", "3", path)
+    assert code == EXIT_ERROR
+
+
+class RewritingWatcher:
+    def __init__(self, path):
+        self.path = path
+
+    def wait_for_change(self):
+        with open(self.path, "w") as f:
+            f.write(MODEL.replace("amount=1", "amount=10"))
+        return [self.path]
+
+
+def test_check_watch(tmp_path):
+    path = write_model(tmp_path)
+    args = _parser().parse_args(["check", "--watch", path])
+    out = io.StringIO()
+    with contextlib.redirect_stdout(out):
+        watch_loop(args, RewritingWatcher(path), runs=2)
+    first, second = out.getvalue().splitlines()
+    assert first.endswith(" PASS 2 states, 2 steps")
+    assert " FAIL [](no_overdrafts" in second
+
+
+def test_new(tmp_path):
+    path = str(tmp_path / "bank")
+    code, out = run("new", path)
+    assert code == EXIT_PASS
+    assert "model.py" in out
+    code, out = run("--config", os.path.join(path, "timewinder.toml"), "check", "-q")
+    assert code == EXIT_VIOLATION
+    code, _ = run("new", path)
+    assert code == EXIT_ERROR
+
+
+def test_warnings(tmp_path):
+    path = tmp_path / "warn.py"
+    body = """\
+    total = amount
+    while True:
+        sender.acc = sender.acc - amount
+        yield "again"
+    print("done")
+"""
+    path.write_text(MODEL.replace("    sender.acc = sender.acc - amount\n", body))
+    err = io.StringIO()
+    with contextlib.redirect_stderr(err):
+        code, _ = run("compile", str(path))
+    assert code == EXIT_PASS
+    lines = err.getvalue().splitlines()
+    assert lines == [f"{path}:17: warning: this code can never run [unreachable]"]
+
+    err = io.StringIO()
+    with contextlib.redirect_stderr(err):
+        run("compile", "-q", str(path))
+    assert err.getvalue() == ""
+
+
+def test_fairness(tmp_path):
+    path = tmp_path / "live.py"
+    specs = """specs=[
+            timewinder.Eventually(
+                timewinder.ForAll(Account, lambda a: a.acc < 5, name="paid")
+            )
+        ]"""
+    path.write_text(MODEL.replace("specs=[no_overdrafts]", specs))
+    code, out = run("check", "-q", str(path))
+    assert code == EXIT_VIOLATION
+    assert out.startswith("Violation: <>(paid:")
+    code, _ = run("check", "-q", "--fairness", "weak", str(path))
+    assert code == EXIT_PASS
+
+
+def test_show(tmp_path):
+    path = write_model(tmp_path)
+    code, out = run(
+        "check", "-q", "-D", "amount=6", "--show", "alice.acc", "--show", "*.pc", path
+    )
+    assert code == EXIT_VIOLATION
+    assert out.splitlines()[1:7] == [
+        "Initial State:",
+        "  _thread_0: {'pc': 0}",
+        "  alice: {'acc': 5}",
+        "Step 1, thread 0 executes withdraw@start",
+        "  _thread_0: {'pc': -1}",
+        "  alice: {'acc': -1}",
+    ]
+
+
+def test_explore(tmp_path, monkeypatch):
+    path = write_model(tmp_path)
+    monkeypatch.setattr("sys.stdin", io.StringIO("back\n0\n0\nback\nstate\nhelp\nq\n"))
+    code, out = run("explore", "-q", "-D", "amount=6", path)
+    assert code == EXIT_PASS
+    assert out.splitlines()[:3] == [
+        "  alice: {'acc': 5}",
+        "[0] thread 0 executes withdraw@start",
+        "      alice.acc: 5 -> -1",
+    ]
+    assert "Nothing to undo" in out
+    assert "Step 1, thread 0 executes withdraw@start\n  alice: {'acc': -1}\n" in out
+    assert "Violated: [](no_overdrafts" in out
+    assert "No actions can be taken" in out
+    assert "No action 0" in out
+    assert "  _thread_0: {" in out
+    assert "restart [N]" in out
+
+
+def test_reach(tmp_path):
+    path = write_model(tmp_path)
+    code, out = run("reach", "-q", "--goal", "alice.acc < 5", path)
+    assert code == EXIT_PASS
+    assert out.splitlines() == [
+        "Reached alice.acc < 5 in 1 steps:",
+        "Initial State:",
+        "  alice: {'acc': 5}",
+        "Step 1, thread 0 executes withdraw@start",
+        "  alice: {'acc': 4}",
+    ]
+
+    code, out = run("reach", "-q", "--goal", "alice.acc < 0", path)
+    assert code == EXIT_VIOLATION
+    assert out == "Unreachable.\n"
+    code, out = run("reach", "-q", "--goal", "alice.acc < 5", "--steps", "0", path)
+    assert code == EXIT_BOUNDED
+    assert out == "Unreachable within the step bound.\n"
+
+    with contextlib.redirect_stderr(io.StringIO()):
+        code, _ = run("reach", "-q", "--goal", "alice.acc <", path)
+    assert code == EXIT_ERROR
+
+
+def test_check_heuristic(tmp_path):
+    path = write_model(tmp_path)
+    args = ("check", "-q", "-D", "amount=6", "--heuristic", "alice.acc", path)
+    code, out = run(*args)
+    assert code == EXIT_VIOLATION
+    code, out = run(*args, "--max-states", "1")
+    assert code == EXIT_BOUNDED
+    assert out.startswith("No violations found (bounded).")
+
+
+def test_sequences(tmp_path):
+    path = write_model(tmp_path)
+    code, out = run("sequences", "-q", path)
+    assert code == EXIT_PASS
+    doc = json.loads(out)
+    assert doc["cover"] == "transitions"
+    (trace,) = doc["sequences"]
+    assert [s["action"] for s in trace] == [None, "withdraw@start"]
+
+    code, out = run("sequences", "-q", "--format", "itf", path)
+    (itf,) = json.loads(out)
+    assert [s["alice"]["acc"] for s in itf["states"]] == [5, 4]
+
+
+def test_check_pytest_format(tmp_path):
+    path = write_model(tmp_path)
+    options = ["--format", "pytest", "--sut", "b:sut"]
+    code, out = run("check", "-q", "-D", "amount=6", *options, path)
+    assert code == EXIT_VIOLATION
+    assert "from b import sut\n" in out
+    assert "    system = sut()\n    system.apply(0, 'withdraw@start')\n" in out
+
+
+def test_sweep(tmp_path):
+    path = write_model(tmp_path)
+    code, out = run("sweep", "-q", "-j", "1", "-D", "amount=4..6", path)
+    assert code == EXIT_VIOLATION
+    lines = out.splitlines()
+    assert lines[0].split() == ["amount", "status", "states", "violation"]
+    assert [line.split()[1] for line in lines[1:]] == ["pass", "pass", "fail"]
+
+    code, out = run(
+        "sweep", "-q", "-j", "1", "-D", "amount=1,2", "--format", "json", path
+    )
+    assert code == EXIT_PASS
+    assert [r["constants"] for r in json.loads(out)] == [{"amount": 1}, {"amount": 2}]
+
+    code, _ = run(
+        "sweep", "-q", "-j", "1", "-D", "amount=1", "--property", "nonsense", path
+    )
+    assert code == EXIT_ERROR
+
+
+def test_scc(tmp_path):
+    path = write_model(tmp_path)
+    code, out = run("scc", "-q", path)
+    assert code == EXIT_PASS
+    assert out.splitlines() == [
+        "2 states in 2 components, 0 cyclic",
+        "  1: 1 states, terminal",
+    ]
+    code, out = run("scc", "-q", "--format", "json", path)
+    assert [c["size"] for c in json.loads(out)["components"]] == [1, 1]
+
+
+def test_check_largest(tmp_path):
+    path = write_model(tmp_path)
+    err = io.StringIO()
+    with contextlib.redirect_stderr(err):
+        code, _ = run("check", "-q", "--largest", "1", path)
+    assert code == EXIT_PASS
+    lines = err.getvalue().splitlines()
+    assert lines[0].startswith("States take ")
+    assert len(lines) == 2
+    assert "alice" in lines[1] and "_thread_0" in lines[1]
+
+
+def test_describe(tmp_path):
+    path = tmp_path / "bank.py"
+    path.write_text('"""A bank."""\n' + MODEL)
+    code, out = run("describe", str(path))
+    assert code == EXIT_PASS
+    assert out.splitlines()[:3] == ["A bank.", "", "object Account (lines 6-9)"]
+    code, out = run("describe", "--format", "json", str(path))
+    kinds = [d["kind"] for d in json.loads(out)]
+    assert kinds == ["module", "object", "process", "model"]
+
+
+def test_compile_dir(tmp_path):
+    write_model(tmp_path)
+    (tmp_path / "broken.py").write_text("def model():\n    raise ValueError('oops')\n")
+    code, out = run("compile", "-q", str(tmp_path))
+    assert code == EXIT_ERROR
+    assert out.splitlines() == [
+        "bank.py: ok, 1 threads, 1 specs",
+        "broken.py: ValueError: oops",
+        "2 models, 1 failed to load",
+    ]
+
+
+def test_flamegraph(tmp_path):
+    path = write_model(tmp_path)
+    folded = tmp_path / "out.folded"
+    code, _ = run("check", "-q", "--flamegraph", str(folded), path)
+    assert code == EXIT_PASS
+    assert "withdraw@start;bank.py:" in folded.read_text()
+    weight = ["--flamegraph-weight", "states"]
+    code, _ = run("check", "-q", "--flamegraph", str(folded), *weight, path)
+    assert folded.read_text() == "withdraw@start 1\n"
+
+
+def test_check_archive(tmp_path):
+    path = write_model(tmp_path)
+    db = str(tmp_path / "runs.db")
+    code, _ = run("check", "-q", "--archive", db, path)
+    assert code == EXIT_PASS
+    archive = Archive(db)
+    (row,) = archive.runs()
+    assert row["status"] == "pass"
+    assert archive.run(row["id"])["states"] == 2
+
+
+def test_config(tmp_path):
+    write_model(tmp_path)
+    config = tmp_path / "timewinder.toml"
+    config.write_text(
+        'model = "bank.py"\nproperties = ["no_overdrafts"]\n\n'
+        "[constants]\namount = 6\n\n"
+        '[check]\nformat = "json"\n'
+    )
+    code, out = run("--config", str(config), "check", "-q")
+    assert code == EXIT_VIOLATION
+    assert json.loads(out)["status"] == "fail"
+
+    # The command line wins
+    code, out = run(
+        "--config", str(config), "check", "-q", "-D", "amount=1", "--format", "text"
+    )
+    assert code == EXIT_PASS
+    assert out.startswith("No violations found.")
+
+    code, _ = run("--config", str(config), "check", "-q", "--property", "nonsense")
+    assert code == EXIT_ERROR
+
+    # A list on the command line replaces the config's, rather than adding to it
+    config.write_text('model = "bank.py"\nproperties = ["nonsense"]\n')
+    code, _ = run("--config", str(config), "check", "-q")
+    assert code == EXIT_ERROR
+    code, _ = run("--config", str(config), "check", "-q", "--property", "no_overdrafts")
+    assert code == EXIT_PASS
+    code, _ = run("--no-config", "check", "-q")
+    assert code == EXIT_ERROR
+
+    config.write_text('model = "bank.py"\n\n[check]\nformat = "nonsense"\n')
+    code, _ = run("--config", str(config), "check", "-q")
+    assert code == EXIT_ERROR
+    config.write_text('model = "bank.py"\n\n[check]\nnonsense = 1\n')
+    code, _ = run("--config", str(config), "check", "-q")
+    assert code == EXIT_ERROR
+
+
+def test_lint(tmp_path):
+    path = write_model(tmp_path)
+    code, out = run("lint", path)
+    assert code == EXIT_VIOLATION
+    assert out == (
+        f"{path}:13: withdraw changes state without checking any first, "
+        "so it can always run [unguarded-action]\n"
+    )
+
+    config = tmp_path / "timewinder.toml"
+    config.write_text('[lint]\ndisable = ["unguarded-action"]\n')
+    code, out = run("--config", str(config), "lint", path)
+    assert code == EXIT_PASS
+    assert out == ""
+    code, out = run("--config", str(config), "lint", "--disable", "shadowing", path)
+    assert code == EXIT_VIOLATION
+    options = ["--enable", "unguarded-action", "--format", "json"]
+    code, out = run("--config", str(config), "lint", *options, path)
+    assert code == EXIT_VIOLATION
+    assert [f["rule"] for f in json.loads(out)["findings"]] == ["unguarded-action"]
+    code, out = run(
+        "lint", "--enable", "unused-variable", "--disable", "unguarded-action", path
+    )
+    assert code == EXIT_PASS
+    code, _ = run("lint", "--disable", "nonsense", path)
+    assert code == EXIT_ERROR
@@ -0,0 +1,36 @@
+import os
+
+import pytest
+
+from timewinder.config import find_config
+from timewinder.config import load_config
+from timewinder.loader import LoadError
+
+
+def test_find_config(tmp_path):
+    nested = tmp_path / "models" / "queues"
+    nested.mkdir(parents=True)
+    assert find_config(str(nested)) is None
+    (tmp_path / "timewinder.toml").write_text("")
+    assert find_config(str(nested)) == str(tmp_path / "timewinder.toml")
+
+
+def test_options(tmp_path):
+    path = tmp_path / "timewinder.toml"
+    path.write_text(
+        'model = "models/bank.py"\nfairness = "weak"\n\n'
+        '[constants]\namount = 3\nname = "alice"\n\n'
+        "[check]\nmax-states = 50\n"
+    )
+    config = load_config(str(path))
+    assert list(config.options("check")) == [
+        ("model", os.path.join(str(tmp_path), "models/bank.py"), False),
+        ("fairness", "weak", False),
+        ("max_states", 50, True),
+        ("constants", ["amount=3", "name='alice'"], False),
+    ]
+    assert ("max_states", 50, True) not in list(config.options("simulate"))
+
+    path.write_text("model = ")
+    with pytest.raises(LoadError):
+        load_config(str(path))
@@ -0,0 +1,67 @@
+import timewinder
+
+from timewinder.conformance import SystemUnderTest
+from timewinder.conformance import check_conformance
+
+
+@timewinder.object
+class Account:
+    def __init__(self, name, amt):
+        self.name = name
+        self.acc = amt
+
+
+class Bank(SystemUnderTest):
+    def __init__(self, buggy=False):
+        self.balances = {"alice": 5, "bob": 5}
+        self.buggy = buggy
+
+    def apply(self, thread_id, name):
+        if name.endswith("@start"):
+            self.balances["alice"] -= 3
+        else:
+            self.balances["bob"] += 2 if self.buggy else 3
+
+    def observe(self):
+        return {k: {"acc": v} for k, v in self.balances.items()}
+
+
+def transfer_trace():
+    alice = Account("alice", 5)
+    bob = Account("bob", 5)
+
+    @timewinder.process
+    def transfer(sender, reciever, amount):
+        sender.acc = sender.acc - amount
+        yield "deposit"
+        reciever.acc = reciever.acc + amount
+
+    # Finishing the transfer fails the spec, which hands us the full trace.
+    not_finished = timewinder.ForAll(Account, lambda a: a.acc != 8)
+
+    ev = timewinder.Evaluator(
+        objects=[alice, bob],
+        threads=[transfer(alice, bob, 3)],
+        specs=[not_finished],
+    )
+    try:
+        ev.evaluate()
+    except timewinder.ConstraintError as e:
+        return ev, e.thunk
+    assert False, "expected the transfer to complete"
+
+
+def test_conformance_ok():
+    ev, thunk = transfer_trace()
+    assert check_conformance(ev, thunk, Bank()) is None
+
+
+def test_conformance_divergence():
+    ev, thunk = transfer_trace()
+    d = check_conformance(ev, thunk, Bank(buggy=True))
+    assert d is not None
+    assert d.step == 2
+    assert d.thread_id == 0
+    assert d.object_name == "bob"
+    assert d.expected == 8
+    assert d.observed == 7
@@ -0,0 +1,69 @@
+from timewinder.describe import describe
+from timewinder.scaffold import MODEL
+
+SOURCE = '''"""Banking.
+
+Transfers between accounts."""
+
+import timewinder
+
+
+@timewinder.object
+class Account:
+    """An account, with a balance."""
+
+    def __init__(self, amt):
+        self.acc = amt
+
+
+@timewinder.invariant(Account)
+def solvent(a):
+    """No account is ever overdrawn."""
+    return a.acc >= 0
+
+
+@timewinder.process(fairness=timewinder.Fairness.WEAKLY_FAIR)
+def withdraw(sender, amount):
+    """Takes amount out of the sender's account.
+
+    It doesn't check the balance first."""
+    sender.acc = sender.acc - amount
+
+
+def helper():
+    pass
+
+
+def model(amount=1):
+    return None
+'''
+
+
+def test_describe(tmp_path):
+    path = tmp_path / "bank.py"
+    path.write_text(SOURCE)
+    module, account, solvent, withdraw, helper, model = describe(str(path))
+    assert module.doc == "Banking.\n\nTransfers between accounts."
+    assert (account.kind, account.name, account.doc) == (
+        "object",
+        "Account",
+        "An account, with a balance.",
+    )
+    assert (account.line, account.end_line) == (8, 13)
+    assert (solvent.kind, solvent.doc) == ("property", "No account is ever overdrawn.")
+    assert withdraw.kind == "process"
+    assert withdraw.doc.endswith("It doesn't check the balance first.")
+    assert (helper.kind, helper.doc) == ("function", None)
+    assert model.kind == "model"
+
+
+def test_describe_scaffold(tmp_path):
+    path = tmp_path / "model.py"
+    path.write_text(MODEL.format(name="demo"))
+    docs = describe(str(path))
+    assert docs[0].doc.startswith("demo: a starter model.")
+    assert [(d.kind, d.name) for d in docs[1:]] == [
+        ("object", "Account"),
+        ("process", "transfer"),
+        ("model", "model"),
+    ]
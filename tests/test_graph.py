@@ -0,0 +1,77 @@
+import timewinder
+
+from timewinder.export import scc_report
+from timewinder.functions import Await
+from timewinder.graph import Edge
+from timewinder.graph import StateGraph
+
+
+def test_components():
+    g = StateGraph()
+    a, b, c, d = (bytes([i]) for i in range(4))
+    g.add_initial(a)
+    g.add_edge(Edge(a, b, 0, "go"))
+    g.add_edge(Edge(b, c, 0, "go"))
+    g.add_edge(Edge(c, b, 1, "back"))
+    g.add_edge(Edge(c, d, 0, "go"))
+    first, loop, last = g.components()
+    assert (first.states, first.cyclic, first.terminal) == ([a], False, False)
+    assert sorted(loop.states) == [b, c]
+    assert (loop.cyclic, loop.terminal, loop.exits) == (True, False, 1)
+    assert loop.always_enabled == {0}
+    assert loop.fair_cycle(set())
+    assert loop.fair_cycle({0})
+    assert (last.states, last.cyclic, last.terminal) == ([d], False, True)
+
+    # Thread 0 can always move, but only out of the loop
+    g.edges[b] = [e for e in g.edges[b] if e.dst != c] + [Edge(b, c, 1, "over")]
+    g.add_edge(Edge(b, d, 0, "go"))
+    (_, loop, _) = g.components()
+    assert not loop.fair_cycle({0})
+    assert loop.fair_cycle({1})
+
+
+def test_long_chain():
+    g = StateGraph()
+    g.add_initial((0).to_bytes(4, "big"))
+    for i in range(5000):
+        g.add_edge(Edge(i.to_bytes(4, "big"), (i + 1).to_bytes(4, "big"), 0, "next"))
+    assert len(g.components()) == 5001
+
+
+@timewinder.object
+class Light:
+    def __init__(self):
+        self.on = 0
+        self.flips = 0
+
+
+@timewinder.process
+def flipper(light):
+    while True:
+        light.on = 1 - light.on
+        yield "flip"
+
+
+@timewinder.process
+def waiter(light):
+    Await(light.on == 1)
+    light.flips = 1
+
+
+def test_scc_report():
+    light = Light()
+    ev = timewinder.Evaluator(
+        objects=[light],
+        threads=[flipper(light), waiter(light)],
+        record_graph=True,
+        fairness=timewinder.Fairness.WEAKLY_FAIR,
+    )
+    ev.evaluate(steps=None)
+    report = scc_report(ev)
+    assert report["fair_threads"] == [0, 1]
+    summary = [(c["size"], c["cyclic"], c["terminal"]) for c in report["components"]]
+    assert summary == [(1, False, False), (2, True, False), (2, True, True)]
+    # The waiter can't always move, so weak fairness lets the flipper loop
+    assert [c["fair_cycle"] for c in report["components"]] == [False, True, True]
+    assert report["components"][1]["threads"] == [0]
@@ -0,0 +1,136 @@
+import timewinder
+
+from timewinder.loader import load_model
+from timewinder.result import Status
+
+MODEL = """
+import timewinder
+
+
+@timewinder.object
+class Account:
+    def __init__(self, amt):
+        self.acc = amt
+
+
+@timewinder.invariant(Account)
+def no_overdrafts(account):
+    return account.acc >= 0
+
+
+@timewinder.invariant
+def small(obj):
+    return obj.acc < 100
+
+
+@timewinder.process
+def withdraw(sender, amount):
+    sender.acc = sender.acc - amount
+
+
+def model(amount=1, listed=False):
+    alice = Account(5)
+    return timewinder.Evaluator(
+        objects=[alice],
+        threads=[withdraw(alice, amount)],
+        specs=[no_overdrafts] if listed else [],
+    )
+"""
+
+
+@timewinder.object
+class Counter:
+    def __init__(self):
+        self.n = 0
+
+
+def test_invariant_decorator():
+    @timewinder.invariant(Counter)
+    def bounded(c):
+        return c.n < 2
+
+    assert bounded.name == "bounded"
+    assert bounded.declared
+    assert bounded.location()[1] == bounded.pred.__code__.co_firstlineno
+
+    c = Counter()
+
+    @timewinder.step
+    def incr(state, c):
+        c.n = c.n + 1
+
+    ev = timewinder.Evaluator(objects=[c], threads=[incr(c), incr(c)], specs=[bounded])
+    result = ev.check()
+    assert result.status == Status.FAIL
+    assert result.violation.startswith("[](bounded")
+
+
+def test_loader_discovers_invariants(tmp_path):
+    path = tmp_path / "bank.py"
+    path.write_text(MODEL)
+
+    ev = load_model(str(path), constants={"amount": 6})
+    assert [str(s) for s in ev.specs] == [
+        "[](no_overdrafts:ForAll(no_overdrafts))",
+        "[](small:ForAll(small))",
+    ]
+    result = ev.check()
+    assert result.status == Status.FAIL
+    assert "no_overdrafts" in result.violation
+
+    # Listed specs aren't added twice
+    ev = load_model(str(path), constants={"listed": True})
+    assert len(ev.specs) == 2
+    assert ev.check().status == Status.PASS
+
+
+def test_temporal_decorators(tmp_path):
+    path = tmp_path / "counter.py"
+    path.write_text("""
+import timewinder
+
+
+@timewinder.object
+class Counter:
+    def __init__(self):
+        self.n = 0
+
+
+@timewinder.always(Counter)
+def bounded(c):
+    return c.n <= 2
+
+
+@timewinder.eventually
+def reaches_goal(c):
+    return c.n >= GOAL
+
+
+GOAL = 0
+
+
+@timewinder.process
+def incr(c):
+    c.n = c.n + 1
+    yield "again"
+    c.n = c.n + 1
+
+
+def model():
+    c = Counter()
+    return timewinder.Evaluator(objects=[c], threads=[incr(c)])
+""")
+    ev = load_model(str(path))
+    assert [str(s) for s in ev.specs] == [
+        "[](bounded:ForAll(bounded))",
+        "<>(reaches_goal:ForAll(reaches_goal))",
+    ]
+    assert [s.is_liveness() for s in ev.specs] == [False, True]
+    assert ev.check().status == Status.PASS
+
+    # Without fairness, a process may stop before the goal
+    ev = load_model(str(path))
+    ev.specs[1].pred.pred = lambda c: c.n == 2
+    result = ev.check()
+    assert result.status == Status.FAIL
+    assert "reaches_goal" in result.violation
@@ -0,0 +1,77 @@
+import timewinder
+
+from timewinder.lint import lint_model
+from timewinder.lint import lint_process
+
+
+@timewinder.object
+class Counter:
+    def __init__(self):
+        self.n = 0
+        self.limit = 3
+
+
+def test_unguarded_action():
+    def bump(c):
+        c.n = c.n + 1
+
+    def guarded(c):
+        if c.n < c.limit:
+            c.n = c.n + 1
+
+    def awaits(c):
+        timewinder.Await(c.n == 0)
+        c.n = 1
+
+    assert [d.rule for d in lint_process(bump)] == ["unguarded-action"]
+    assert lint_process(guarded) == []
+    assert lint_process(awaits) == []
+
+
+def test_unbounded_growth():
+    def grows(c):
+        while True:
+            c.n += 1
+            yield "again"
+
+    def bounded(c):
+        while c.n < 3:
+            c.n = c.n + 1
+            yield "again"
+
+    rules = [d.rule for d in lint_process(grows)]
+    assert rules == ["unbounded-growth", "unguarded-action"]
+    assert lint_process(bounded) == []
+
+
+def test_lint_model():
+    c = Counter()
+
+    @timewinder.process
+    def bump(c):
+        while c.n < c.limit:
+            c.n = c.n + 1
+            yield "again"
+
+    always = timewinder.ForAll(Counter, lambda c: True)
+    below = timewinder.ForAll(Counter, lambda c: c.n <= c.limit)
+    ev = timewinder.Evaluator(
+        objects=[c], threads=[bump(c), bump(c)], specs=[always, below]
+    )
+    (finding,) = lint_model(ev)
+    assert finding.rule == "constant-invariant"
+    assert finding.msg.startswith("always doesn't read")
+    assert str(finding).endswith(" [constant-invariant]")
+    assert lint_model(ev, disabled={"constant-invariant"}) == []
+
+    # Specs may read a process's locals, so unused ones are only reported
+    # when asked for
+    @timewinder.process
+    def hold(c):
+        held = True
+        yield "hold"
+
+    ev = timewinder.Evaluator(objects=[c], threads=[hold(c)], specs=[below])
+    assert lint_model(ev) == []
+    (finding,) = lint_model(ev, disabled=())
+    assert finding.rule == "unused-variable"
@@ -0,0 +1,66 @@
+from timewinder.loader import compile_dir
+from timewinder.loader import compile_path
+from timewinder.loader import model_files
+
+GOOD = """
+import timewinder
+
+
+@timewinder.object
+class Counter:
+    def __init__(self):
+        self.n = 0
+
+
+@timewinder.process
+def incr(c):
+    while True:
+        c.n = c.n + 1
+        yield "incr"
+    print("done")
+
+
+def model():
+    c = Counter()
+    return timewinder.Evaluator(objects=[c], threads=[incr(c)])
+"""
+
+BROKEN = """
+import timewinder
+
+
+@timewinder.process
+def bad(c):
+    c.n = c.n @ 2
+
+
+def model():
+    return timewinder.Evaluator(threads=[bad(None)])
+"""
+
+
+def test_compile_dir(tmp_path):
+    (tmp_path / "a.py").write_text(GOOD)
+    nested = tmp_path / "nested" / "deeper"
+    nested.mkdir(parents=True)
+    (nested / "b.py").write_text(BROKEN)
+    (tmp_path / "helpers.py").write_text("def helper():\n    return 1\n")
+    (tmp_path / "test_a.py").write_text(GOOD)
+    (tmp_path / ".hidden").mkdir()
+    (tmp_path / ".hidden" / "c.py").write_text(GOOD)
+
+    files = model_files(str(tmp_path))
+    prefix = len(str(tmp_path)) + 1
+    assert [f[prefix:] for f in files] == ["a.py", "nested/deeper/b.py"]
+
+    a, b = compile_dir(str(tmp_path))
+    assert a.ok and a.evaluator is not None
+    assert len(a.warnings) == 1 and "this code can never run" in a.warnings[0]
+    assert not b.ok and b.evaluator is None
+    assert b.error.startswith("VerificationError: bad can't be interpreted")
+
+
+def test_compile_path_missing(tmp_path):
+    c = compile_path(str(tmp_path / "nope.py"))
+    assert not c.ok
+    assert "nope.py" in c.error
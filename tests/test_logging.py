@@ -0,0 +1,66 @@
+import logging
+
+import timewinder
+
+from tests.test_helpers import A
+from timewinder.reinterp.process import trace_logger
+
+
+class ListHandler(logging.Handler):
+    def __init__(self):
+        super().__init__()
+        self.messages = []
+
+    def emit(self, record):
+        self.messages.append(record.getMessage())
+
+
+def test_evaluation_logging():
+    @timewinder.step
+    def t(state, m):
+        m.foo = "b"
+
+    a = A()
+    ev = timewinder.Evaluator(objects=[a], threads=[t(a)])
+
+    handler = ListHandler()
+    logger = logging.getLogger("timewinder")
+    logger.addHandler(handler)
+    logger.setLevel(logging.DEBUG)
+    try:
+        ev.evaluate()
+    finally:
+        logger.removeHandler(handler)
+        logger.setLevel(logging.NOTSET)
+
+    assert "step 1: evaluating 1 states" in handler.messages
+    assert any(m.startswith("thread 0 ") for m in handler.messages)
+
+
+def test_instruction_logging():
+    @timewinder.process
+    def t(m):
+        m.foo = "b"
+        yield "done"
+
+    def messages(level):
+        a = A()
+        ev = timewinder.Evaluator(objects=[a], threads=[t(a)])
+        handler = ListHandler()
+        logger = logging.getLogger("timewinder")
+        logger.addHandler(handler)
+        logger.setLevel(logging.DEBUG)
+        trace_logger.setLevel(level)
+        try:
+            ev.evaluate()
+        finally:
+            logger.removeHandler(handler)
+            logger.setLevel(logging.NOTSET)
+            trace_logger.setLevel(logging.INFO)
+        return [m for m in handler.messages if " pc " in m and " depth " in m]
+
+    assert messages(logging.INFO) == []
+    traced = messages(logging.DEBUG)
+    line = t.func.__code__.co_firstlineno + 2
+    assert traced[0] == f"t@start pc 0 {__file__}:{line} depth 0 LOAD_CONST 'b'"
+    assert f"t@start pc 2 {__file__}:{line} depth 2 STORE_ATTR foo" in traced
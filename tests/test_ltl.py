@@ -1,5 +1,13 @@
+import pytest
+
+import timewinder
 import timewinder.ltl as ltl
+
 from tests.test_helpers import MockPredicate
+from timewinder.closure import Closure
+from timewinder.pause import Fairness
+from timewinder.reinterp.process import BytecodeProcess
+from timewinder.result import Status
 
 
 mocks = [MockPredicate(x) for x in range(10)]
@@ -50,3 +58,119 @@ def test_ltl_tree():
 
     ev = ltl.Eventually(ltl.LeadsTo(mocks[6], mocks[5]))
     assert ev.to_ltl_tree() == {"eventually": {"leads_to": [6, 5]}}
+
+
+class FairProcess(BytecodeProcess):
+    def fairness(self):
+        return Fairness.WEAKLY_FAIR
+
+
+@timewinder.object
+class Request:
+    def __init__(self):
+        self.requested = False
+        self.granted = False
+
+
+def leads_to_model(cls):
+    def serve(r):
+        r.requested = True
+        yield "grant"
+        r.granted = True
+
+    requested = timewinder.ForAll(Request, lambda r: r.requested)
+    granted = timewinder.ForAll(Request, lambda r: r.granted)
+    served = timewinder.LeadsTo(requested, granted)
+    r = Request()
+    return timewinder.Evaluator(
+        objects=[r], threads=[Closure(serve, cls)(r)], specs=[served]
+    )
+
+
+def test_leads_to_fairness():
+    # The process may stop between the request and the grant
+    result = leads_to_model(BytecodeProcess).check()
+    assert result.status == Status.FAIL
+    assert result.violation.startswith("(requested")
+    assert [s.action for s in result.trace] == [None, "serve@start"]
+
+    # Unless it's fair, when it has to carry on to the grant
+    assert leads_to_model(FairProcess).check().status == Status.PASS
+
+
+def test_until_release_next():
+    p = ltl.TTrace(trace=[True, True, False, False])
+    q = ltl.TTrace(trace=[False, True, False, False])
+    a, b = mocks[0], mocks[1]
+
+    assert ltl.Until(a, b).eval_traces([p, q]) == [True, True, False, False]
+    assert ltl.Release(b, a).eval_traces([p, q]) == [True, True, False, False]
+    assert ltl.Release(b, b).eval_traces([p, q]) == [False, True, False, False]
+    # The last state repeats once the behavior stops
+    assert ltl.Next(mocks[0]).eval_traces([p, q]) == [True, False, False, False]
+    assert ltl.Next(mocks[0]).eval_traces([ltl.TTrace(trace=[True])]) == [True]
+
+    ev = ltl.Until(mocks[3], ltl.Next(mocks[4]))
+    assert ev.to_ltl_tree() == {"until": [3, {"next": 4}]}
+
+
+class NamedPredicate(MockPredicate):
+    def __init__(self, index, name):
+        super().__init__(index)
+        self.name = name
+
+    def __repr__(self):
+        return self.name
+
+
+def test_parse():
+    busy, done = NamedPredicate(0, "busy"), NamedPredicate(1, "done")
+
+    ev = ltl.parse("[](busy U done) ~> <>!X done", [busy, done])
+    assert repr(ev) == "([]((busy) U (done))) ~> (<>(!(X(done))))"
+    assert ev.to_ltl_tree() == {
+        "leads_to": [
+            {"always": {"until": [0, 1]}},
+            {"eventually": {"inverse": {"next": 1}}},
+        ]
+    }
+    ev = ltl.parse("busy U done R busy", [busy, done])
+    assert repr(ev) == "(busy) U ((done) R (busy))"
+
+    with pytest.raises(ValueError, match="isn't a known predicate"):
+        ltl.parse("[]idle", [busy, done])
+    with pytest.raises(ValueError, match="missing \\)"):
+        ltl.parse("(busy U done", [busy, done])
+
+
+def fairness_model(decorate, **kwargs):
+    @decorate
+    def serve(r):
+        r.requested = True
+        yield "grant"
+        r.granted = True
+
+    requested = timewinder.ForAll(Request, lambda r: r.requested)
+    granted = timewinder.ForAll(Request, lambda r: r.granted)
+    r = Request()
+    served = timewinder.LeadsTo(requested, granted)
+    return timewinder.Evaluator(
+        objects=[r], threads=[serve(r)], specs=[served], **kwargs
+    )
+
+
+def test_default_fairness():
+    weak = Fairness.WEAKLY_FAIR
+    plain = timewinder.process
+    assert fairness_model(plain).check().status == Status.FAIL
+    assert fairness_model(plain, fairness=weak).check().status == Status.PASS
+
+    # A process's own fairness wins over the default
+    fair = timewinder.process(fairness=weak)
+    assert fairness_model(fair).check().status == Status.PASS
+    unfair = timewinder.process(fairness=Fairness.NORMAL)
+    assert fairness_model(unfair, fairness=weak).check().status == Status.FAIL
+
+    # And an action's wins over its process's
+    lazy = timewinder.process(fairness=weak, actions={"grant": Fairness.NORMAL})
+    assert fairness_model(lazy).check().status == Status.FAIL
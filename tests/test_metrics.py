@@ -0,0 +1,60 @@
+import json
+
+import timewinder
+
+from timewinder.result import Status
+
+
+@timewinder.object
+class Network:
+    def __init__(self):
+        self.sent = 0
+        self.delivered = False
+
+
+def network_model():
+    # Each sender retries until a message is delivered, or it gives up
+    @timewinder.process
+    def sender(net, tries):
+        for _ in range(tries):
+            if net.delivered:
+                return
+            net.sent = net.sent + 1
+            yield "send"
+
+    @timewinder.process
+    def receiver(net):
+        while net.sent == 0:
+            yield "wait"
+        net.delivered = True
+
+    net = Network()
+    return timewinder.Evaluator(
+        objects=[net],
+        threads=[sender(net, 3), receiver(net)],
+        metrics=[timewinder.Metric("sent", lambda objs: objs["net"].sent)],
+    )
+
+
+def test_check_metrics():
+    result = network_model().check(steps=None)
+    assert result.status == Status.PASS
+    (sent,) = result.metrics
+    assert (sent.min, sent.max) == (1, 3)
+    assert sent.count == result.stats.final_states
+    assert str(sent).startswith("sent: min 1, max 3, mean ")
+    assert str(sent).endswith(f"over {sent.count} distinct final states")
+
+    doc = json.loads(result.dumps())
+    assert doc["metrics"][0]["max"] == 3
+    assert doc["metrics"][0]["over"] == "distinct final states"
+
+
+def test_bounded_and_simulated_metrics():
+    (sent,) = network_model().check(steps=2).metrics
+    assert (sent.min, sent.max) == (1, 2)
+
+    (sent,) = network_model().simulate(runs=20, seed=1).metrics
+    assert sent.count == 20
+    assert str(sent).endswith("over 20 runs")
+    assert 1 <= sent.min <= sent.mean <= sent.max <= 3
@@ -0,0 +1,71 @@
+import timewinder
+
+from timewinder.profile import Profiler
+
+
+@timewinder.object
+class Account:
+    def __init__(self, amt):
+        self.acc = amt
+
+
+def test_profile_evaluation():
+    alice = Account(5)
+
+    @timewinder.process
+    def withdraw(sender, amount):
+        sender.acc = sender.acc - amount
+        yield "again"
+        sender.acc = sender.acc - amount
+
+    @timewinder.step
+    def audit(state, account):
+        pass
+
+    ev = timewinder.Evaluator(
+        objects=[alice],
+        threads=[withdraw(alice, 1), audit(alice)],
+        profile=True,
+    )
+    ev.evaluate(steps=None)
+    p = ev.profiler
+    assert p is not None
+    assert set(p.actions) == {"withdraw@start", "withdraw@again", "audit"}
+    assert sum(t.count for t in p.actions.values()) == ev.stats.thread_executions
+
+    first = withdraw.func.__code__.co_firstlineno
+    filename = withdraw.func.__code__.co_filename
+    # withdraw@start runs before and after audit, six instructions each time
+    assert p.spans[(filename, first + 2)].count == 2 * 6
+    report = p.report()
+    assert "withdraw@start" in report
+    assert "sender.acc = sender.acc - amount" in report
+
+    # Every execution leads to one state, none of them explored before
+    assert sum(s.total for s in p.successors.values()) == ev.stats.thread_executions
+    assert p.successors["audit"].total == 3
+    assert p.successors["audit"].new == 3
+
+
+def test_folded_stacks():
+    p = Profiler()
+    p.action = "send@start"
+    p.record_instruction(("/models/my model.py", 7), 0.002)
+    p.record_instruction(("/models/my model.py", 7), 0.001)
+    p.action = None
+    p.record_instruction(("/models/my model.py", 9), 0.001)
+    p.record_predicate("a;b", 0.0005)
+    p.record_successor("send@start", True)
+    p.record_successor("send@start", False)
+    p.record_successor("recv", False)
+    assert p.folded() == "predicates;a,b 500\nsend@start;my_model.py:7 3000\n"
+    assert p.folded("states") == "send@start 1\n"
+
+
+def test_sorted_by_time():
+    p = Profiler()
+    p.record_action("fast", 1.0)
+    p.record_action("slow", 3.0)
+    p.record_instruction(None, 1.0)
+    assert [name for name, _ in p.hot_actions()] == ["slow", "fast"]
+    assert p.spans == {}
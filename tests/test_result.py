@@ -0,0 +1,94 @@
+import json
+
+import timewinder
+
+from timewinder.result import Status
+from timewinder.result import RESULT_VERSION
+from timewinder.result import TraceStep
+from timewinder.result import project_trace
+
+
+@timewinder.object
+class Account:
+    def __init__(self, name, amt):
+        self.name = name
+        self.acc = amt
+
+
+def transfer_model(amount):
+    alice = Account("alice", 5)
+    bob = Account("bob", 5)
+
+    @timewinder.process
+    def withdraw(sender, reciever, amount):
+        sender.acc = sender.acc - amount
+        yield "deposit"
+        reciever.acc = reciever.acc + amount
+
+    no_overdrafts = timewinder.ForAll(Account, lambda a: a.acc >= 0)
+
+    return timewinder.Evaluator(
+        objects=[alice, bob],
+        threads=[withdraw(alice, bob, amount)],
+        specs=[no_overdrafts],
+    )
+
+
+def test_check_pass():
+    result = transfer_model(3).check()
+    assert result.status == Status.PASS
+    assert not result.bounded
+    assert [p.status for p in result.properties] == ["pass"]
+    assert result.trace == []
+
+    doc = json.loads(result.dumps())
+    assert doc["version"] == RESULT_VERSION
+    assert doc["status"] == "pass"
+    assert doc["stats"]["states"] == 3
+    assert doc["properties"][0]["location"]["file"] == __file__
+
+
+def test_check_bounded():
+    result = transfer_model(3).check(steps=1)
+    assert result.status == Status.PASS
+    assert result.bounded
+    assert result.bound == "steps"
+    assert [p.status for p in result.properties] == ["bounded"]
+
+
+def test_check_fail():
+    result = transfer_model(6).check()
+    assert result.status == Status.FAIL
+    assert result.properties[0].status == "fail"
+    assert len(result.trace) == 2
+
+    doc = json.loads(result.dumps())
+    initial, step = doc["trace"]
+    assert initial["thread"] is None
+    assert initial["state"]["alice"]["acc"] == 5
+    assert step["thread"] == 0
+    assert step["action"] == "withdraw@start"
+    assert step["location"]["file"] == __file__
+    assert step["state"]["alice"]["acc"] == -1
+
+
+def test_project_trace():
+    result = transfer_model(6).check().project(["alice.acc", "_thread_*.pc"])
+    initial, step = result.trace
+    assert initial.state == {"alice": {"acc": 5}, "_thread_0": {"pc": 0}}
+    assert step.action == "withdraw@start"
+    assert step.state["alice"] == {"acc": -1}
+
+    trace = [
+        TraceStep("0", {"q": {"items": [1], "n": 0}, "r": {"n": 0}}),
+        TraceStep("1", {"q": {"items": [1], "n": 1}, "r": {"n": 0}}, 0, "p@start"),
+        TraceStep("2", {"q": {"items": [1, 2], "n": 1}, "r": {"n": 0}}, 0, "p@more"),
+    ]
+    projected = project_trace(trace, ["q.items", "*.n"])
+    assert [s.state for s in projected] == [
+        {"q": {"items": [1], "n": 0}, "r": {"n": 0}},
+        {"q": {"n": 1}},
+        {"q": {"items": [1, 2]}},
+    ]
+    assert [s.action for s in projected] == [None, "p@start", "p@more"]
+    assert project_trace(trace, ["q.items.1"])[2].state == {"q": {"items": {1: 2}}}
@@ -0,0 +1,29 @@
+import os
+
+import pytest
+
+from timewinder.loader import load_model
+from timewinder.scaffold import create_project
+from timewinder.testing import expect_fail
+from timewinder.testing import expect_pass
+
+
+def test_create_project(tmp_path):
+    path = str(tmp_path / "bank")
+    files = create_project(path)
+    assert sorted(os.path.basename(f) for f in files) == [
+        "README.md",
+        "model.py",
+        "test_model.py",
+        "timewinder.toml",
+    ]
+    with open(os.path.join(path, "README.md")) as f:
+        assert f.read().startswith("# bank\n")
+
+    model = os.path.join(path, "model.py")
+    # The same expectations as the generated test_model.py
+    expect_fail(load_model(model), violation="no_overdrafts")
+    expect_pass(load_model(model, constants={"amount": 2}))
+
+    with pytest.raises(FileExistsError):
+        create_project(path)
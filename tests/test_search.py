@@ -0,0 +1,52 @@
+import timewinder
+
+from timewinder.predicate import Expression
+from timewinder.result import Status
+
+
+@timewinder.object
+class Counter:
+    def __init__(self):
+        self.n = 0
+
+
+def counters_model():
+    @timewinder.process
+    def count(c):
+        while True:
+            c.n = c.n + 1
+            yield "again"
+
+    a = Counter()
+    b = Counter()
+    small = timewinder.ForAll(Counter, lambda c: c.n < 20)
+    return timewinder.Evaluator(
+        objects=[a, b], threads=[count(a), count(b)], specs=[small]
+    )
+
+
+def test_search():
+    # Interleaving the counters, a breadth-first search visits 400 states
+    # before one reaches 20; heading for the largest gets there directly
+    result = counters_model().check(score=lambda objs: -objs["a"].n, max_states=100)
+    assert result.status == Status.FAIL
+    assert result.stats.states == 21
+    assert [s.state["a"]["n"] for s in result.trace][-1] == 20
+
+    result = counters_model().check(score=lambda objs: -objs["a"].n, max_states=10)
+    assert result.status == Status.PASS
+    assert result.bounded
+    assert result.stats.states == 10
+
+
+def test_reuse():
+    # Each query on an Evaluator starts afresh, whatever ran before it
+    ev = counters_model()
+    first = ev.check(steps=3)
+    assert ev.check(steps=3).stats.states == first.stats.states
+    goal = Expression("a.n == 1")
+    for _ in range(2):
+        trace = ev.reach(goal)
+        assert trace is not None and trace[-1].state["a"]["n"] == 1
+    result = ev.check(score=lambda objs: -objs["a"].n, max_states=100)
+    assert result.status == Status.FAIL
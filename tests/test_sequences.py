@@ -0,0 +1,59 @@
+import pytest
+
+import timewinder
+
+from timewinder.sequences import covering_sequences
+
+
+@timewinder.object
+class Account:
+    def __init__(self, amt):
+        self.acc = amt
+
+
+def transfer_model():
+    @timewinder.process
+    def transfer(sender, receiver, amount):
+        sender.acc = sender.acc - amount
+        yield "deposit"
+        receiver.acc = receiver.acc + amount
+
+    alice = Account(5)
+    bob = Account(5)
+    no_overdrafts = timewinder.ForAll(Account, lambda a: a.acc >= 0)
+    return timewinder.Evaluator(
+        objects=[alice, bob],
+        threads=[transfer(alice, bob, 3), transfer(bob, alice, 6)],
+        specs=[no_overdrafts],
+    )
+
+
+def taken(ev, sequences):
+    out = set()
+    for trace in sequences:
+        for prev, step in zip(trace, trace[1:]):
+            out.add((prev.hash, step.hash, step.thread))
+    return out
+
+
+def test_cover_transitions():
+    ev = transfer_model()
+    # The specs would fail, but every transition is still covered
+    sequences = covering_sequences(ev)
+    edges = {(e.src.hex(), e.dst.hex(), e.thread) for e in ev.graph.all_edges()}
+    assert len(edges) == 12
+    assert taken(ev, sequences) == edges
+    assert len(sequences) == 4
+    assert all(trace[0].thread is None for trace in sequences)
+    assert len(ev.specs) == 1
+
+
+def test_cover_actions():
+    sequences = covering_sequences(transfer_model(), cover="actions")
+    actions = {step.action for trace in sequences for step in trace[1:]}
+    assert actions == {"transfer@start", "transfer@deposit"}
+    assert len(sequences) == 1
+    assert len(sequences[0]) == 3
+
+    with pytest.raises(ValueError, match="can't cover 'lines'"):
+        covering_sequences(transfer_model(), cover="lines")
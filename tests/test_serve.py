@@ -0,0 +1,91 @@
+import json
+import threading
+import urllib.error
+import urllib.request
+
+import timewinder
+
+from timewinder.export import archive_run
+from timewinder.serve import Archive
+from timewinder.serve import make_server
+
+
+@timewinder.object
+class Lock:
+    def __init__(self):
+        self.holder = 0
+
+
+def archived(tmp_path):
+    lock = Lock()
+
+    @timewinder.process
+    def worker(lock, id):
+        lock.holder = id
+        yield "release"
+        lock.holder = 0
+
+    ev = timewinder.Evaluator(
+        objects=[lock],
+        threads=[worker(lock, 1), worker(lock, 2)],
+        record_graph=True,
+    )
+    path = str(tmp_path / "runs.db")
+    run_id = archive_run(path, ev, ev.check(steps=None))
+    return path, run_id, ev
+
+
+def test_archive_queries(tmp_path):
+    path, run_id, ev = archived(tmp_path)
+    archive = Archive(path)
+    assert [r["id"] for r in archive.runs()] == [run_id]
+
+    run = archive.run(run_id)
+    assert run["status"] == "pass"
+    assert run["states"] == ev.stats.states
+    assert archive.run(run_id + 1) is None
+
+    everything = archive.states(run_id)
+    assert len(everything) == ev.stats.states
+    assert everything[0]["initial"]
+    held_by_2 = archive.states(run_id, '"holder": 2')
+    assert 0 < len(held_by_2) < len(everything)
+    assert all(s["state"]["lock"]["holder"] == 2 for s in held_by_2)
+    assert archive.states(run_id, "%") == []
+
+    initial = archive.state(run_id, everything[0]["hash"])
+    assert initial["incoming"] == []
+    assert {t["action"] for t in initial["outgoing"]} == {"worker@start"}
+    after = archive.state(run_id, initial["outgoing"][0]["dst"])
+    assert after["incoming"][0]["src"] == initial["hash"]
+
+
+def test_server(tmp_path, monkeypatch):
+    path, run_id, _ = archived(tmp_path)
+    monkeypatch.setattr("timewinder.serve.MAX_LIMIT", 2)
+    server = make_server(path, port=0)
+    threading.Thread(target=server.serve_forever, daemon=True).start()
+    base = "http://%s:%d" % server.server_address[:2]
+    states = f"{base}/api/runs/{run_id}/states"
+    try:
+        with urllib.request.urlopen(base + "/") as r:
+            assert b"<title>timewinder</title>" in r.read()
+        with urllib.request.urlopen(states + "?q=holder&limit=1") as r:
+            assert len(json.load(r)) == 1
+        with urllib.request.urlopen(states + "?limit=50") as r:
+            assert len(json.load(r)) == 2
+        try:
+            urllib.request.urlopen(f"{base}/api/runs/{run_id + 1}")
+            assert False, "expected a 404"
+        except urllib.error.HTTPError as e:
+            assert e.code == 404
+        for limit in ("abc", "-1"):
+            try:
+                urllib.request.urlopen(f"{states}?limit={limit}")
+                assert False, "expected a 400"
+            except urllib.error.HTTPError as e:
+                assert e.code == 400
+                assert json.load(e) == {"error": "limit must be a whole number"}
+    finally:
+        server.shutdown()
+        server.server_close()
@@ -0,0 +1,130 @@
+import pytest
+import timewinder
+
+from timewinder.functions import Await
+from timewinder.result import Status
+from timewinder.simulation import ReplayError
+from timewinder.simulation import Simulator
+from timewinder.simulation import decode_choices
+from timewinder.simulation import encode_choices
+
+
+@timewinder.object
+class Die:
+    def __init__(self):
+        self.rolls = []
+
+
+def model():
+    die = Die()
+
+    @timewinder.process
+    def roll(die):
+        import random
+        from random import choice
+
+        for _ in range(3):
+            die.rolls.append(random.randint(1, 6))
+            yield "roll"
+        die.rolls.append(choice(["heads", "tails"]))
+
+    no_six = timewinder.ForAll(Die, lambda d: 6 not in d.rolls)
+    return timewinder.Evaluator(objects=[die], threads=[roll(die)], specs=[no_six])
+
+
+def test_random_is_seeded():
+    results = [model().simulate(runs=20, seed=3) for _ in range(2)]
+    assert results[0].status == Status.FAIL
+    assert [s.state for s in results[0].trace] == [s.state for s in results[1].trace]
+
+
+def test_random_rejected_when_checking():
+    result = model().check()
+    assert result.status == Status.ERROR
+    assert "random is only available when simulating" in result.error
+
+
+def test_replay():
+    sim = Simulator(model(), seed=5)
+    result = sim.run(runs=20)
+    assert result.status == Status.FAIL
+    log = encode_choices(sim.choices)
+
+    replayed = Simulator(model(), replay=decode_choices(log))
+    again = replayed.run(runs=20)
+    assert again.status == Status.FAIL
+    assert [s.state for s in again.trace] == [s.state for s in result.trace]
+    assert replayed.choices == sim.choices
+
+    with pytest.raises(ReplayError, match="choice 7 of 1"):
+        Simulator(model(), replay=[0, 7]).run()
+    with pytest.raises(ReplayError, match="isn't a choice log"):
+        decode_choices("1,x")
+
+
+@timewinder.object
+class Lock:
+    def __init__(self):
+        self.progress = 0
+
+
+def lock_model():
+    # Opening the lock takes ten turns of the right dial in a row, and any
+    # other dial resets it
+    @timewinder.process
+    def dial(lock, right):
+        while True:
+            if right:
+                lock.progress = lock.progress + 1
+            else:
+                lock.progress = 0
+            yield "turn"
+
+    lock = Lock()
+    dials = [dial(lock, True), dial(lock, False), dial(lock, False)]
+    locked = timewinder.ForAll(Lock, lambda lock: lock.progress < 10)
+    return timewinder.Evaluator(objects=[lock], threads=dials, specs=[locked])
+
+
+def test_guided():
+    # Uniformly random runs rarely turn the right dial ten times in a row
+    assert Simulator(lock_model(), seed=0).run(runs=100, depth=20).status == Status.PASS
+
+    sim = Simulator(lock_model(), seed=0, guided=True)
+    result = sim.run(runs=100, depth=20)
+    assert result.status == Status.FAIL
+    assert result.trace[-1].state["lock"] == {"progress": 10}
+    assert len(sim.corpus) != 0
+
+    replayed = Simulator(lock_model(), replay=sim.choices).run()
+    assert replayed.status == Status.FAIL
+    assert [s.state for s in replayed.trace] == [s.state for s in result.trace]
+
+
+@timewinder.object
+class Gate:
+    def __init__(self):
+        self.open = False
+
+
+@timewinder.process
+def wait_for_gate(gate):
+    Await(gate.open)
+
+
+def test_stuck():
+    def model(**kwargs):
+        gate = Gate()
+        return timewinder.Evaluator(
+            objects=[gate], threads=[wait_for_gate(gate)], **kwargs
+        )
+
+    # A run where every thread is blocked stops, rather than stuttering
+    # until the depth
+    sim = Simulator(model(), seed=0)
+    assert sim.run(runs=1, depth=50).status == Status.PASS
+    assert len(sim.last_thunk.trace) == 0
+
+    result = model(deadlock=True).simulate(runs=1, seed=0)
+    assert result.violation == "deadlock"
+    assert len(result.trace) == 1
@@ -0,0 +1,75 @@
+import pytest
+
+from timewinder.loader import LoadError
+from timewinder.result import Status
+from timewinder.sweep import format_matrix
+from timewinder.sweep import grid_points
+from timewinder.sweep import parse_axis
+from timewinder.sweep import sweep
+
+MODEL = """
+import timewinder
+
+
+@timewinder.object
+class Account:
+    def __init__(self, amt):
+        self.acc = amt
+
+
+@timewinder.process
+def withdraw(sender, amount):
+    sender.acc = sender.acc - amount
+    yield "again"
+    sender.acc = sender.acc - amount
+
+
+def model(amount=1, start=5):
+    alice = Account(start)
+    no_overdrafts = timewinder.ForAll(Account, lambda a: a.acc >= 0)
+    return timewinder.Evaluator(
+        objects=[alice], threads=[withdraw(alice, amount)], specs=[no_overdrafts]
+    )
+"""
+
+
+def test_parse_axis():
+    assert parse_axis("N=2..4") == ("N", [2, 3, 4])
+    assert parse_axis("N=1,2,4") == ("N", [1, 2, 4])
+    assert parse_axis("mode=fast") == ("mode", ["fast"])
+    with pytest.raises(LoadError, match="LOW..HIGH"):
+        parse_axis("N=a..b")
+    assert grid_points({"a": [1, 2], "b": ["x"]}) == [
+        {"a": 1, "b": "x"},
+        {"a": 2, "b": "x"},
+    ]
+
+
+def test_sweep(tmp_path):
+    path = tmp_path / "bank.py"
+    path.write_text(MODEL)
+    grid = {"amount": [1, 2, 3], "start": [5, 6]}
+    rows = sweep(str(path), grid)
+    parallel = sweep(str(path), grid, jobs=2)
+    outcomes = [(r.status, r.states) for r in rows]
+    assert [(r.status, r.states) for r in parallel] == outcomes
+    points = [(r.constants["amount"], r.constants["start"]) for r in rows]
+    assert points == [(1, 5), (1, 6), (2, 5), (2, 6), (3, 5), (3, 6)]
+    assert [r.status for r in rows] == [Status.PASS] * 4 + [Status.FAIL, Status.PASS]
+    assert rows[0].states == 3
+
+    lines = format_matrix(rows).splitlines()
+    assert lines[0].split() == ["amount", "start", "status", "states", "violation"]
+    assert lines[5].startswith("3       5      fail    3       [](no_overdrafts")
+
+    (row,) = sweep(str(path), {"nope": [1]})
+    assert row.status == Status.ERROR
+    assert "nope" in row.error
+
+    # Options configure each point's model, as they would for check
+    options = {"properties": ["no_overdrafts"]}
+    (row,) = sweep(str(path), {"amount": [3]}, options=options)
+    assert row.status == Status.FAIL
+    (row,) = sweep(str(path), {"amount": [3]}, options={"properties": ["nonsense"]})
+    assert row.status == Status.ERROR
+    assert "no property nonsense" in row.error
@@ -0,0 +1,81 @@
+import timewinder
+
+from timewinder.functions import Await
+from timewinder.functions import Done
+from timewinder.predicate import Expression
+from timewinder.result import Status
+
+
+@timewinder.object
+class Work:
+    def __init__(self, jobs):
+        self.jobs = jobs
+        self.ready = False
+
+
+@timewinder.process
+def waiter(w):
+    Await(w.ready)
+    w.jobs = 0
+
+
+@timewinder.process
+def worker(w):
+    while True:
+        if w.jobs == 0:
+            Done()
+        w.jobs = w.jobs - 1
+        yield "work"
+
+
+def test_deadlock():
+    def model(**kwargs):
+        w = Work(1)
+        return timewinder.Evaluator(objects=[w], threads=[waiter(w)], **kwargs)
+
+    assert model().check(steps=None).status == Status.PASS
+    result = model(deadlock=True).check(steps=None)
+    assert result.violation == "deadlock"
+    assert len(result.trace) == 1
+
+    # Searching for a goal, a deadlock is just where a behavior ends
+    ev = model(deadlock=True)
+    assert ev.reach(Expression("w.jobs == 0"), steps=None) is None
+    assert ev.deadlock
+
+    # A blocked thread can't take the behavior anywhere, fair or not
+    w = Work(1)
+    is_ready = timewinder.ForAll(Work, lambda w: w.ready)
+    ready = timewinder.Eventually(is_ready)
+    ev = timewinder.Evaluator(
+        objects=[w],
+        threads=[waiter(w)],
+        specs=[ready],
+        fairness=timewinder.Fairness.FAIR,
+    )
+    assert ev.check(steps=None).status == Status.FAIL
+
+
+def test_termination():
+    def model(**kwargs):
+        w = Work(2)
+        five = timewinder.ForAll(Work, lambda w: w.jobs == 5)
+        never = timewinder.Eventually(five)
+        return timewinder.Evaluator(
+            objects=[w],
+            threads=[worker(w)],
+            specs=[never],
+            fairness=timewinder.Fairness.WEAKLY_FAIR,
+            **kwargs,
+        )
+
+    result = model().check(steps=None)
+    assert result.violation == "<>(five:ForAll(<lambda>))"
+    assert [s.state["w"]["jobs"] for s in result.trace] == [2, 1, 0, 0]
+
+    # Done ends the process, so finishing isn't a deadlock
+    assert model(deadlock=True).check(steps=None).violation != "deadlock"
+    # Unless finishing isn't an acceptable end, when no liveness is judged there
+    assert model(termination=False).check(steps=None).status == Status.PASS
+    result = model(deadlock=True, termination=False).check(steps=None)
+    assert result.violation == "deadlock"
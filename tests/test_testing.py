@@ -0,0 +1,56 @@
+import json
+
+import pytest
+import timewinder
+
+from timewinder.testing import assert_golden
+from timewinder.testing import expect_fail
+from timewinder.testing import expect_pass
+
+
+@timewinder.object
+class Account:
+    def __init__(self, name, amt):
+        self.name = name
+        self.acc = amt
+
+
+def model(amount):
+    alice = Account("alice", 5)
+
+    @timewinder.process
+    def withdraw(sender, amount):
+        sender.acc = sender.acc - amount
+
+    no_overdrafts = timewinder.ForAll(Account, lambda a: a.acc >= 0)
+    return timewinder.Evaluator(
+        objects=[alice],
+        threads=[withdraw(alice, amount)],
+        specs=[no_overdrafts],
+    )
+
+
+def test_expect():
+    expect_pass(model(1))
+    expect_fail(model(10), violation="no_overdrafts", actions=["withdraw@start"])
+    with pytest.raises(AssertionError):
+        expect_fail(model(1))
+    with pytest.raises(AssertionError):
+        expect_pass(model(10))
+    with pytest.raises(AssertionError):
+        expect_fail(model(10), actions=["deposit@start"])
+
+
+def test_golden(tmp_path):
+    path = str(tmp_path / "golden" / "withdraw.json")
+    assert_golden(model(10).check(steps=None), path)
+    with open(path) as f:
+        saved = json.load(f)
+    assert saved["status"] == "fail"
+    assert saved["trace"][1]["state"]["alice"]["acc"] == -5
+
+    assert_golden(model(10).check(steps=None), path)
+    with pytest.raises(AssertionError, match="-5"):
+        assert_golden(model(9).check(steps=None), path)
+    assert_golden(model(9).check(steps=None), path, update=True)
+    assert_golden(model(9).check(steps=None), path)
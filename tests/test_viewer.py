@@ -0,0 +1,45 @@
+from timewinder.viewer import TraceView
+
+
+TRACE = [
+    {
+        "hash": "a",
+        "thread": None,
+        "action": None,
+        "location": None,
+        "state": {"alice": {"acc": 5, "log": {"n": 0}}, "_thread_0": {}},
+    },
+    {
+        "hash": "b",
+        "thread": 0,
+        "action": "withdraw@start",
+        "location": None,
+        "state": {"alice": {"acc": 4, "log": {"n": 0}}, "_thread_0": {}},
+    },
+]
+
+
+def test_collapsed_and_expanded():
+    view = TraceView(TRACE)
+    assert view.header() == ["Initial state (step 0/1)"]
+    lines = view.lines()
+    assert len(lines) == 1
+    assert lines[0][0].startswith("  + alice: ")
+    view.toggle_cursor()
+    texts = [t for t, _ in view.lines()]
+    assert texts == ["  - alice", "      acc: 5", '    + log: {"n": 0}']
+
+
+def test_diff_marks_changes():
+    view = TraceView(TRACE)
+    view.toggle(("alice",))
+    view.next()
+    view.next()
+    assert view.index == 1
+    assert view.header() == ["Step 1/1: thread 0 executes withdraw@start"]
+    texts = [t for t, _ in view.lines()]
+    assert texts[0] == "* - alice"
+    assert texts[1] == "*     acc: 4"
+    assert texts[2].startswith("    + log")
+    view.show_diff = False
+    assert view.lines()[0][0] == "  - alice"
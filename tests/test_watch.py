@@ -0,0 +1,66 @@
+import os
+
+import timewinder
+
+from timewinder.result import CheckResult
+from timewinder.watch import Watcher
+from timewinder.watch import summarize
+
+
+def test_waits_for_change_to_settle(tmp_path):
+    path = tmp_path / "model.py"
+    path.write_text("a")
+    os.utime(path, (1, 1))
+    sleeps = []
+    # The editor writes the file over two polls; only then does it settle
+    writes = {2: "b", 3: "bc"}
+
+    def sleep(secs):
+        sleeps.append(secs)
+        n = len(sleeps)
+        if n in writes:
+            path.write_text(writes[n])
+            os.utime(path, (n, n))
+
+    w = Watcher([str(path)], interval=1.0, debounce=0.1, sleep=sleep)
+    assert w.wait_for_change() == [str(path)]
+    assert sleeps == [1.0, 1.0, 0.1, 0.1]
+
+
+def test_notices_created_files(tmp_path):
+    path = tmp_path / "timewinder.toml"
+
+    def sleep(secs):
+        if not path.exists():
+            path.write_text("")
+
+    w = Watcher([str(path)], sleep=sleep)
+    assert w.last == {str(path): None}
+    assert w.wait_for_change() == [str(path)]
+
+
+@timewinder.object
+class Counter:
+    def __init__(self):
+        self.n = 0
+
+
+def test_summarize():
+    c = Counter()
+
+    @timewinder.process
+    def incr(c):
+        c.n = c.n + 1
+
+    ev = timewinder.Evaluator(objects=[c], threads=[incr(c)])
+    first = ev.check(steps=2)
+    assert summarize(first) == "PASS 2 states, 2 steps"
+
+    below = timewinder.ForAll(Counter, lambda c: c.n < 1)
+    c = Counter()
+    ev = timewinder.Evaluator(objects=[c], threads=[incr(c)], specs=[below])
+    second = ev.check(steps=2)
+    assert isinstance(second, CheckResult)
+    line = summarize(second, first)
+    assert line.startswith("FAIL [](below")
+    assert line.endswith(" in 1 steps; 2 states, 2 steps")
@@ -9,8 +9,24 @@ from .process import StopProcess
 from .process import ProcessException
 from .predicate import ForAll
 from .predicate import predicate
+from .predicate import invariant
 from .ltl import Always
 from .ltl import Eventually
+from .ltl import LeadsTo
+from .ltl import Next
+from .ltl import Until
+from .ltl import Release
+from .properties import always
+from .properties import eventually
 from .reinterp import interp as process
+from .pause import Fairness
+from .statetree import Symbol
+from .statetree import symbols
+from .statetree import Record
+from .statetree import SortedMap
+from .statetree import SortedSet
+from . import checks
 from .evaluation import ConstraintError
 from .evaluation import StutterConstraintError
+from .result import CheckResult
+from .metrics import Metric
@@ -1,6 +1,7 @@
-def main():
-    pass
+import sys
+
+from timewinder.cli import main
 
 
 if __name__ == "__main__":
-    main()
+    sys.exit(main())
@@ -0,0 +1,116 @@
+"""Predicates for common invariants, so they can be written in one line:
+
+    specs=[
+        checks.mutual_exclusion([a, b], ["worker@critical"]),
+        checks.bounded(Queue, "items", 3),
+    ]
+"""
+from collections.abc import Sized
+
+from typing import Any
+from typing import Callable
+from typing import Iterable
+from typing import List
+from typing import Optional
+from typing import TYPE_CHECKING
+
+from .object import ClassObject
+from .predicate import Predicate
+from .process import Process
+
+if TYPE_CHECKING:
+    from timewinder.statetree import StateController
+
+
+class Check(Predicate):
+    """A predicate over the whole state."""
+
+    def __init__(self, name: str, func: Callable[["StateController"], bool]):
+        self._name = name
+        self.func = func
+
+    @property
+    def name(self) -> str:
+        return self._name
+
+    def check(self, sc: "StateController") -> bool:
+        return self.func(sc)
+
+    def __repr__(self) -> str:
+        return self._name
+
+
+def mutual_exclusion(
+    processes: List[Process], steps: Iterable[str], name: str = "mutual_exclusion"
+) -> Check:
+    """At most one of the processes is about to take one of the steps, named
+    as in traces (`worker@critical`)."""
+    steps = set(steps)
+
+    def check(sc) -> bool:
+        inside = [p for p in processes if p.can_execute() and p.step_name() in steps]
+        return len(inside) <= 1
+
+    return Check(name, check)
+
+
+def bounded(objecttype, attr: str, n: int, name: Optional[str] = None) -> Check:
+    """Every object of the type has at most n in an attribute, or at most n
+    items if it's a collection."""
+
+    def check(sc) -> bool:
+        for obj in _objects(sc, objecttype):
+            v = getattr(obj, attr)
+            if isinstance(v, Sized):
+                v = len(v)
+            if v > n:
+                return False
+        return True
+
+    return Check(name or f"bounded_{attr}", check)
+
+
+def no_duplicates(objecttype, attr: str, name: Optional[str] = None) -> Check:
+    """No object of the type holds the same item twice in a collection."""
+
+    def check(sc) -> bool:
+        for obj in _objects(sc, objecttype):
+            seen: List[Any] = []
+            # Items may be lists or dicts, which can't be hashed
+            for item in getattr(obj, attr):
+                if item in seen:
+                    return False
+                seen.append(item)
+        return True
+
+    return Check(name or f"no_duplicate_{attr}", check)
+
+
+def aggregate(
+    processes: List[Process],
+    var: str,
+    check: Callable[[List[Any]], bool],
+    name: Optional[str] = None,
+) -> Check:
+    """Checks the values of a variable across processes, such as
+    `aggregate(workers, "held", lambda held: sum(held) <= 1)`. Processes
+    that haven't assigned it yet are left out."""
+
+    def check_values(sc) -> bool:
+        values = []
+        for p in processes:
+            try:
+                values.append(p.variable(var))
+            except KeyError:
+                continue
+        return check(values)
+
+    return Check(name or f"aggregate_{var}", check_values)
+
+
+def _objects(sc: "StateController", objecttype) -> List[ClassObject]:
+    return [
+        m
+        for m in sc.get_object_list()
+        if isinstance(m, ClassObject) and isinstance(m._instance, objecttype._cls)
+    ]
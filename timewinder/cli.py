@@ -0,0 +1,1239 @@
+import argparse
+import contextlib
+import json
+import linecache
+import logging
+import os
+import shlex
+import sys
+import textwrap
+import time
+import traceback
+import warnings
+
+from dataclasses import asdict
+from typing import Any
+from typing import Callable
+from typing import Dict
+from typing import List
+from typing import Optional
+
+import timewinder
+
+from .config import CONFIG_FILE
+from .config import Config
+from .config import find_config
+from .config import load_config
+from .describe import describe
+from .evaluation import Evaluator
+from .explore import Choice
+from .explore import Explorer
+from .export import archive_run
+from .export import format_tlc
+from .export import scc_report
+from .export import to_dot
+from .export import to_html
+from .export import to_itf
+from .export import trace_to_itf
+from .export import to_junit_xml
+from .export import to_mermaid
+from .export import to_pytest
+from .export import to_sarif
+from .loader import LoadError
+from .loader import compile_dir
+from .loader import configure
+from .lint import OPT_IN_RULES
+from .lint import RULES
+from .lint import lint_path
+from .loader import load_model
+from .loader import model_files
+from .loader import parse_constant
+from .pause import Fairness
+from .predicate import Expression
+from .process import ProcessException
+from .reinterp.debugger import DebugSession
+from .reinterp.process import BytecodeProcess
+from .reinterp.process import trace_logger
+from .reinterp.verify import TimewinderWarning
+from .reinterp.verify import VerificationError
+from .result import RESULT_VERSION
+from .result import CheckResult
+from .result import Status
+from .result import TraceStep
+from .result import build_trace
+from .result import encode_value
+from .result import project_trace
+from .result import trace_to_dicts
+from .scaffold import create_project
+from .serve import make_server
+from .sequences import COVER
+from .sequences import covering_sequences
+from .simulation import ReplayError
+from .simulation import Simulator
+from .simulation import decode_choices
+from .simulation import encode_choices
+from .sweep import format_matrix
+from .sweep import parse_axis
+from .sweep import sweep
+from .viewer import TraceView
+from .viewer import load_trace
+from .viewer import run_curses
+from .watch import Watcher
+from .watch import summarize
+
+
+# Exit codes, stable for scripts
+EXIT_PASS = 0
+EXIT_VIOLATION = 1
+# No violation found, but a bound stopped the search short of a proof
+EXIT_BOUNDED = 2
+EXIT_ERROR = 3
+
+EXIT_CODES = """\
+exit codes:
+  0  no violation, every state explored
+  1  a violation was found (for lint, a likely mistake; for reach, the goal
+     is unreachable)
+  2  no violation found, but a bound (--steps, --max-states, or simulating)
+     stopped the search
+  3  the model couldn't be loaded or run"""
+
+# Options that can be given more than once, making a list
+LIST_OPTIONS = ("constants", "properties", "disable", "enable", "show", "breakpoints")
+
+# The step bound for watch mode, unless --steps is given
+WATCH_STEPS = 20
+
+FORMATS = [
+    "text",
+    "json",
+    "tlc",
+    "junit",
+    "sarif",
+    "itf",
+    "mermaid",
+    "dot",
+    "pytest",
+    "html",
+]
+
+FAIRNESS = {
+    "none": Fairness.NORMAL,
+    "weak": Fairness.WEAKLY_FAIR,
+    "strong": Fairness.FAIR,
+}
+
+
+def main(argv: Optional[List[str]] = None) -> int:
+    try:
+        args = _parse(sys.argv[1:] if argv is None else argv)
+        if args.command is None:
+            _parser().print_help()
+            return EXIT_ERROR
+        if getattr(args, "model", "") is None:
+            raise LoadError(f"no model given, on the command line or in {CONFIG_FILE}")
+        return args.func(args)
+    except (LoadError, OSError, VerificationError) as e:
+        print(f"timewinder: {e}", file=sys.stderr)
+        return EXIT_ERROR
+    except Exception:
+        # Python would exit 1 for this, which means a violation
+        traceback.print_exc()
+        return EXIT_ERROR
+
+
+def _parse(argv: List[str]) -> argparse.Namespace:
+    """Parses the command line, with defaults from the timewinder.toml given
+    by --config, or else found from the current directory."""
+    pre = argparse.ArgumentParser(add_help=False)
+    pre.add_argument("--config", default=None)
+    pre.add_argument("--no-config", action="store_true")
+    known, _ = pre.parse_known_args(argv)
+    path = known.config
+    if path is None and not known.no_config:
+        path = find_config(os.getcwd())
+    config = None if path is None else load_config(path)
+    args = _parser(config).parse_args(argv)
+    args.argv = argv
+    return args
+
+
+def _parser(config: Optional[Config] = None) -> argparse.ArgumentParser:
+    parser = _Parser(
+        prog="timewinder",
+        description=timewinder.__doc__,
+        epilog=EXIT_CODES,
+        formatter_class=argparse.RawDescriptionHelpFormatter,
+    )
+    parser.add_argument("--version", action="version", version=timewinder.__version__)
+    parser.add_argument(
+        "--config",
+        default=None if config is None else config.path,
+        metavar="FILE",
+        help=f"read defaults from this file, instead of the nearest {CONFIG_FILE}",
+    )
+    parser.add_argument(
+        "--no-config", action="store_true", help=f"don't read {CONFIG_FILE}"
+    )
+    sub = parser.add_subparsers(dest="command")
+
+    check = sub.add_parser("check", help="exhaustively evaluate a model")
+    _model_args(check)
+    check.add_argument("--steps", type=int, default=None, help="stop after N steps")
+    check.add_argument(
+        "--heuristic",
+        default=None,
+        metavar="EXPR",
+        help="explore states where this expression over the objects is lowest first",
+    )
+    check.add_argument(
+        "--max-states",
+        type=int,
+        default=10000,
+        help="stop a --heuristic search after N states",
+    )
+    check.add_argument(
+        "--watch", action="store_true", help="re-check whenever the model changes"
+    )
+    check.add_argument(
+        "--largest",
+        type=int,
+        default=0,
+        metavar="N",
+        help="print the N largest states, by approximate size, to stderr",
+    )
+    _output_args(check)
+    check.set_defaults(func=cmd_check)
+
+    simulate = sub.add_parser("simulate", help="check random behaviors of a model")
+    _model_args(simulate)
+    simulate.add_argument("--runs", type=int, default=100)
+    simulate.add_argument("--depth", type=int, default=100)
+    simulate.add_argument("--seed", type=int, default=None)
+    simulate.add_argument(
+        "--guided", action="store_true", help="build on runs that found new states"
+    )
+    _output_args(simulate)
+    simulate.set_defaults(func=cmd_simulate)
+
+    run = sub.add_parser("run", help="run and print a single behavior of a model")
+    _model_args(run)
+    run.add_argument("--depth", type=int, default=100)
+    run.add_argument("--seed", type=int, default=0)
+    run.add_argument(
+        "--replay",
+        default=None,
+        metavar="LOG",
+        help="repeat the choices logged by a failing simulation",
+    )
+    _show_arg(run)
+    run.add_argument(
+        "--trace-instructions",
+        action="store_true",
+        help="log every bytecode instruction executed to stderr",
+    )
+    run.set_defaults(func=cmd_run)
+
+    reach = sub.add_parser("reach", help="find a shortest behavior reaching a goal")
+    _model_args(reach)
+    reach.add_argument(
+        "--goal",
+        required=True,
+        help="Python expression over the objects, eg 'alice.acc < 0'",
+    )
+    reach.add_argument("--steps", type=int, default=None, help="stop after N steps")
+    _show_arg(reach)
+    reach.set_defaults(func=cmd_reach)
+
+    sequences = sub.add_parser(
+        "sequences", help="generate behaviors covering every transition, for tests"
+    )
+    _model_args(sequences)
+    sequences.add_argument("--steps", type=int, default=None, help="stop after N steps")
+    sequences.add_argument("--cover", choices=COVER, default="transitions")
+    sequences.add_argument("--format", choices=["json", "itf"], default="json")
+    sequences.add_argument(
+        "-o", "--output", default=None, help="write the sequences here"
+    )
+    sequences.set_defaults(func=cmd_sequences)
+
+    scc = sub.add_parser(
+        "scc", help="list the strongly connected components of the states"
+    )
+    _model_args(scc)
+    scc.add_argument("--steps", type=int, default=None, help="stop after N steps")
+    scc.add_argument("--format", choices=["text", "json"], default="text")
+    scc.set_defaults(func=cmd_scc)
+
+    sweep = sub.add_parser("sweep", help="check a model across a grid of constants")
+    _model_args(sweep)
+    sweep.add_argument(
+        "--steps", type=int, default=None, help="stop each check after N steps"
+    )
+    sweep.add_argument(
+        "-j",
+        "--jobs",
+        type=int,
+        default=os.cpu_count() or 1,
+        help="checks to run at once",
+    )
+    sweep.add_argument("--format", choices=["text", "json"], default="text")
+    sweep.set_defaults(func=cmd_sweep)
+
+    describe = sub.add_parser(
+        "describe", help="print the documentation of a model file"
+    )
+    describe.add_argument(
+        "model", nargs="?", default=None, help="path to the model file"
+    )
+    describe.add_argument("--entry", default="model", help="name of the model function")
+    describe.add_argument("--format", choices=["text", "json"], default="text")
+    describe.set_defaults(func=cmd_describe)
+
+    compile = sub.add_parser(
+        "compile", help="load a model and summarize it, or every model in a directory"
+    )
+    _model_args(compile)
+    compile.set_defaults(func=cmd_compile)
+
+    lint = sub.add_parser(
+        "lint", help="find likely mistakes in a model, or every model in a directory"
+    )
+    _model_args(lint)
+    lint.add_argument(
+        "--disable",
+        action="append",
+        default=None,
+        metavar="RULE",
+        help=f"skip this rule, one of {', '.join(RULES)}",
+    )
+    lint.add_argument(
+        "--enable",
+        action="append",
+        default=None,
+        metavar="RULE",
+        help="apply this rule, though it's off by default or the config disables it",
+    )
+    lint.add_argument("--format", choices=["text", "json"], default="text")
+    lint.set_defaults(func=cmd_lint)
+
+    debug = sub.add_parser("debug", help="step through a process's bytecode")
+    _model_args(debug)
+    debug.add_argument("--thread", type=int, default=0, help="thread to debug")
+    debug.add_argument(
+        "-b",
+        "--break",
+        dest="breakpoints",
+        type=int,
+        action="append",
+        default=None,
+        metavar="LINE",
+        help="stop before executing this source line",
+    )
+    debug.set_defaults(func=cmd_debug)
+
+    explore = sub.add_parser("explore", help="walk a model by choosing each action")
+    _model_args(explore)
+    explore.set_defaults(func=cmd_explore)
+
+    dis = sub.add_parser("dis", help="print the bytecode of a model's processes")
+    _model_args(dis)
+    dis.add_argument("--thread", type=int, default=None, help="only this thread")
+    dis.add_argument(
+        "--with-source", action="store_true", help="interleave the source lines"
+    )
+    dis.set_defaults(func=cmd_dis)
+
+    new = sub.add_parser("new", help="create a starter project")
+    new.add_argument("name", help="directory to create")
+    new.set_defaults(func=cmd_new)
+
+    serve = sub.add_parser("serve", help="browse archived runs in a web browser")
+    serve.add_argument("db", help="database written by --archive")
+    serve.add_argument("--host", default="127.0.0.1")
+    serve.add_argument("--port", type=int, default=8000)
+    serve.set_defaults(func=cmd_serve)
+
+    trace = sub.add_parser("trace", help="work with saved counterexamples")
+    trace_sub = trace.add_subparsers(dest="trace_command", required=True)
+    view = trace_sub.add_parser("view", help="browse a trace from a JSON report")
+    view.add_argument("report", help="output of check --format json")
+    view.set_defaults(func=cmd_trace_view)
+    if config is not None:
+        for name, p in sub.choices.items():
+            _apply_config(p, config, name)
+    return parser
+
+
+class _Parser(argparse.ArgumentParser):
+    """Options given more than once default to None, rather than a list, so
+    a list given on the command line can replace the config's instead of
+    being appended to it."""
+
+    def parse_known_args(self, args=None, namespace=None):
+        ns, rest = super().parse_known_args(args, namespace)
+        lists = getattr(ns, "config_lists", {})
+        for dest in LIST_OPTIONS:
+            if hasattr(ns, dest) and getattr(ns, dest) is None:
+                setattr(ns, dest, list(lists.get(dest, [])))
+        return ns, rest
+
+
+def _apply_config(p: argparse.ArgumentParser, config: Config, command: str) -> None:
+    actions = {a.dest: a for a in p._actions}
+    defaults: Dict[str, Any] = {"config_lists": {}}
+    for dest, value, own in config.options(command):
+        # Settings for every command skip those they don't suit
+        action = actions.get(dest)
+        if action is None:
+            if own:
+                raise LoadError(f"{config.path}: {command} has no option {dest}")
+            continue
+        if action.choices is not None and value not in action.choices:
+            if own:
+                choices = ", ".join(action.choices)
+                raise LoadError(f"{config.path}: {dest} must be one of {choices}")
+            continue
+        if dest in LIST_OPTIONS:
+            if not isinstance(value, list):
+                value = [value]
+            defaults["config_lists"][dest] = value
+        else:
+            defaults[dest] = value
+    p.set_defaults(**defaults)
+
+
+def _model_args(p: argparse.ArgumentParser) -> None:
+    p.add_argument(
+        "model",
+        nargs="?",
+        default=None,
+        help=f"Python file defining the model, if {CONFIG_FILE} doesn't give one",
+    )
+    p.add_argument(
+        "--entry", default=None, help="name of the model function or Evaluator"
+    )
+    p.add_argument(
+        "-D",
+        dest="constants",
+        action="append",
+        default=None,
+        metavar="NAME=VALUE",
+        help="pass a constant to the model function",
+    )
+    p.add_argument(
+        "--fairness",
+        choices=list(FAIRNESS),
+        default=None,
+        help="for processes that don't declare their own",
+    )
+    p.add_argument(
+        "--deadlock",
+        action="store_true",
+        default=None,
+        help="report states where unfinished processes are all blocked",
+    )
+    p.add_argument(
+        "--no-termination",
+        dest="termination",
+        action="store_false",
+        default=None,
+        help="don't accept states where every process has finished as an end",
+    )
+    p.add_argument(
+        "--property",
+        dest="properties",
+        action="append",
+        default=None,
+        metavar="NAME",
+        help="only check this property (by its name or its predicate's)",
+    )
+    p.add_argument("-q", "--quiet", action="store_true", help="hide progress")
+
+
+def _output_args(p: argparse.ArgumentParser) -> None:
+    p.add_argument("--format", choices=FORMATS, default="text")
+    p.add_argument("-o", "--output", default=None, help="write the report here")
+    p.add_argument(
+        "--archive",
+        default=None,
+        metavar="DB",
+        help="also store the run, and every state it explored, in this SQLite database",
+    )
+    p.add_argument(
+        "--sut",
+        default="make_sut",
+        metavar="MODULE:FUNCTION",
+        help="what the pytest format's test calls to build the system under test",
+    )
+    _show_arg(p)
+    p.add_argument(
+        "--profile",
+        action="store_true",
+        help="print the most expensive actions and lines to stderr",
+    )
+    p.add_argument(
+        "--flamegraph",
+        default=None,
+        metavar="FILE",
+        help="profile, and write folded stacks for inferno or flamegraph.pl here",
+    )
+    p.add_argument(
+        "--flamegraph-weight",
+        choices=["time", "states"],
+        default="time",
+        help="weigh the flamegraph by time, or by the new states each action finds",
+    )
+
+
+def _show_arg(p: argparse.ArgumentParser) -> None:
+    p.add_argument(
+        "--show",
+        action="append",
+        default=None,
+        metavar="PATH",
+        help="only show this variable in traces (eg, alice.acc), when it changes",
+    )
+
+
+def _load(args) -> Evaluator:
+    constants = dict(parse_constant(c) for c in args.constants)
+    with warnings.catch_warnings(record=True) as caught:
+        warnings.simplefilter("always", TimewinderWarning)
+        ev = load_model(args.model, args.entry, constants)
+    for w in caught:
+        if not issubclass(w.category, TimewinderWarning):
+            warnings.warn_explicit(w.message, w.category, w.filename, w.lineno)
+        elif not args.quiet:
+            print(f"{w.filename}:{w.lineno}: warning: {w.message}", file=sys.stderr)
+    return configure(ev, **_check_options(args))
+
+
+def _check_options(args) -> Dict[str, Any]:
+    """The options of _model_args that configure how a model is checked."""
+    return {
+        "fairness": None if args.fairness is None else FAIRNESS[args.fairness],
+        "deadlock": args.deadlock,
+        "termination": args.termination,
+        "properties": args.properties,
+    }
+
+
+@contextlib.contextmanager
+def _progress(args):
+    """Keeps evaluation progress off stdout, which is saved for reports."""
+    if args.quiet:
+        with open(os.devnull, "w") as devnull:
+            with contextlib.redirect_stdout(devnull):
+                yield
+    else:
+        with contextlib.redirect_stdout(sys.stderr):
+            yield
+
+
+def cmd_check(args) -> int:
+    if args.watch:
+        paths = [os.path.abspath(args.model)]
+        if args.config is not None:
+            paths.append(args.config)
+        watcher = Watcher(paths)
+        try:
+            watch_loop(args, watcher)
+        except KeyboardInterrupt:
+            pass
+        return EXIT_PASS
+    ev = _load(args)
+    if args.format in ("dot", "html") or args.archive is not None:
+        ev.record_graph = True
+    ev.profile = args.profile or args.flamegraph is not None
+    score = None if args.heuristic is None else _score(args.heuristic)
+    with _progress(args):
+        result = ev.check(steps=args.steps, score=score, max_states=args.max_states)
+    if args.largest:
+        sys.stderr.write(_largest_report(ev, args.largest))
+    return _report(args, ev, result)
+
+
+def _largest_report(ev: Evaluator, n: int) -> str:
+    cas = ev.state_controller.cas
+    lines = [f"States take {ev.stats.cas_bytes} bytes in all; the largest:"]
+    for h, size in ev.largest_states[:n]:
+        objects = cas.get(h)
+        parts = ", ".join(
+            f"{name} {cas.tree_size(objects[name])}"
+            for name in sorted(objects, key=lambda name: -cas.tree_size(objects[name]))
+        )
+        lines.append(f"  {h.hex()[:7]} {size} bytes: {parts}")
+    return "\n".join(lines) + "\n"
+
+
+def _score(expr: str) -> Callable[[Dict[str, Any]], float]:
+    code = compile(expr, "<heuristic>", "eval")
+    return lambda objects: eval(code, {}, objects)
+
+
+def watch_loop(args, watcher: Watcher, runs: Optional[int] = None) -> None:
+    """Checks the model, then again after every change, printing one line
+    per check, and re-reading the config if it changes."""
+    previous: Optional[CheckResult] = None
+    count = 0
+    while True:
+        stamp = time.strftime("%H:%M:%S")
+        steps = WATCH_STEPS if args.steps is None else args.steps
+        linecache.checkcache()
+        try:
+            ev = _load(args)
+            with open(os.devnull, "w") as devnull:
+                with contextlib.redirect_stdout(devnull):
+                    result = ev.check(steps=steps)
+            print(f"{stamp} {summarize(result, previous)}", flush=True)
+            previous = result
+        except Exception as e:
+            print(f"{stamp} ERROR {e!r}", flush=True)
+        count += 1
+        if runs is not None and count >= runs:
+            return
+        changed = watcher.wait_for_change()
+        if args.config in changed:
+            try:
+                args = _parse(args.argv)
+            except LoadError as e:
+                print(f"{time.strftime('%H:%M:%S')} ERROR {e}", flush=True)
+
+
+def cmd_simulate(args) -> int:
+    ev = _load(args)
+    ev.profile = args.profile or args.flamegraph is not None
+    sim = Simulator(ev, args.seed, guided=args.guided)
+    with _progress(args):
+        result = sim.run(args.runs, args.depth)
+    if result.status != Status.PASS:
+        command = _replay_command(args, encode_choices(sim.choices))
+        print(f"Replay with: {command}", file=sys.stderr)
+    return _report(args, ev, result)
+
+
+def _replay_command(args, log: str) -> str:
+    """The run command repeating a simulated behavior, with the options that
+    built and configured its model."""
+    argv = ["timewinder", "run", "--replay", log, "--depth", str(args.depth)]
+    if args.entry is not None:
+        argv += ["--entry", args.entry]
+    for c in args.constants:
+        argv += ["-D", c]
+    if args.fairness is not None:
+        argv += ["--fairness", args.fairness]
+    if args.deadlock:
+        argv.append("--deadlock")
+    if args.termination is False:
+        argv.append("--no-termination")
+    for name in args.properties:
+        argv += ["--property", name]
+    argv.append(args.model)
+    return " ".join(shlex.quote(a) for a in argv)
+
+
+@contextlib.contextmanager
+def _instruction_trace(enabled: bool):
+    if not enabled:
+        yield
+        return
+    handler = logging.StreamHandler(sys.stderr)
+    level = trace_logger.level
+    trace_logger.addHandler(handler)
+    trace_logger.setLevel(logging.DEBUG)
+    try:
+        yield
+    finally:
+        trace_logger.removeHandler(handler)
+        trace_logger.setLevel(level)
+
+
+def cmd_run(args) -> int:
+    ev = _load(args)
+    try:
+        replay = None if args.replay is None else decode_choices(args.replay)
+        sim = Simulator(ev, args.seed, replay)
+        with _progress(args), _instruction_trace(args.trace_instructions):
+            result = sim.run(runs=1, depth=args.depth)
+    except ReplayError as e:
+        print(f"timewinder: {e}", file=sys.stderr)
+        return EXIT_ERROR
+    if result.status == Status.ERROR:
+        print(f"timewinder: {result.error}", file=sys.stderr)
+        return EXIT_ERROR
+    if result.status == Status.PASS:
+        assert sim.last_thunk is not None
+        result.trace = build_trace(ev, sim.last_thunk)
+    if args.show:
+        result = result.project(args.show)
+    for line in _trace_lines(result.trace, threads=bool(args.show)):
+        print(line)
+    if result.status == Status.FAIL:
+        print(f"Violated: {result.violation}")
+        return EXIT_VIOLATION
+    return EXIT_PASS
+
+
+def cmd_reach(args) -> int:
+    """Exits 0 if the goal is reached, like grep finding a match."""
+    ev = _load(args)
+    try:
+        goal = Expression(args.goal)
+    except SyntaxError as e:
+        print(f"timewinder: bad goal: {e}", file=sys.stderr)
+        return EXIT_ERROR
+    try:
+        with _progress(args):
+            trace = ev.reach(goal, steps=args.steps)
+    except ProcessException as e:
+        print(f"timewinder: {e!r}", file=sys.stderr)
+        return EXIT_ERROR
+    if trace is None:
+        if ev.bounded:
+            print("Unreachable within the step bound.")
+            return EXIT_BOUNDED
+        print("Unreachable.")
+        return EXIT_VIOLATION
+    if args.show:
+        trace = project_trace(trace, args.show)
+    print(f"Reached {args.goal} in {len(trace) - 1} steps:")
+    for line in _trace_lines(trace, threads=bool(args.show)):
+        print(line)
+    return EXIT_PASS
+
+
+def cmd_sequences(args) -> int:
+    ev = _load(args)
+    try:
+        with _progress(args):
+            found = covering_sequences(ev, steps=args.steps, cover=args.cover)
+    except ProcessException as e:
+        print(f"timewinder: {e!r}", file=sys.stderr)
+        return EXIT_ERROR
+    if args.format == "itf":
+        doc: Any = [trace_to_itf(trace) for trace in found]
+    else:
+        doc = {
+            "version": RESULT_VERSION,
+            "cover": args.cover,
+            "sequences": [trace_to_dicts(trace) for trace in found],
+        }
+    text = json.dumps(doc, indent=2, default=encode_value) + "\n"
+    if args.output is None:
+        sys.stdout.write(text)
+    else:
+        with open(args.output, "w") as f:
+            f.write(text)
+    return EXIT_PASS
+
+
+def cmd_scc(args) -> int:
+    ev = _load(args)
+    ev.specs = []
+    ev.record_graph = True
+    try:
+        with _progress(args):
+            ev.evaluate(args.steps)
+    except ProcessException as e:
+        print(f"timewinder: {e!r}", file=sys.stderr)
+        return EXIT_ERROR
+    report = scc_report(ev)
+    if args.format == "json":
+        print(json.dumps(report, indent=2))
+        return EXIT_PASS
+    components = report["components"]
+    cyclic = sum(1 for c in components if c["cyclic"])
+    print(f"{report['states']} states in {len(components)} components, {cyclic} cyclic")
+    for i, c in enumerate(components):
+        if not c["cyclic"] and not c["terminal"]:
+            continue
+        kinds = [k for k in ("cyclic", "terminal", "fair_cycle") if c[k]]
+        described = ", ".join(k.replace("_", " ") for k in kinds)
+        line = f"  {i}: {c['size']} states, {described}"
+        if c["threads"]:
+            line += ", moved by threads " + ", ".join(str(t) for t in c["threads"])
+        print(line)
+    return EXIT_PASS
+
+
+def cmd_sweep(args) -> int:
+    """Each -D gives the values of a constant, as N=2..6 or N=1,2,4."""
+    grid = dict(parse_axis(c) for c in args.constants)
+    options = _check_options(args)
+    rows = sweep(args.model, grid, args.entry, args.steps, args.jobs, options)
+    if args.format == "json":
+        doc = [
+            {
+                "constants": r.constants,
+                "status": r.status.value,
+                "bounded": r.bounded,
+                "states": r.states,
+                "violation": r.violation,
+                "error": r.error,
+            }
+            for r in rows
+        ]
+        print(json.dumps(doc, indent=2, default=encode_value))
+    else:
+        sys.stdout.write(format_matrix(rows))
+    statuses = {r.status for r in rows}
+    if Status.ERROR in statuses:
+        return EXIT_ERROR
+    if Status.FAIL in statuses:
+        return EXIT_VIOLATION
+    if any(r.bounded for r in rows):
+        return EXIT_BOUNDED
+    return EXIT_PASS
+
+
+def cmd_describe(args) -> int:
+    try:
+        docs = describe(args.model, args.entry)
+    except (OSError, SyntaxError) as e:
+        print(f"timewinder: {e}", file=sys.stderr)
+        return EXIT_ERROR
+    if args.format == "json":
+        print(json.dumps([asdict(d) for d in docs], indent=2))
+        return EXIT_PASS
+    module, defs = docs[0], docs[1:]
+    if module.doc:
+        print(module.doc + "\n")
+    for d in defs:
+        print(f"{d.kind} {d.name} (lines {d.line}-{d.end_line})")
+        if d.doc:
+            print(textwrap.indent(d.doc, "    "))
+    return EXIT_PASS
+
+
+def cmd_compile(args) -> int:
+    if os.path.isdir(args.model):
+        return _compile_dir(args)
+    ev = _load(args)
+    objects = [k for k in ev.state_controller.tree if not k.startswith("_thread_")]
+    print(f"Objects: {', '.join(objects)}")
+    for i, t in enumerate(ev.threads):
+        print(f"Thread {i}: {', '.join(t.step_names())}")
+    for s in ev.specs:
+        kind = "liveness" if s.is_liveness() else "safety"
+        print(f"Spec ({kind}): {s}")
+    return EXIT_PASS
+
+
+def _compile_dir(args) -> int:
+    results = compile_dir(args.model)
+    for c in results:
+        if not args.quiet:
+            for w in c.warnings:
+                print(w, file=sys.stderr)
+        path = os.path.relpath(c.path, args.model)
+        if c.evaluator is not None:
+            ev = c.evaluator
+            print(f"{path}: ok, {len(ev.threads)} threads, {len(ev.specs)} specs")
+        else:
+            print(f"{path}: {c.error}")
+    failed = sum(1 for c in results if not c.ok)
+    print(f"{len(results)} models, {failed} failed to load")
+    return EXIT_ERROR if failed else EXIT_PASS
+
+
+def cmd_lint(args) -> int:
+    unknown = [r for r in args.disable + args.enable if r not in RULES]
+    if unknown:
+        rules = ", ".join(RULES)
+        raise LoadError(f"no lint rule {', '.join(unknown)}; there's {rules}")
+    disabled = (set(args.disable) | set(OPT_IN_RULES)) - set(args.enable)
+    constants = dict(parse_constant(c) for c in args.constants)
+    paths = model_files(args.model) if os.path.isdir(args.model) else [args.model]
+    findings = []
+    errors = []
+    for path in paths:
+        try:
+            findings.extend(lint_path(path, args.entry, constants, disabled))
+        except Exception as e:
+            errors.append({"path": path, "error": f"{type(e).__name__}: {e}"})
+    if args.format == "json":
+        out = {"findings": [asdict(f) for f in findings], "errors": errors}
+        print(json.dumps(out, indent=2))
+    else:
+        for f in findings:
+            print(f)
+        for e in errors:
+            print(f"{e['path']}: {e['error']}")
+    if errors:
+        return EXIT_ERROR
+    return EXIT_VIOLATION if findings else EXIT_PASS
+
+
+EXPLORE_HELP = """\
+NUMBER            take the action with that number
+back              return to the previous state
+restart [N]       start over, from initial state N (default 0)
+state             print the whole current state, threads included
+quit (q)          exit"""
+
+
+def cmd_explore(args) -> int:
+    ev = _load(args)
+    ex = Explorer(ev)
+    choices = _print_explore(ex)
+    while True:
+        print("(explore) ", end="", flush=True)
+        line = sys.stdin.readline()
+        if line == "":
+            return EXIT_PASS
+        cmd, _, arg = line.strip().partition(" ")
+        if cmd in ("q", "quit"):
+            return EXIT_PASS
+        try:
+            if cmd.isdigit():
+                if int(cmd) >= len(choices):
+                    print(f"No action {cmd}")
+                    continue
+                c = choices[int(cmd)]
+                ex.take(c)
+                step = len(ex.thunk.trace)
+                print(f"Step {step}, thread {c.thread} executes {c.action}")
+            elif cmd == "back":
+                if not ex.back():
+                    print("Nothing to undo")
+                    continue
+            elif cmd == "restart":
+                ex.start(int(arg or 0))
+            elif cmd == "state":
+                for name, val in sorted(ex.state().items()):
+                    print(f"  {name}: {val}")
+                continue
+            else:
+                print(EXPLORE_HELP)
+                continue
+            choices = _print_explore(ex)
+        except Exception as e:
+            print(f"error: {e!r}")
+
+
+def _print_explore(ex: Explorer) -> List[Choice]:
+    """Prints the current state and the actions that can follow it."""
+    for name, val in sorted(ex.state().items()):
+        if not name.startswith("_thread_"):
+            print(f"  {name}: {val}")
+    if ex.violation is not None:
+        print(f"Violated: {ex.violation}")
+    choices = ex.choices()
+    if len(choices) == 0:
+        print("No actions can be taken")
+    for i, c in enumerate(choices):
+        print(f"[{i}] thread {c.thread} executes {c.action}")
+        for line in str(c.diff).splitlines():
+            if not line[2:].startswith("_thread_"):
+                print(f"      {line.strip()}")
+    return choices
+
+
+def cmd_dis(args) -> int:
+    ev = _load(args)
+    threads = list(enumerate(ev.threads))
+    if args.thread is not None:
+        if not 0 <= args.thread < len(ev.threads):
+            print(f"timewinder: no thread {args.thread}", file=sys.stderr)
+            return EXIT_ERROR
+        threads = [threads[args.thread]]
+    for i, thread in threads:
+        print(f"Thread {i} ({thread.name}):")
+        if isinstance(thread, BytecodeProcess):
+            print(thread.interp.disassemble(args.with_source))
+        else:
+            print("  not an interpreted process\n")
+    return EXIT_PASS
+
+
+DEBUG_HELP = """\
+step (s)          execute one instruction
+back              undo the last instruction
+next (n)          execute until the source line changes
+continue (c)      execute until a breakpoint or the end of the step
+break (b) LINE    stop before LINE; without LINE, list breakpoints
+delete (d) LINE   remove a breakpoint
+print (p) EXPR    evaluate EXPR against the process's variables
+watch (w) EXPR    print EXPR every time execution stops
+locals (l)        print the process's variables
+stack             print the operand stack
+where             print the current location
+quit (q)          exit the debugger"""
+
+
+def cmd_debug(args) -> int:
+    ev = _load(args)
+    if not 0 <= args.thread < len(ev.threads):
+        print(f"timewinder: no thread {args.thread}", file=sys.stderr)
+        return EXIT_ERROR
+    thread = ev.threads[args.thread]
+    if not isinstance(thread, BytecodeProcess):
+        print(f"timewinder: thread {args.thread} is not a process", file=sys.stderr)
+        return EXIT_ERROR
+    sc = ev.state_controller
+    # Start from the first initial state, as the evaluator would
+    sc.restore(list(sc.commit())[0])
+    dbg = DebugSession(thread, sc)
+    for line in args.breakpoints:
+        dbg.add_breakpoint(line)
+    _print_where(dbg)
+    while True:
+        print("(tw) ", end="", flush=True)
+        line = sys.stdin.readline()
+        if line == "":
+            return EXIT_PASS
+        cmd, _, arg = line.strip().partition(" ")
+        arg = arg.strip()
+        if cmd in ("q", "quit"):
+            return EXIT_PASS
+        try:
+            _debug_command(dbg, cmd, arg)
+        except Exception as e:
+            print(f"error: {e!r}")
+
+
+def _debug_command(dbg: DebugSession, cmd: str, arg: str) -> None:
+    handler = DEBUG_COMMANDS.get(cmd)
+    if handler is None:
+        print(DEBUG_HELP)
+        return
+    handler(dbg, arg)
+
+
+def _debug_run(
+    advance: Callable[[DebugSession], Any],
+) -> Callable[[DebugSession, str], None]:
+    def run(dbg: DebugSession, arg: str) -> None:
+        if dbg.done:
+            print("Process has finished")
+            return
+        cont = advance(dbg)
+        if cont.yield_msg:
+            print(f"Yielded {cont.yield_msg!r}")
+        _print_where(dbg)
+        for expr, val in dbg.watch_values().items():
+            print(f"  {expr} = {val!r}")
+
+    return run
+
+
+def _debug_back(dbg: DebugSession, arg: str) -> None:
+    if not dbg.step_back():
+        print("Nothing to undo")
+        return
+    _print_where(dbg)
+
+
+def _debug_break(dbg: DebugSession, arg: str) -> None:
+    if arg:
+        dbg.add_breakpoint(int(arg))
+    print(f"Breakpoints: {sorted(dbg.breakpoints)}")
+
+
+def _debug_delete(dbg: DebugSession, arg: str) -> None:
+    dbg.remove_breakpoint(int(arg))
+    print(f"Breakpoints: {sorted(dbg.breakpoints)}")
+
+
+def _debug_print(dbg: DebugSession, arg: str) -> None:
+    print(repr(dbg.evaluate(arg)))
+
+
+def _debug_watch(dbg: DebugSession, arg: str) -> None:
+    dbg.watch(arg)
+    print(f"  {arg} = {dbg.watch_values()[arg]!r}")
+
+
+def _debug_locals(dbg: DebugSession, arg: str) -> None:
+    for name, val in sorted(dbg.variables().items()):
+        print(f"  {name} = {val!r}")
+
+
+def _debug_stack(dbg: DebugSession, arg: str) -> None:
+    for val in reversed(dbg.stack()):
+        print(f"  {val!r}")
+
+
+DEBUG_COMMANDS: Dict[str, Callable[[DebugSession, str], None]] = {
+    "s": _debug_run(DebugSession.step),
+    "step": _debug_run(DebugSession.step),
+    "n": _debug_run(DebugSession.step_line),
+    "next": _debug_run(DebugSession.step_line),
+    "c": _debug_run(DebugSession.cont),
+    "continue": _debug_run(DebugSession.cont),
+    "back": _debug_back,
+    "b": _debug_break,
+    "break": _debug_break,
+    "d": _debug_delete,
+    "delete": _debug_delete,
+    "p": _debug_print,
+    "print": _debug_print,
+    "w": _debug_watch,
+    "watch": _debug_watch,
+    "l": _debug_locals,
+    "locals": _debug_locals,
+    "stack": _debug_stack,
+    "where": lambda dbg, arg: _print_where(dbg),
+}
+
+
+def _print_where(dbg: DebugSession) -> None:
+    if dbg.done:
+        print(f"{dbg.process.name}: finished")
+        return
+    inst = dbg.interp.instructions[dbg.interp.pc]
+    loc = dbg.interp.location()
+    if loc is None:
+        print(f"{dbg.process.name}: {inst.opname} {inst.argrepr}")
+        return
+    source = linecache.getline(loc[0], loc[1]).strip()
+    print(f"{loc[0]}:{loc[1]}: {source}")
+    print(f"  -> {inst.offset} {inst.opname} {inst.argrepr}")
+
+
+def cmd_new(args) -> int:
+    try:
+        files = create_project(args.name)
+    except OSError as e:
+        print(f"timewinder: {e}", file=sys.stderr)
+        return EXIT_ERROR
+    for f in files:
+        print(f"created {f}")
+    print(f"\nTry: cd {args.name} && timewinder check")
+    return EXIT_PASS
+
+
+def cmd_serve(args) -> int:
+    if not os.path.exists(args.db):
+        raise LoadError(f"{args.db} doesn't exist; write it with check --archive")
+    server = make_server(args.db, args.host, args.port)
+    host, port = server.server_address[:2]
+    print(f"Serving {args.db} at http://{host}:{port}/", flush=True)
+    try:
+        server.serve_forever()
+    except KeyboardInterrupt:
+        pass
+    finally:
+        server.server_close()
+    return EXIT_PASS
+
+
+def cmd_trace_view(args) -> int:
+    try:
+        view = TraceView(load_trace(args.report))
+    except (OSError, ValueError) as e:
+        print(f"timewinder: {e}", file=sys.stderr)
+        return EXIT_ERROR
+    if sys.stdout.isatty():
+        run_curses(view)
+        return EXIT_PASS
+    # Not a terminal: print every step, fully expanded
+    for i in range(len(view.trace)):
+        view.index = i
+        _expand_all(view)
+        print("\n".join(view.header()))
+        for text, _ in view.lines():
+            print(text)
+        print()
+    return EXIT_PASS
+
+
+def _expand_all(view: TraceView) -> None:
+    while True:
+        collapsed = [
+            p for _, p in view.lines() if p is not None and p not in view.expanded
+        ]
+        if not collapsed:
+            return
+        view.expanded.update(collapsed)
+
+
+def _report(args, ev: Evaluator, result: CheckResult) -> int:
+    if ev.profiler is not None and args.profile:
+        sys.stderr.write(ev.profiler.report())
+    if ev.profiler is not None and args.flamegraph is not None:
+        with open(args.flamegraph, "w") as f:
+            f.write(ev.profiler.folded(args.flamegraph_weight))
+    if args.archive is not None:
+        archive_run(args.archive, ev, result)
+    if args.show:
+        result = result.project(args.show)
+    text = _format(args.format, ev, result, threads=bool(args.show), sut=args.sut)
+    if args.output is None:
+        sys.stdout.write(text)
+    else:
+        with open(args.output, "w") as f:
+            f.write(text)
+    return _exit_code(result)
+
+
+def _exit_code(result: CheckResult) -> int:
+    if result.status == Status.PASS:
+        return EXIT_BOUNDED if result.bounded else EXIT_PASS
+    if result.status == Status.FAIL:
+        return EXIT_VIOLATION
+    return EXIT_ERROR
+
+
+def _format(
+    fmt: str,
+    ev: Evaluator,
+    result: CheckResult,
+    threads: bool = False,
+    sut: str = "make_sut",
+) -> str:
+    if fmt == "json":
+        return result.dumps(indent=2) + "\n"
+    if fmt == "tlc":
+        return format_tlc(result)
+    if fmt == "junit":
+        return to_junit_xml(result) + "\n"
+    if fmt == "sarif":
+        return json.dumps(to_sarif(result, base=os.getcwd()), indent=2) + "\n"
+    if fmt == "itf":
+        return json.dumps(to_itf(result), indent=2) + "\n"
+    if fmt == "mermaid":
+        return to_mermaid(result)
+    if fmt == "dot":
+        return to_dot(ev)
+    if fmt == "html":
+        return to_html(result, ev)
+    if fmt == "pytest" and result.status == Status.FAIL:
+        return to_pytest(result, sut)
+    return _format_text(result, threads)
+
+
+def _format_text(result: CheckResult, threads: bool = False) -> str:
+    stats = result.stats
+    lines = []
+    if result.status == Status.PASS:
+        bound = " (bounded)" if result.bounded else ""
+        lines.append(f"No violations found{bound}.")
+    elif result.status == Status.ERROR:
+        lines.append(f"Error: {result.error}")
+    else:
+        lines.append(f"Violation: {result.violation}")
+        lines.extend(_trace_lines(result.trace, threads))
+    for m in result.metrics:
+        lines.append(f"Metric {m}")
+    lines.append(
+        f"{stats.states} states, {stats.steps} steps, "
+        f"{stats.thread_executions} thread executions"
+    )
+    return "\n".join(lines) + "\n"
+
+
+def _trace_lines(trace: List[TraceStep], threads: bool = False) -> List[str]:
+    """Each step of a trace and the state it reached. Threads' states are
+    left out unless asked for, as they're mostly bytecode bookkeeping."""
+    lines = []
+    for i, step in enumerate(trace):
+        if step.thread is None:
+            lines.append("Initial State:")
+        else:
+            lines.append(f"Step {i}, thread {step.thread} executes {step.action}")
+        for name in sorted(step.state):
+            if threads or not name.startswith("_thread_"):
+                lines.append(f"  {name}: {step.state[name]}")
+    return lines
@@ -0,0 +1,89 @@
+"""Reads timewinder.toml, a project's defaults for the command line.
+
+Keys at the top level apply to every command that has the option, and a
+table named for a command holds its own; either way, keys are the long
+option names. `[constants]` are passed to the model as with `-D`. Paths
+are relative to the file. Anything given on the command line wins; for
+options that can be given more than once, such as `-D` or `--property`,
+the command line's list replaces the config's.
+
+    model = "model.py"
+    fairness = "weak"
+    properties = ["no_overdrafts"]
+
+    [constants]
+    amount = 3
+
+    [check]
+    steps = 20
+    format = "json"
+    output = "report.json"
+"""
+import os
+
+from dataclasses import dataclass
+
+from typing import Any
+from typing import Dict
+from typing import Iterator
+from typing import Optional
+from typing import Tuple
+
+from .loader import LoadError
+
+try:
+    import tomllib
+except ImportError:
+    import tomli as tomllib
+
+
+CONFIG_FILE = "timewinder.toml"
+
+# Options that name files, resolved against the config's directory
+PATH_OPTIONS = ("model", "output", "archive", "flamegraph")
+
+
+@dataclass
+class Config:
+    path: str
+    data: Dict[str, Any]
+
+    def options(self, command: str) -> Iterator[Tuple[str, Any, bool]]:
+        """The defaults for a command, as (option dest, value, whether it
+        was set for this command rather than for all of them)."""
+        section = self.data.get(command, {})
+        if not isinstance(section, dict):
+            raise LoadError(f"{self.path}: {command} must be a table")
+        for table, own in ((self.data, False), (section, True)):
+            for key, value in table.items():
+                if isinstance(value, dict):
+                    continue
+                dest = key.replace("-", "_")
+                if dest in PATH_OPTIONS and isinstance(value, str):
+                    value = os.path.join(os.path.dirname(self.path), value)
+                yield dest, value, own
+        constants = self.data.get("constants", {})
+        if constants:
+            yield "constants", [f"{k}={v!r}" for k, v in constants.items()], False
+
+
+def find_config(start: str) -> Optional[str]:
+    """The nearest timewinder.toml in start or a directory above it."""
+    path = os.path.abspath(start)
+    while True:
+        candidate = os.path.join(path, CONFIG_FILE)
+        if os.path.isfile(candidate):
+            return candidate
+        parent = os.path.dirname(path)
+        if parent == path:
+            return None
+        path = parent
+
+
+def load_config(path: str) -> Config:
+    try:
+        with open(path, "rb") as f:
+            data = tomllib.load(f)
+    except tomllib.TOMLDecodeError as e:
+        raise LoadError(f"{path}: {e}")
+    return Config(os.path.abspath(path), data)
@@ -0,0 +1,83 @@
+from abc import ABC
+from abc import abstractmethod
+from dataclasses import dataclass
+
+from typing import Any
+from typing import Dict
+from typing import Optional
+from typing import TYPE_CHECKING
+
+from .object import ClassObject
+
+if TYPE_CHECKING:
+    from .evaluation import Evaluator
+    from .evaluation import EvalThunk
+
+
+class SystemUnderTest(ABC):
+    """An implementation to be checked against a model, one transition at a time."""
+
+    @abstractmethod
+    def apply(self, thread_id: int, name: str) -> None:
+        """Perform the action of the given model thread, named as it was
+        before the step ran (eg, `withdraw@deposit`)."""
+        pass
+
+    @abstractmethod
+    def observe(self) -> Dict[str, Dict[str, Any]]:
+        """Return the observable state, keyed by object name and then attribute.
+
+        Only the objects and attributes returned are compared against the model."""
+        pass
+
+
+@dataclass
+class Divergence:
+    step: int
+    thread_id: Optional[int]
+    object_name: str
+    attribute: str
+    expected: Any
+    observed: Any
+
+    def __str__(self) -> str:
+        where = "initial state" if self.step == 0 else f"step {self.step}"
+        return (
+            f"{where}: {self.object_name}.{self.attribute} "
+            f"expected {self.expected!r}, observed {self.observed!r}"
+        )
+
+
+def check_conformance(
+    ev: "Evaluator", thunk: "EvalThunk", sut: SystemUnderTest
+) -> Optional[Divergence]:
+    """Replays the trace in thunk against the system under test, returning the
+    first point where the observed state differs from the model."""
+    sc = ev.state_controller
+    sc.restore(thunk.initial_hash())
+    d = _compare(sc, sut.observe(), 0, None)
+    if d is not None:
+        return d
+
+    prev_hash = thunk.initial_hash()
+    for step, (tid, hash) in enumerate(zip(thunk.trace, thunk.hashes[1:]), 1):
+        sc.restore(prev_hash)
+        sut.apply(tid, ev.threads[tid].name)
+        sc.restore(hash)
+        d = _compare(sc, sut.observe(), step, tid)
+        if d is not None:
+            return d
+        prev_hash = hash
+    return None
+
+
+def _compare(sc, observed, step, tid) -> Optional[Divergence]:
+    for name, attrs in observed.items():
+        obj = sc.tree.get(name)
+        if not isinstance(obj, ClassObject):
+            raise KeyError(f"No model object named {name}")
+        model = obj.get_state()
+        for attr, val in attrs.items():
+            if model.get(attr) != val:
+                return Divergence(step, tid, name, attr, model.get(attr), val)
+    return None
@@ -0,0 +1,65 @@
+import ast
+
+from dataclasses import dataclass
+
+from typing import List
+from typing import Optional
+
+
+# What a definition is, by the name of its decorator
+KINDS = {
+    "process": "process",
+    "step": "step",
+    "object": "object",
+    "invariant": "property",
+    "always": "property",
+    "eventually": "property",
+}
+
+
+@dataclass
+class Doc:
+    """The documentation of a module or one of its definitions."""
+
+    kind: str
+    name: str
+    doc: Optional[str]
+    line: int
+    end_line: int
+
+
+def describe(path: str, entry: str = "model") -> List[Doc]:
+    """Reads the docstrings of a model file, without running it: the
+    module's, then those of its processes, objects, properties and other
+    top-level definitions, in the order they're written. The function named
+    entry is described as the model."""
+    with open(path) as f:
+        source = f.read()
+    tree = ast.parse(source, path)
+    lines = source.count("\n") + 1
+    out = [Doc("module", "", ast.get_docstring(tree), 1, lines)]
+    for node in tree.body:
+        if not isinstance(node, (ast.FunctionDef, ast.ClassDef)):
+            continue
+        out.append(
+            Doc(
+                _kind(node, entry),
+                node.name,
+                ast.get_docstring(node),
+                min([node.lineno] + [d.lineno for d in node.decorator_list]),
+                getattr(node, "end_lineno", node.lineno),
+            )
+        )
+    return out
+
+
+def _kind(node, entry: str) -> str:
+    for d in node.decorator_list:
+        if isinstance(d, ast.Call):
+            d = d.func
+        name = d.attr if isinstance(d, ast.Attribute) else getattr(d, "id", "")
+        if name in KINDS:
+            return KINDS[name]
+    if node.name == entry:
+        return "model"
+    return "class" if isinstance(node, ast.ClassDef) else "function"
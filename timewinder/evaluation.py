@@ -1,6 +1,13 @@
+import heapq
+import logging
+from typing import Any
+from typing import Callable
+from typing import Dict
 from typing import List
+from typing import Tuple
 from typing import Set
 from typing import Optional
+from typing import TYPE_CHECKING
 import progressbar
 
 from copy import copy
@@ -8,21 +15,46 @@ from dataclasses import dataclass
 from dataclasses import field
 from inspect import isfunction
 
+from timewinder.statetree import Canonicalizer
 from timewinder.statetree import StateController
 from timewinder.statetree import MemoryCAS
 from timewinder.statetree import Hash
+from timewinder.pause import Continue
 from timewinder.pause import Fairness
+from timewinder.pause import PauseReason
+
+from .graph import Component
+from .graph import Edge
+from .graph import StateGraph
 
 from .ltl import TTrace
 from .ltl import LTLOp
 from .ltl import Always
+from .ltl import Not
 
 from .process import Process
 from .process import Step
 from .process import FuncProcess
+from .process import ProcessException
 
 from .predicate import Predicate
 from .predicate import predicate
+from .predicate import model_objects
+
+from .metrics import Metric
+from .metrics import MetricSummary
+from .profile import Profiler
+
+if TYPE_CHECKING:
+    from .result import CheckResult
+    from .result import TraceStep
+
+
+logger = logging.getLogger(__name__)
+
+
+# How many of the largest states to remember
+LARGEST_STATES = 10
 
 
 @dataclass
@@ -30,8 +62,11 @@ class EvaluatorStats:
     thread_executions: int = 0
     states: int = 0
     cas_objects: int = 0
+    # Roughly, of everything stored in the CAS
+    cas_bytes: int = 0
     steps: int = 0
     final_states: int = 0
+    largest_state_bytes: int = 0
 
 
 @dataclass
@@ -71,6 +106,10 @@ def _prepare_threads(threads) -> List[Process]:
     return out
 
 
+def _predicate_names(spec) -> List[str]:
+    return [p.name for p in spec.get_predicates()]
+
+
 def _prepare_specs(specs) -> List[LTLOp]:
     if specs is None:
         return []
@@ -88,7 +127,20 @@ def _prepare_specs(specs) -> List[LTLOp]:
 
 
 class Evaluator:
-    def __init__(self, *, objects=None, threads: List = None, specs: List = None):
+    def __init__(
+        self,
+        *,
+        objects=None,
+        threads: List = None,
+        specs: List = None,
+        record_graph: bool = False,
+        profile: bool = False,
+        canonicalizer: Optional[Canonicalizer] = None,
+        fairness: Fairness = Fairness.NORMAL,
+        metrics: Optional[List[Metric]] = None,
+        deadlock: bool = False,
+        termination: bool = True,
+    ):
         self.state_controller = StateController(MemoryCAS())
         if objects is not None:
             for m in objects:
@@ -102,26 +154,60 @@ class Evaluator:
                 t.on_register_evaluator(i)
                 self.state_controller.mount(f"_thread_{i}", t)
         self.specs = _prepare_specs(specs)
+        self.canonicalizer = canonicalizer or Canonicalizer()
+        # For processes that don't declare their own
+        self.fairness = fairness
+        self.metrics = metrics or []
+        # Whether a state where no process can move, though some haven't
+        # finished, is a violation
+        self.deadlock = deadlock
+        # Whether a state where every process has finished is an acceptable
+        # end to a behavior; if not, liveness isn't judged there, and with
+        # deadlock it's a violation too
+        self.termination = termination
+        self._metric_summaries: List[MetricSummary] = []
         self._evaled_states: Set[bytes] = set()
         self._stats: EvaluatorStats = EvaluatorStats()
+        # A min-heap of (size, hash) of the largest states evaluated
+        self._largest: List[Tuple[int, bytes]] = []
+        # Which bound stopped the last evaluation short, if one did
+        self._bound: Optional[str] = None
+        self.record_graph = record_graph
+        self.graph: Optional[StateGraph] = None
+        self.profile = profile
+        self.profiler: Optional[Profiler] = None
 
     def _initialize_evaluation(self):
+        self._evaled_states = set()
         self._stats = EvaluatorStats()
+        self._largest = []
+        self._metric_summaries = [MetricSummary(m.name) for m in self.metrics]
+        if self.record_graph:
+            self.graph = StateGraph()
+        if self.profile:
+            self.profiler = Profiler()
+        for t in self.threads:
+            t.verify()
+            t.set_profiler(self.profiler)
+            t.set_random(None)
         preds: List[List[Predicate]] = [s.get_predicates() for s in self.specs]
         # Flatten the list
         self.preds = [item for sub in preds for item in sub]
         for i, p in enumerate(self.preds):
             p.set_index(i)
 
+    def _initial_thunks(self) -> List[EvalThunk]:
+        out = []
+        for h in self.state_controller.commit():
+            if self.graph is not None:
+                self.graph.add_initial(h)
+            pred_traces = [TTrace([]) for i in self.preds]
+            out.append(EvalThunk(trace=[], hashes=[h], predicate_traces=pred_traces))
+        return out
+
     def evaluate(self, steps: Optional[int] = 5):
         self._initialize_evaluation()
-        initial_hashes = self.state_controller.commit()
-        next_queue = []
-        for h in initial_hashes:
-            pred_traces = [TTrace([]) for i in self.preds]
-            next_queue.append(
-                EvalThunk(trace=[], hashes=[h], predicate_traces=pred_traces)
-            )
+        next_queue = self._initial_thunks()
 
         if steps is None:
             steps = 2 ** 30
@@ -132,26 +218,145 @@ class Evaluator:
                 print("No more states to evaluate")
                 break
             print(f"Evaluating Step {step} ({len(state_queue)} states)...")
+            logger.debug("step %d: evaluating %d states", step, len(state_queue))
             self._stats.steps += 1
             for thunk in progressbar.progressbar(state_queue):
                 new_runs = self._eval_state(thunk)
                 next_queue.extend(new_runs)
+        self._bound = "steps" if len(next_queue) != 0 else None
+        if len(self.metrics) != 0:
+            # The step bound ends these behaviors
+            for thunk in next_queue:
+                self.state_controller.restore(thunk.state_hash())
+                self._measure()
+
+    def search(self, score: Callable[[Dict[str, Any]], float], max_states: int = 10000):
+        """Evaluates the model best-first instead of step by step: the state
+        with the lowest score, given the model's objects by name, is explored
+        next. A score that falls as states get closer to a suspected bug
+        finds it sooner in a space too large to exhaust, though the trace
+        found may not be the shortest. Stops after max_states states."""
+        self._initialize_evaluation()
+        sc = self.state_controller
+        queue: List[Tuple[float, int, EvalThunk]] = []
+        for t in self._initial_thunks():
+            heapq.heappush(queue, (score(model_objects(sc)), len(queue), t))
+        pushed = len(queue)
+        print(f"Searching up to {max_states} states...")
+        while len(queue) != 0 and self._stats.states < max_states:
+            _, _, thunk = heapq.heappop(queue)
+            self._stats.steps = max(self._stats.steps, len(thunk.trace) + 1)
+            for t in self._eval_state(thunk):
+                sc.restore(t.state_hash())
+                heapq.heappush(queue, (score(model_objects(sc)), pushed, t))
+                pushed += 1
+        self._bound = "max_states" if len(queue) != 0 else None
+
+    def check(
+        self,
+        steps: Optional[int] = 5,
+        score: Optional[Callable[[Dict[str, Any]], float]] = None,
+        max_states: int = 10000,
+    ) -> "CheckResult":
+        """Evaluates the model, returning a CheckResult rather than raising
+        on a violation. Given a score, it searches best-first instead; see
+        search."""
+        from .result import CheckResult
+
+        try:
+            if score is None:
+                self.evaluate(steps)
+            else:
+                self.search(score, max_states)
+        except ConstraintError as e:
+            return CheckResult.from_violation(self, e)
+        except ProcessException as e:
+            return CheckResult.from_error(self, e)
+        return CheckResult.from_success(self)
+
+    def reach(
+        self, goal: Predicate, steps: Optional[int] = 5
+    ) -> Optional[List["TraceStep"]]:
+        """Searches for a state where goal holds, ignoring the specs. Returns
+        the trace of a shortest behavior reaching one, or None if none does
+        within the steps; `bounded` then says whether there were more to
+        explore. Deadlocks aren't reported: they're only where behaviors
+        end."""
+        from .result import build_trace
+
+        specs, deadlock = self.specs, self.deadlock
+        self.specs = _prepare_specs([Always(Not(goal))])
+        self.deadlock = False
+        try:
+            self.evaluate(steps)
+        except ConstraintError as e:
+            if getattr(e, "spec", None) is not self.specs[0]:
+                raise
+            return build_trace(self, e.thunk)
+        finally:
+            self.specs, self.deadlock = specs, deadlock
+        return None
+
+    def components(self) -> List[Component]:
+        """The strongly connected components of the graph recorded by the
+        last evaluation (see `record_graph`)."""
+        if self.graph is None:
+            raise ValueError("Evaluator must be created with record_graph=True")
+        return self.graph.components()
+
+    @property
+    def bounded(self) -> bool:
+        """Whether the last evaluation stopped with states left to explore."""
+        return self._bound is not None
+
+    def simulate(
+        self, runs: int = 100, depth: int = 100, seed: Optional[int] = None
+    ) -> "CheckResult":
+        """Checks random behaviors instead of exhaustively evaluating."""
+        from .simulation import Simulator
+
+        return Simulator(self, seed).run(runs, depth)
 
     def _eval_preds(self, t: EvalThunk):
         for i, p in enumerate(self.preds):
-            b = p.check(self.state_controller)
+            if self.profiler is None:
+                b = p.check(self.state_controller)
+            else:
+                start = self.profiler.clock()
+                b = p.check(self.state_controller)
+                self.profiler.record_predicate(p.name, self.profiler.clock() - start)
             t.predicate_traces[i].append(b)
 
-    def _should_stutter(self) -> bool:
-        # TODO: Fairness, etc.
+    def _should_stutter(self, t: EvalThunk) -> bool:
+        """Whether a behavior may stop at this state, which is where liveness
+        properties are judged. It can't while it must run a thread, nor while
+        a fair thread can run. Behaviors that loop forever among fair threads
+        are never judged."""
+        if len(t.must_run) != 0:
+            return False
+        if not self.termination and self._terminated():
+            return False
+        for thread in self.threads:
+            if thread.can_execute() and self.thread_fairness(thread) != Fairness.NORMAL:
+                return False
         return True
 
-    def _check_liveness(self, spec, t: EvalThunk):
-        if self._should_stutter():
+    def _terminated(self) -> bool:
+        return all(not thread.can_execute() for thread in self.threads)
+
+    def thread_fairness(self, thread: Process) -> Fairness:
+        """The fairness of the action a thread takes next."""
+        fairness = thread.fairness()
+        return self.fairness if fairness is None else fairness
+
+    def _check_liveness(self, spec, t: EvalThunk, stopped: bool = False):
+        if stopped or self._should_stutter(t):
             trace = spec.eval_traces(t.predicate_traces)
             ok = trace[0]
             if not ok:
+                logger.info("liveness property %s violated", spec)
                 err = StutterConstraintError(str(spec))
+                err.spec = spec
                 err.thunk = t
                 err.state = self.state_controller.tree
                 raise err
@@ -160,11 +365,38 @@ class Evaluator:
         trace = spec.eval_traces(t.predicate_traces)
         ok = trace[0]
         if not ok:
+            logger.info("safety property %s violated", spec)
             err = ConstraintError(str(spec))
+            err.spec = spec
             err.thunk = t
             err.state = self.state_controller.tree
             raise err
 
+    def add_spec(self, spec) -> None:
+        """Adds a spec after construction, unless it's checked already."""
+        if spec in self.specs:
+            return
+        (prepared,) = _prepare_specs([spec])
+        if isinstance(spec, LTLOp):
+            self.specs.append(prepared)
+            return
+        checked = [p for s in self.specs for p in s.get_predicates()]
+        if all(p in checked for p in prepared.get_predicates()):
+            return
+        self.specs.append(prepared)
+
+    def select_specs(self, names: List[str]) -> None:
+        """Keeps only the specs named: by the name reports give them, or by
+        the name of one of their predicates."""
+        known = {n: s for s in self.specs for n in [str(s)] + _predicate_names(s)}
+        unknown = [n for n in names if n not in known]
+        if unknown:
+            raise ValueError(
+                f"no property {', '.join(unknown)}; there's {', '.join(sorted(known))}"
+            )
+        selected = [known[n] for n in names]
+        self.specs = [s for s in self.specs if s in selected]
+
     def _check_constraints(self, t: EvalThunk):
         for spec in self.specs:
             if spec.is_liveness():
@@ -173,10 +405,12 @@ class Evaluator:
                 self._check_safety(spec, t)
 
     def _eval_state(self, t: EvalThunk) -> List[EvalThunk]:
-        if t.state_hash().bytes in self._evaled_states:
+        fingerprint = self.fingerprint(t.state_hash())
+        if fingerprint in self._evaled_states:
             return []
         self._stats.states += 1
-        self._evaled_states.add(t.state_hash().bytes)
+        self._evaled_states.add(fingerprint)
+        self._note_size(t.state_hash())
         self.state_controller.restore(t.state_hash())
         self._eval_preds(t)
         try:
@@ -192,12 +426,54 @@ class Evaluator:
 
         if len(runnable_threads) == 0:
             self._stats.final_states += 1
-            # TODO: Check for deadlocking when awaiting
+            self._measure()
+            if self.deadlock and not self.termination:
+                self._deadlocked(t)
             return []
-        return self._execute_threads(runnable_threads, t)
+        out, moved = self._execute_threads(runnable_threads, t)
+        if not moved:
+            self._stuck(t)
+        return out
 
-    def _execute_threads(self, thread_ids: List[int], t: EvalThunk):
+    def _note_size(self, h: Hash) -> None:
+        size = self.state_controller.cas.tree_size(h)
+        self._stats.largest_state_bytes = max(self._stats.largest_state_bytes, size)
+        if len(self._largest) < LARGEST_STATES:
+            heapq.heappush(self._largest, (size, h.bytes))
+        elif size > self._largest[0][0]:
+            heapq.heapreplace(self._largest, (size, h.bytes))
+
+    @property
+    def largest_states(self) -> List[Tuple[Hash, int]]:
+        """The largest states evaluated, by their approximate size in bytes,
+        largest first."""
+        return [(Hash(h), size) for size, h in sorted(self._largest, reverse=True)]
+
+    def _stuck(self, t: EvalThunk) -> None:
+        """The threads that can run are all blocked, so the behavior stops
+        here, whatever their fairness."""
+        self._stats.final_states += 1
+        self.state_controller.restore(t.state_hash())
+        self._measure()
+        if self.deadlock:
+            self._deadlocked(t)
+        for spec in self.specs:
+            if spec.is_liveness():
+                self._check_liveness(spec, t, stopped=True)
+
+    def _deadlocked(self, t: EvalThunk) -> None:
+        logger.info("deadlock")
+        err = ConstraintError("deadlock")
+        err.thunk = t
+        err.state = self.state_controller.tree
+        raise err
+
+    def _execute_threads(
+        self, thread_ids: List[int], t: EvalThunk
+    ) -> Tuple[List[EvalThunk], bool]:
+        """The states the threads lead to, and whether any of them moved."""
         pre_restored = True
+        moved = False
         out = []
         for thread_id in thread_ids:
             if pre_restored:
@@ -208,18 +484,53 @@ class Evaluator:
             new_thunk.must_run = []
             new_thunk.trace.append(thread_id)
             thread = self.threads[thread_id]
-            self._stats.thread_executions += 1
-            cont = thread.execute(self.state_controller)
+            if self.graph is not None or self.profiler is not None:
+                action = thread.step_name()
+            cont = self._run_thread(thread)
             next_hashes = self.state_controller.commit()
+            if logger.isEnabledFor(logging.DEBUG):
+                logger.debug(
+                    "thread %d (%s) from %s: %s",
+                    thread_id,
+                    thread.name,
+                    t.state_hash().hex()[:7],
+                    cont.kind.name,
+                )
             for h in next_hashes:
-                if h.bytes in self._evaled_states:
+                if h != t.state_hash():
+                    moved = True
+                elif cont.kind == PauseReason.BLOCKED:
+                    # Not a step, so not an edge
+                    continue
+                if self.graph is not None:
+                    self.graph.add_edge(Edge(t.state_hash(), h, thread_id, action))
+                seen = self.fingerprint(h) in self._evaled_states
+                if self.profiler is not None:
+                    self.profiler.record_successor(action, not seen)
+                if seen:
                     continue
                 t_with_hash = new_thunk.clone()
                 t_with_hash.hashes.append(h)
                 if cont.fairness == Fairness.IMMEDIATE:
                     t_with_hash.must_run = [thread_id]
                 out.append(t_with_hash)
-        return out
+        return out, moved
+
+    def fingerprint(self, h: Hash) -> bytes:
+        """Identifies a state for deduplication; see Canonicalizer."""
+        return self.canonicalizer.fingerprint(self.state_controller.cas, h)
+
+    def _run_thread(self, thread: Process) -> Continue:
+        self._stats.thread_executions += 1
+        if self.profiler is None:
+            return thread.execute(self.state_controller)
+        action = thread.step_name()
+        self.profiler.action = action
+        start = self.profiler.clock()
+        cont = thread.execute(self.state_controller)
+        self.profiler.record_action(action, self.profiler.clock() - start)
+        self.profiler.action = None
+        return cont
 
     def replay_thunk(self, t: EvalThunk):
         print("Initial State:")
@@ -240,10 +551,23 @@ class Evaluator:
     def _print_state_space(self):
         self.state_controller.cas.debug_print()
 
+    def _measure(self) -> None:
+        """Records the metrics of a behavior ending in the current state."""
+        if len(self.metrics) == 0:
+            return
+        objects = model_objects(self.state_controller)
+        for m, summary in zip(self.metrics, self._metric_summaries):
+            summary.add(m.measure(objects))
+
+    @property
+    def metric_summaries(self) -> List[MetricSummary]:
+        return [copy(s) for s in self._metric_summaries]
+
     @property
     def stats(self) -> EvaluatorStats:
         s = copy(self._stats)
         s.cas_objects = self.state_controller.cas.size()
+        s.cas_bytes = self.state_controller.cas.bytes_used()
         return s
 
 
@@ -251,6 +575,7 @@ class ConstraintError(BaseException):
     def __init__(self, name, thunk=None):
         self.name = name
         self.thunk = thunk
+        self.spec = None
         self.state = None
 
 
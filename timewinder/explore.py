@@ -0,0 +1,120 @@
+from dataclasses import dataclass
+
+from typing import Any
+from typing import Dict
+from typing import List
+from typing import Optional
+from typing import TYPE_CHECKING
+
+from timewinder.pause import Fairness
+from timewinder.statetree import Hash
+from timewinder.statetree import StateDiff
+
+from .evaluation import ConstraintError
+from .evaluation import EvalThunk
+from .evaluation import StutterConstraintError
+from .ltl import TTrace
+
+if TYPE_CHECKING:
+    from .evaluation import Evaluator
+
+
+@dataclass
+class Choice:
+    """An action that can be taken from the current state, and where it
+    leads. A nondeterministic action offers one Choice per outcome."""
+
+    thread: int
+    action: str
+    hash: Hash
+    diff: StateDiff
+    must_run: bool = False
+
+
+class Explorer:
+    """Walks a model one chosen action at a time, rather than evaluating
+    every interleaving, to get a feel for how it behaves.
+
+    The specs are checked at every state reached; a violation is recorded
+    in `violation` rather than stopping the walk, so it's possible to step
+    back and try another way."""
+
+    def __init__(self, ev: "Evaluator"):
+        self.ev = ev
+        ev._initialize_evaluation()
+        self.initial_states: List[Hash] = list(ev.state_controller.commit())
+        self.path: List[EvalThunk] = []
+        self.violation: Optional[str] = None
+        self.start(0)
+
+    @property
+    def thunk(self) -> EvalThunk:
+        return self.path[-1]
+
+    def start(self, i: int) -> None:
+        """Starts over from one of the initial states."""
+        t = EvalThunk(
+            trace=[],
+            hashes=[self.initial_states[i]],
+            predicate_traces=[TTrace([]) for _ in self.ev.preds],
+        )
+        self.path = []
+        self._enter(t)
+
+    def state(self) -> Dict[str, Any]:
+        return self.ev.state_controller.cas.restore(self.thunk.state_hash())
+
+    def choices(self) -> List[Choice]:
+        ev = self.ev
+        sc = ev.state_controller
+        t = self.thunk
+        sc.restore(t.state_hash())
+        if len(t.must_run) != 0:
+            runnable = t.must_run
+        else:
+            runnable = [
+                i for i, thread in enumerate(ev.threads) if thread.can_execute()
+            ]
+        out = []
+        for tid in runnable:
+            sc.restore(t.state_hash())
+            thread = ev.threads[tid]
+            action = thread.step_name()
+            cont = ev._run_thread(thread)
+            for h in sc.commit():
+                diff = sc.diff(t.state_hash(), h)
+                immediate = cont.fairness == Fairness.IMMEDIATE
+                out.append(Choice(tid, action, h, diff, immediate))
+        sc.restore(t.state_hash())
+        return out
+
+    def take(self, choice: Choice) -> None:
+        t = self.thunk.clone()
+        t.trace.append(choice.thread)
+        t.hashes.append(choice.hash)
+        t.must_run = [choice.thread] if choice.must_run else []
+        self._enter(t)
+
+    def back(self) -> bool:
+        """Returns to the previous state, if there is one."""
+        if len(self.path) == 1:
+            return False
+        self.path.pop()
+        self.ev.state_controller.restore(self.thunk.state_hash())
+        self._check(self.thunk)
+        return True
+
+    def _enter(self, t: EvalThunk) -> None:
+        self.ev.state_controller.restore(t.state_hash())
+        self.ev._eval_preds(t)
+        self.path.append(t)
+        self._check(t)
+
+    def _check(self, t: EvalThunk) -> None:
+        self.violation = None
+        try:
+            self.ev._check_constraints(t)
+        except StutterConstraintError as e:
+            self.violation = f"{e.name}, if the behavior stopped here"
+        except ConstraintError as e:
+            self.violation = e.name
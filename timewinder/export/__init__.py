@@ -0,0 +1,13 @@
+from .dot import to_dot
+from .tlc import format_tlc
+from .tla import to_tla_module
+from .junit import to_junit_xml
+from .sqlite import archive_run
+from .sarif import to_sarif
+from .mermaid import to_mermaid
+from .html import to_html
+from .regression import to_pytest
+from .itf import to_itf
+from .itf import trace_to_itf
+from .itf import replay_itf
+from .scc import scc_report
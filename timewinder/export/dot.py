@@ -0,0 +1,74 @@
+from typing import Any
+from typing import Callable
+from typing import Dict
+from typing import List
+from typing import Optional
+from typing import TYPE_CHECKING
+
+from timewinder.statetree import Hash
+
+if TYPE_CHECKING:
+    from timewinder.evaluation import Evaluator
+
+
+StateFilter = Callable[[Dict[str, Any]], bool]
+
+
+def to_dot(
+    ev: "Evaluator",
+    variables: Optional[List[str]] = None,
+    max_nodes: Optional[int] = None,
+    node_filter: Optional[StateFilter] = None,
+) -> str:
+    """Renders the graph recorded by an Evaluator (see `record_graph`) in
+    Graphviz DOT format.
+
+    States are labeled by the dotted `object.attribute` paths in variables, or
+    by their hash. Only states passing node_filter are drawn, and at most
+    max_nodes of those, nearest the initial states first."""
+    graph = ev.graph
+    if graph is None:
+        raise ValueError("Evaluator must be created with record_graph=True")
+
+    ids: Dict[Hash, str] = {}
+    lines = ["digraph timewinder {"]
+    for h in graph.bfs():
+        if max_nodes is not None and len(ids) >= max_nodes:
+            lines.append(f"  // truncated to {max_nodes} of {len(graph)} states")
+            break
+        state = ev.state_controller.cas.restore(h)
+        if node_filter is not None and not node_filter(state):
+            continue
+        ids[h] = f"s{len(ids)}"
+        attrs = f'label="{_escape(state_label(state, h, variables))}"'
+        if h in graph.initial:
+            attrs += ", peripheries=2"
+        lines.append(f"  {ids[h]} [{attrs}];")
+
+    for e in graph.all_edges():
+        if e.src in ids and e.dst in ids:
+            label = _escape(f"{e.thread}: {e.action}")
+            lines.append(f'  {ids[e.src]} -> {ids[e.dst]} [label="{label}"];')
+    lines.append("}")
+    return "\n".join(lines) + "\n"
+
+
+def state_label(state: Dict[str, Any], h: Hash, variables: Optional[List[str]]) -> str:
+    if not variables:
+        return h.hex()[:7]
+    return "\n".join(f"{v} = {lookup_path(state, v)!r}" for v in variables)
+
+
+def lookup_path(state: Dict[str, Any], path: str) -> Any:
+    """Resolves a dotted path such as `alice.acc` against a restored state."""
+    v: Any = state
+    for part in path.split("."):
+        if isinstance(v, list):
+            v = v[int(part)]
+        else:
+            v = v[part]
+    return v
+
+
+def _escape(s: str) -> str:
+    return s.replace("\\", "\\\\").replace('"', '\\"').replace("\n", "\\n")
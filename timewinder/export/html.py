@@ -0,0 +1,163 @@
+import html
+import json
+
+from typing import Any
+from typing import Dict
+from typing import List
+from typing import Optional
+from typing import TYPE_CHECKING
+
+from timewinder.result import CheckResult
+from timewinder.result import encode_value
+
+if TYPE_CHECKING:
+    from timewinder.evaluation import Evaluator
+
+
+def to_html(
+    result: CheckResult,
+    ev: Optional["Evaluator"] = None,
+    title: str = "timewinder report",
+) -> str:
+    """Renders a CheckResult as a single, self-contained HTML page: the
+    stats, each property's status, which actions were taken (given an
+    Evaluator that recorded its graph) and the counterexample, stepped
+    through with the values each step changed highlighted. It needs nothing
+    but a browser, so it can be shared with people who don't have
+    timewinder."""
+    doc = result.to_dict()
+    doc["coverage"] = action_coverage(ev) if ev is not None else None
+    data = json.dumps(doc, default=encode_value).replace("</", "<\\/")
+
+    if result.violation is not None:
+        summary = f"Violation: {result.violation}"
+    elif result.error is not None:
+        summary = f"Error: {result.error}"
+    elif result.bounded:
+        summary = f"No violations found, but the search was bounded by {result.bound}."
+    else:
+        summary = "No violations found."
+    body = [
+        f"<h1>{_e(title)}</h1>",
+        f"<p class=status-{_e(result.status.value)}>{_e(summary)}</p>",
+    ]
+
+    stats = doc["stats"]
+    body.append("<h2>Stats</h2><table>")
+    body.extend(f"<tr><th>{_e(k)}</th><td>{_e(v)}</td></tr>" for k, v in stats.items())
+    body.append("</table>")
+
+    body.append("<h2>Properties</h2><table>")
+    body.append("<tr><th>Property</th><th>Kind</th><th>Status</th><th>Where</th></tr>")
+    for p in result.properties:
+        kind = "liveness" if p.liveness else "safety"
+        where = f"{p.location[0]}:{p.location[1]}" if p.location else ""
+        body.append(
+            f"<tr><td>{_e(p.name)}</td><td>{kind}</td>"
+            f"<td class=status-{_e(p.status)}>{_e(p.status)}</td>"
+            f"<td>{_e(where)}</td></tr>"
+        )
+    body.append("</table>")
+
+    if doc["coverage"] is not None:
+        body.append("<h2>Coverage</h2><table>")
+        body.append("<tr><th>Thread</th><th>Action</th><th>Taken</th></tr>")
+        for c in doc["coverage"]:
+            taken = "yes" if c["taken"] else "no"
+            body.append(
+                f"<tr><td>{c['thread']}</td><td>{_e(c['action'])}</td>"
+                f"<td class=taken-{taken}>{taken}</td></tr>"
+            )
+        body.append("</table>")
+
+    if len(result.trace) != 0:
+        body.append("<h2>Counterexample</h2>")
+        body.append(
+            "<div id=viewer><button id=prev>&larr; Previous</button> "
+            "<span id=position></span> <button id=next>Next &rarr;</button>"
+            "<div id=step></div></div>"
+        )
+        body.append("<noscript><pre>")
+        for i, s in enumerate(doc["trace"]):
+            body.append(_e(f"{i}: {s['action'] or 'Initial state'}"))
+            body.extend(_e(f"  {k}: {v!r}") for k, v in sorted(s["state"].items()))
+        body.append("</pre></noscript>")
+
+    return _PAGE.format(
+        title=_e(title), style=_STYLE, body="\n".join(body), data=data, script=_SCRIPT
+    )
+
+
+def action_coverage(ev: "Evaluator") -> Optional[List[Dict[str, Any]]]:
+    """Each action of each thread, and whether the recorded graph took it;
+    None if no graph was recorded."""
+    if ev.graph is None:
+        return None
+    taken = {(e.thread, e.action) for e in ev.graph.all_edges()}
+    return [
+        {"thread": i, "action": action, "taken": (i, action) in taken}
+        for i, thread in enumerate(ev.threads)
+        for action in thread.step_names()
+    ]
+
+
+def _e(v: Any) -> str:
+    return html.escape(str(v))
+
+
+_PAGE = """<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>{style}</style>
+</head>
+<body>
+{body}
+<script type="application/json" id="data">{data}</script>
+<script>{script}</script>
+</body>
+</html>
+"""
+
+_STYLE = """
+body { font-family: sans-serif; margin: 2em; max-width: 60em; }
+table { border-collapse: collapse; margin-bottom: 1em; }
+th, td { border: 1px solid #ccc; padding: 0.2em 0.6em; text-align: left; }
+.status-pass, .taken-yes { color: #070; }
+.status-fail, .status-error, .taken-no { color: #b00; }
+.status-bounded, .status-unknown { color: #a60; }
+#step pre { background: #f6f6f6; padding: 0.5em; }
+.changed { background: #ffe680; }
+"""
+
+_SCRIPT = """
+(function () {
+  var doc = JSON.parse(document.getElementById("data").textContent);
+  var trace = doc.trace, at = trace.length - 1;
+  if (trace.length === 0) return;
+  function esc(s) {
+    return String(s).replace(/&/g, "&amp;").replace(/</g, "&lt;").replace(/>/g, "&gt;");
+  }
+  function show() {
+    var step = trace[at], prev = at > 0 ? trace[at - 1] : null;
+    var out = "<p>" + (step.action === null ? "Initial state" :
+      "Thread " + step.thread + " executes " + esc(step.action)) + "</p><pre>";
+    Object.keys(step.state).sort().forEach(function (name) {
+      if (name.indexOf("_thread_") === 0) return;
+      var now = JSON.stringify(step.state[name]);
+      var changed = prev !== null && now !== JSON.stringify(prev.state[name]);
+      out += (changed ? "<span class=changed>" : "") + esc(name + ": " + now) +
+        (changed ? "</span>" : "") + "\\n";
+    });
+    document.getElementById("step").innerHTML = out + "</pre>";
+    document.getElementById("position").textContent =
+      "Step " + at + " of " + (trace.length - 1);
+    document.getElementById("prev").disabled = at === 0;
+    document.getElementById("next").disabled = at === trace.length - 1;
+  }
+  document.getElementById("prev").onclick = function () { at--; show(); };
+  document.getElementById("next").onclick = function () { at++; show(); };
+  show();
+})();
+"""
@@ -0,0 +1,167 @@
+from typing import Any
+from typing import Dict
+from typing import List
+from typing import TYPE_CHECKING
+
+from timewinder.evaluation import EvalThunk
+from timewinder.result import CheckResult
+from timewinder.result import TraceStep
+from timewinder.statetree import Hash
+from timewinder.statetree import Record
+
+if TYPE_CHECKING:
+    from timewinder.evaluation import Evaluator
+
+
+ITF_DESCRIPTION = "https://apalache.informal.systems/docs/adr/015adr-trace.html"
+
+_MAX_SAFE_INT = 2 ** 53 - 1
+
+
+class ReplayError(Exception):
+    def __init__(self, step: int, message: str):
+        super().__init__(f"step {step}: {message}")
+        self.step = step
+
+
+def to_itf(result: CheckResult) -> Dict[str, Any]:
+    """Converts a counterexample to the Informal Trace Format used by
+    Apalache and Quint. Thread states are left out; the thread and action of
+    each step are recorded in the state's #meta."""
+    return trace_to_itf(result.trace)
+
+
+def trace_to_itf(trace: List[TraceStep]) -> Dict[str, Any]:
+    if len(trace) == 0:
+        return {"#meta": _meta(), "vars": [], "states": []}
+    variables = sorted(k for k in trace[0].state if not k.startswith("_thread_"))
+    states = []
+    for i, step in enumerate(trace):
+        meta: Dict[str, Any] = {"index": i}
+        if step.thread is not None:
+            meta["thread"] = step.thread
+            meta["action"] = step.action
+        state = {"#meta": meta}
+        for v in variables:
+            state[v] = to_itf_value(step.state[v])
+        states.append(state)
+    return {"#meta": _meta(), "vars": variables, "states": states}
+
+
+def to_itf_value(v: Any) -> Any:
+    if isinstance(v, bool) or isinstance(v, str):
+        return v
+    if isinstance(v, int):
+        if abs(v) > _MAX_SAFE_INT:
+            return {"#bigint": str(v)}
+        return v
+    if isinstance(v, list):
+        return [to_itf_value(x) for x in v]
+    if isinstance(v, tuple):
+        return {"#tup": [to_itf_value(x) for x in v]}
+    if isinstance(v, (set, frozenset)):
+        return {"#set": [to_itf_value(x) for x in sorted(v, key=repr)]}
+    if isinstance(v, Record):
+        return {k: to_itf_value(x) for k, x in v.asdict().items()}
+    if isinstance(v, dict):
+        if all(isinstance(k, str) and not k.startswith("#") for k in v):
+            return {k: to_itf_value(x) for k, x in v.items()}
+        return {"#map": [[to_itf_value(k), to_itf_value(x)] for k, x in v.items()]}
+    return {"#unserializable": repr(v)}
+
+
+def from_itf_value(v: Any) -> Any:
+    if isinstance(v, list):
+        return [from_itf_value(x) for x in v]
+    if not isinstance(v, dict):
+        return v
+    if "#bigint" in v:
+        return int(v["#bigint"])
+    if "#tup" in v:
+        return tuple(from_itf_value(x) for x in v["#tup"])
+    if "#set" in v:
+        return frozenset(from_itf_value(x) for x in v["#set"])
+    if "#map" in v:
+        return {from_itf_value(k): from_itf_value(x) for k, x in v["#map"]}
+    if "#unserializable" in v:
+        return None
+    return {k: from_itf_value(x) for k, x in v.items() if not k.startswith("#")}
+
+
+def replay_itf(ev: "Evaluator", itf: Dict[str, Any]) -> EvalThunk:
+    """Finds the behavior of the model matching an ITF trace.
+
+    Each state of the trace constrains the variables it lists; at every step,
+    some thread must execute into a state that agrees with it. Since the
+    trace may not tell apart states that differ elsewhere, every behavior
+    agreeing with it so far is followed, until one reaches its end. That
+    behavior is returned as an EvalThunk, to be used with replay_thunk or
+    build_trace. Raises ReplayError when the model can't follow the trace."""
+    sc = ev.state_controller
+    variables = itf["vars"]
+    expected = [{v: from_itf_value(s[v]) for v in variables} for s in itf["states"]]
+    if len(expected) == 0:
+        raise ReplayError(0, "trace has no states")
+
+    frontier = [
+        EvalThunk(trace=[], hashes=[h], predicate_traces=[])
+        for h in _matching(ev, list(sc.commit()), expected[0])
+    ]
+    if len(frontier) == 0:
+        raise ReplayError(0, "no initial state matches")
+
+    for i, want in enumerate(expected[1:], 1):
+        # By state, so behaviors that meet are followed once
+        following: Dict[Hash, EvalThunk] = {}
+        for thunk in frontier:
+            for tid, thread in enumerate(ev.threads):
+                sc.restore(thunk.state_hash())
+                if not thread.can_execute():
+                    continue
+                thread.execute(sc)
+                for h in _matching(ev, list(sc.commit()), want):
+                    if h not in following:
+                        following[h] = EvalThunk(
+                            trace=thunk.trace + [tid],
+                            hashes=thunk.hashes + [h],
+                            predicate_traces=[],
+                        )
+        if len(following) == 0:
+            raise ReplayError(i, "no thread's execution matches")
+        frontier = list(following.values())
+    thunk = frontier[0]
+    sc.restore(thunk.state_hash())
+    return thunk
+
+
+def _matching(ev: "Evaluator", hashes: List[Hash], want: Dict[str, Any]) -> List[Hash]:
+    out = []
+    for h in hashes:
+        state = ev.state_controller.cas.restore(h)
+        if all(_equal(state.get(k), v) for k, v in want.items()):
+            out.append(h)
+    return out
+
+
+def _equal(actual: Any, expected: Any) -> bool:
+    """Whether a value of the state is the one ITF gives, which may have
+    tuples where the state has lists, at any depth."""
+    if isinstance(expected, frozenset) and isinstance(actual, (set, frozenset)):
+        return frozenset(actual) == expected
+    if isinstance(expected, (list, tuple)) and isinstance(actual, (list, tuple)):
+        return len(actual) == len(expected) and all(
+            _equal(a, e) for a, e in zip(actual, expected)
+        )
+    if isinstance(expected, dict) and isinstance(actual, dict):
+        return actual.keys() == expected.keys() and all(
+            _equal(actual[k], e) for k, e in expected.items()
+        )
+    return actual == expected
+
+
+def _meta() -> Dict[str, Any]:
+    return {
+        "format": "ITF",
+        "format-description": ITF_DESCRIPTION,
+        "source": "timewinder",
+    }
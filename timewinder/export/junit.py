@@ -0,0 +1,47 @@
+import xml.etree.ElementTree as ET
+
+from typing import Optional
+
+from timewinder.result import CheckResult
+from timewinder.result import Status
+
+from .tlc import format_tlc
+
+
+def to_junit_xml(
+    result: CheckResult, suite: str = "timewinder", artifact: Optional[str] = None
+) -> str:
+    """Renders a CheckResult as a JUnit XML report with one test case per
+    property. artifact, if given, is the path of the saved counterexample and
+    is attached to failing cases."""
+    failures = sum(1 for p in result.properties if p.status == "fail")
+    errors = len(result.properties) if result.status == Status.ERROR else 0
+    unknown = sum(1 for p in result.properties if p.status == "unknown")
+    skipped = 0 if errors else unknown
+
+    root = ET.Element(
+        "testsuite",
+        name=suite,
+        tests=str(len(result.properties)),
+        failures=str(failures),
+        errors=str(errors),
+        skipped=str(skipped),
+    )
+    for p in result.properties:
+        case = ET.SubElement(root, "testcase", classname=suite, name=p.name)
+        if p.location is not None:
+            case.set("file", p.location[0])
+            case.set("line", str(p.location[1]))
+        if result.status == Status.ERROR:
+            ET.SubElement(case, "error", message=str(result.error))
+        elif p.status == "fail":
+            failure = ET.SubElement(case, "failure", message=f"{p.name} is violated")
+            failure.text = format_tlc(result)
+            if artifact is not None:
+                out = ET.SubElement(case, "system-out")
+                out.text = f"[[ATTACHMENT|{artifact}]]"
+        elif p.status == "unknown":
+            ET.SubElement(
+                case, "skipped", message="evaluation stopped before this was checked"
+            )
+    return ET.tostring(root, encoding="unicode")
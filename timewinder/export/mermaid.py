@@ -0,0 +1,62 @@
+import json
+
+from typing import Dict
+from typing import List
+
+from timewinder.result import CheckResult
+from timewinder.result import encode_value
+
+
+def to_mermaid(result: CheckResult) -> str:
+    """Renders a counterexample as a Mermaid sequence diagram.
+
+    Each thread and each object it touches gets a lifeline. Every step draws an
+    arrow from the thread to the objects whose attributes it changed (eg, a
+    send or receive on a queue object), labeled with the new values."""
+    threads: Dict[int, str] = {}
+    objects: List[str] = []
+    messages: List[str] = []
+    for prev, step in zip(result.trace, result.trace[1:]):
+        if step.thread is None:
+            continue
+        tid = step.thread
+        if tid not in threads:
+            threads[tid] = str(step.action).split("@")[0]
+        changed = False
+        for name in sorted(step.state):
+            if name.startswith("_thread_") or step.state[name] == prev.state.get(name):
+                continue
+            if name not in objects:
+                objects.append(name)
+            changed = True
+            label = ", ".join(_changes(prev.state.get(name), step.state[name]))
+            messages.append(f"    T{tid}->>{name}: {_text(f'{step.action}: {label}')}")
+        if not changed:
+            messages.append(f"    Note over T{tid}: {_text(str(step.action))}")
+
+    lines = ["sequenceDiagram"]
+    for tid in sorted(threads):
+        lines.append(f"    participant T{tid} as {_text(threads[tid])} {tid}")
+    for name in objects:
+        lines.append(f"    participant {name}")
+    lines.extend(messages)
+    return "\n".join(lines) + "\n"
+
+
+def _changes(before, after) -> List[str]:
+    if isinstance(before, dict) and isinstance(after, dict):
+        return [
+            f"{k} = {_value(after[k])}"
+            for k in sorted(after)
+            if before.get(k) != after[k]
+        ]
+    return [_value(after)]
+
+
+def _value(v) -> str:
+    return json.dumps(v, default=encode_value)
+
+
+def _text(s: str) -> str:
+    # Semicolons end statements and # starts an entity code in Mermaid
+    return s.replace("#", "#35;").replace(";", "#59;")
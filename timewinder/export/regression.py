@@ -0,0 +1,44 @@
+import re
+
+from timewinder.result import CheckResult
+
+
+def to_pytest(result: CheckResult, sut: str = "make_sut") -> str:
+    """Writes a counterexample as a pytest regression test for an
+    implementation.
+
+    The test builds a SystemUnderTest by calling `sut`, applies the actions
+    of the counterexample in order, then asserts the implementation doesn't
+    end up with the values the model had when it violated its spec. Given
+    as "package.module:function", `sut` is imported; a bare name must be
+    defined where the test is pasted."""
+    if len(result.trace) == 0:
+        raise ValueError("only a failing result has a counterexample")
+    module, _, factory = sut.rpartition(":")
+    final = result.trace[-1].state
+    violating = {k: v for k, v in sorted(final.items()) if not k.startswith("_thread_")}
+    m = re.search(r"[A-Za-z_][A-Za-z0-9_]*", result.violation or "")
+    name = m.group(0) if m else "counterexample"
+
+    lines = [f"# Generated by timewinder from a counterexample to {result.violation}"]
+    if module:
+        lines.append(f"from {module} import {factory}")
+    lines += [
+        "",
+        "",
+        f"def test_{name}():",
+        f"    system = {factory}()",
+    ]
+    for step in result.trace[1:]:
+        lines.append(f"    system.apply({step.thread}, {step.action!r})")
+    lines += [
+        "    observed = system.observe()",
+        "    # Where the model ended up",
+        f"    violating = {violating!r}",
+        "    assert any(",
+        "        observed[obj][attr] != violating[obj][attr]",
+        "        for obj in observed",
+        "        for attr in observed[obj]",
+        f"    ), {('implementation violates ' + str(result.violation))!r}",
+    ]
+    return "\n".join(lines) + "\n"
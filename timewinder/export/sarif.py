@@ -0,0 +1,104 @@
+import os
+
+from typing import Any
+from typing import Dict
+from typing import List
+from typing import Optional
+
+from timewinder.result import CheckResult
+from timewinder.result import Location
+
+from .tlc import tla_value
+
+
+SARIF_SCHEMA = "https://json.schemastore.org/sarif-2.1.0.json"
+HOMEPAGE = "https://github.com/timewinder-dev/timewinder"
+
+
+def to_sarif(
+    result: CheckResult, base: Optional[str] = None, artifact: Optional[str] = None
+) -> Dict[str, Any]:
+    """Builds a SARIF 2.1.0 log of the violated properties in a CheckResult.
+
+    Paths are made relative to base, if given, so viewers such as GitHub code
+    scanning can match them to the repository. The counterexample is included
+    as a code flow over the actions, and artifact is attached when given."""
+    rules = [
+        {"id": p.name, "shortDescription": {"text": p.name}} for p in result.properties
+    ]
+    results = []
+    for p in result.properties:
+        if p.status != "fail":
+            continue
+        r: Dict[str, Any] = {
+            "ruleId": p.name,
+            "level": "error",
+            "message": {"text": f"{p.name} is violated"},
+        }
+        loc = _location(p.location, base)
+        if loc is not None:
+            r["locations"] = [loc]
+        flow = _thread_flow(result, base)
+        if flow:
+            r["codeFlows"] = [{"threadFlows": [{"locations": flow}]}]
+        if artifact is not None:
+            r["attachments"] = [
+                {
+                    "description": {"text": "counterexample"},
+                    "artifactLocation": {"uri": _uri(artifact, base)},
+                }
+            ]
+        results.append(r)
+
+    return {
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [
+            {
+                "tool": {
+                    "driver": {
+                        "name": "timewinder",
+                        "informationUri": HOMEPAGE,
+                        "rules": rules,
+                    }
+                },
+                "results": results,
+            }
+        ],
+    }
+
+
+def _thread_flow(result: CheckResult, base: Optional[str]) -> List[Dict[str, Any]]:
+    out = []
+    for i, step in enumerate(result.trace):
+        if step.thread is None:
+            continue
+        loc = _location(step.location, base) or {}
+        changed = [
+            f"{k} = {tla_value(v)}"
+            for k, v in sorted(step.state.items())
+            if not k.startswith("_thread_") and v != result.trace[i - 1].state.get(k)
+        ]
+        text = f"Step {i}: thread {step.thread} runs {step.action}"
+        if changed:
+            text += "; " + ", ".join(changed)
+        loc["message"] = {"text": text}
+        out.append({"location": loc})
+    return out
+
+
+def _location(loc: Location, base: Optional[str]) -> Optional[Dict[str, Any]]:
+    if loc is None:
+        return None
+    return {
+        "physicalLocation": {
+            "artifactLocation": {"uri": _uri(loc[0], base)},
+            "region": {"startLine": loc[1]},
+        }
+    }
+
+
+def _uri(path: str, base: Optional[str]) -> str:
+    if base is not None:
+        path = os.path.relpath(path, base)
+    return path.replace(os.sep, "/")
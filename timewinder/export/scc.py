@@ -0,0 +1,45 @@
+from typing import Any
+from typing import Dict
+from typing import Set
+from typing import TYPE_CHECKING
+
+from timewinder.pause import Fairness
+
+if TYPE_CHECKING:
+    from timewinder.evaluation import Evaluator
+
+
+def scc_report(ev: "Evaluator") -> Dict[str, Any]:
+    """Summarizes the strongly connected components of the graph recorded
+    by an Evaluator (see `record_graph`), in the order they can be reached.
+
+    A component has a fair cycle if a behavior can loop in it forever while
+    treating fairly the threads that are fair in the initial state."""
+    components = ev.components()
+    assert ev.graph is not None
+    fair = _fair_threads(ev)
+    return {
+        "states": len(ev.graph),
+        "fair_threads": sorted(fair),
+        "components": [
+            {
+                "size": c.size,
+                "cyclic": c.cyclic,
+                "terminal": c.terminal,
+                "fair_cycle": c.fair_cycle(fair),
+                "threads": sorted({e.thread for e in c.edges}),
+                "states": [h.hex() for h in c.states],
+            }
+            for c in components
+        ],
+    }
+
+
+def _fair_threads(ev: "Evaluator") -> Set[int]:
+    assert ev.graph is not None
+    ev.state_controller.restore(ev.graph.initial[0])
+    return {
+        i
+        for i, thread in enumerate(ev.threads)
+        if ev.thread_fairness(thread) != Fairness.NORMAL
+    }
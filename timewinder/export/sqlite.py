@@ -0,0 +1,137 @@
+"""Archives evaluation runs into a SQLite database for later querying.
+
+The schema is:
+
+    runs(id, created, status, bounded, violation, error, stats)
+    properties(run_id, name, liveness, status)
+    states(run_id, hash, initial, state)
+    transitions(run_id, src, dst, thread, action)
+    counterexamples(run_id, step, hash, thread, action)
+
+`stats` and `state` hold JSON, laid out as in CheckResult.to_dict(), so
+SQLite's JSON functions can query them. For example, counting the states
+where a lock object is held by process 2:
+
+    SELECT count(*) FROM states
+    WHERE run_id = ? AND json_extract(state, '$.lock.holder') = 2
+
+Every explored state is stored when the Evaluator was created with
+record_graph=True; otherwise only the states of the counterexample are.
+"""
+import json
+import sqlite3
+
+from dataclasses import asdict
+from datetime import datetime
+from datetime import timezone
+
+from typing import TYPE_CHECKING
+from typing import Union
+
+from timewinder.result import CheckResult
+from timewinder.result import encode_value
+
+if TYPE_CHECKING:
+    from timewinder.evaluation import Evaluator
+
+
+SCHEMA = """
+CREATE TABLE IF NOT EXISTS runs (
+    id INTEGER PRIMARY KEY,
+    created TEXT NOT NULL,
+    status TEXT NOT NULL,
+    bounded INTEGER NOT NULL,
+    violation TEXT,
+    error TEXT,
+    stats TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS properties (
+    run_id INTEGER NOT NULL REFERENCES runs(id),
+    name TEXT NOT NULL,
+    liveness INTEGER NOT NULL,
+    status TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS states (
+    run_id INTEGER NOT NULL REFERENCES runs(id),
+    hash TEXT NOT NULL,
+    initial INTEGER NOT NULL,
+    state TEXT NOT NULL,
+    PRIMARY KEY (run_id, hash)
+);
+CREATE TABLE IF NOT EXISTS transitions (
+    run_id INTEGER NOT NULL REFERENCES runs(id),
+    src TEXT NOT NULL,
+    dst TEXT NOT NULL,
+    thread INTEGER NOT NULL,
+    action TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS counterexamples (
+    run_id INTEGER NOT NULL REFERENCES runs(id),
+    step INTEGER NOT NULL,
+    hash TEXT NOT NULL,
+    thread INTEGER,
+    action TEXT
+);
+"""
+
+
+def archive_run(
+    db: Union[str, sqlite3.Connection], ev: "Evaluator", result: CheckResult
+) -> int:
+    """Stores a checked run, returning its id in the runs table."""
+    conn = sqlite3.connect(db) if isinstance(db, str) else db
+    try:
+        with conn:
+            conn.executescript(SCHEMA)
+            cur = conn.execute(
+                "INSERT INTO runs (created, status, bounded, violation, error, stats) "
+                "VALUES (?, ?, ?, ?, ?, ?)",
+                (
+                    datetime.now(timezone.utc).isoformat(),
+                    result.status.value,
+                    int(result.bounded),
+                    result.violation,
+                    result.error,
+                    json.dumps(asdict(result.stats)),
+                ),
+            )
+            run_id = cur.lastrowid
+            properties = [
+                (run_id, p.name, int(p.liveness), p.status) for p in result.properties
+            ]
+            conn.executemany("INSERT INTO properties VALUES (?, ?, ?, ?)", properties)
+            _insert_states(conn, run_id, ev, result)
+            conn.executemany(
+                "INSERT INTO counterexamples VALUES (?, ?, ?, ?, ?)",
+                [
+                    (run_id, i, s.hash, s.thread, s.action)
+                    for i, s in enumerate(result.trace)
+                ],
+            )
+    finally:
+        if isinstance(db, str):
+            conn.close()
+    return run_id
+
+
+def _insert_states(conn, run_id, ev, result):
+    def state_json(state):
+        return json.dumps(state, default=encode_value, sort_keys=True)
+
+    rows = {}
+    for i, s in enumerate(result.trace):
+        rows[s.hash] = (run_id, s.hash, int(i == 0), state_json(s.state))
+    graph = ev.graph
+    if graph is not None:
+        initial = set(graph.initial)
+        for h in graph.nodes:
+            state = ev.state_controller.cas.restore(h)
+            rows[h.hex()] = (run_id, h.hex(), int(h in initial), state_json(state))
+        conn.executemany(
+            "INSERT INTO transitions VALUES (?, ?, ?, ?, ?)",
+            [
+                (run_id, e.src.hex(), e.dst.hex(), e.thread, e.action)
+                for e in graph.all_edges()
+            ],
+        )
+    conn.executemany("INSERT INTO states VALUES (?, ?, ?, ?)", list(rows.values()))
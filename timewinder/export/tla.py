@@ -0,0 +1,132 @@
+import re
+
+from typing import Dict
+from typing import List
+from typing import TYPE_CHECKING
+
+from timewinder.ltl import Always
+from timewinder.ltl import LTLBinOp
+from timewinder.ltl import LTLOp
+from timewinder.predicate import Predicate
+
+from .tlc import tla_value
+
+if TYPE_CHECKING:
+    from timewinder.evaluation import Evaluator
+
+
+_UNARY = {"always": "[]", "eventually": "<>", "inverse": "~"}
+_BINARY = {"leads_to": "~>"}
+
+
+def to_tla_module(ev: "Evaluator", name: str = "Spec") -> str:
+    """Translates the shape of a model into a TLA+ module skeleton.
+
+    Objects become variables, the initial states become Init, each process
+    label becomes a guarded action, safety specs become invariants and
+    liveness specs keep their temporal structure.
+    Python code can't be translated, so action bodies and predicate
+    definitions are left as TODOs to fill in before running TLC.
+
+    This reads the current object states, so call it before evaluating."""
+    sc = ev.state_controller
+    objects = [k for k in sc.tree if not k.startswith("_thread_")]
+
+    initial: Dict[str, List[str]] = {k: [] for k in objects}
+    for h in sc.commit():
+        state = sc.cas.restore(h)
+        for k in objects:
+            v = tla_value(state[k])
+            if v not in initial[k]:
+                initial[k].append(v)
+
+    variables = [_ident(k) for k in objects] + ["pc"]
+    lines = [
+        f"---- MODULE {name} ----",
+        "\\* Generated by timewinder. Action bodies and predicates are not",
+        "\\* translated, and are left as TODOs.",
+        "EXTENDS Integers, Sequences, TLC",
+        "",
+        f"VARIABLES {', '.join(variables)}",
+        "",
+        f"vars == <<{', '.join(variables)}>>",
+        "",
+        "Init ==",
+    ]
+    for k in objects:
+        vals = initial[k]
+        if len(vals) == 1:
+            lines.append(f"    /\\ {_ident(k)} = {vals[0]}")
+        else:
+            lines.append(f"    /\\ {_ident(k)} \\in {{{', '.join(vals)}}}")
+    pcs = ", ".join(tla_value(t.step_name()) for t in ev.threads)
+    lines.append(f"    /\\ pc = <<{pcs}>>")
+    lines.append("")
+
+    actions = []
+    for i, t in enumerate(ev.threads):
+        for step in t.step_names():
+            action = _ident(f"{step}_{i}")
+            actions.append(action)
+            lines.append(f"{action} ==")
+            lines.append(f"    /\\ pc[{i + 1}] = {tla_value(step)}")
+            lines.append("    /\\ FALSE \\* TODO: translate the Python step")
+            lines.append("")
+
+    lines.append("Next ==")
+    for a in actions:
+        lines.append(f"    \\/ {a}")
+    lines.append("")
+    lines.append("Spec == Init /\\ [][Next]_vars")
+    lines.append("")
+
+    defined = set()
+    for spec in ev.specs:
+        for p in spec.get_predicates():
+            pname = _ident(p.name)
+            if pname in defined:
+                continue
+            defined.add(pname)
+            loc = p.location()
+            where = f" ({loc[0]}:{loc[1]})" if loc is not None else ""
+            lines.append(f"{pname} == TRUE \\* TODO: translate {p.name}{where}")
+    lines.append("")
+
+    config = []
+    for i, spec in enumerate(ev.specs, 1):
+        if spec.is_liveness():
+            lines.append(f"Property{i} == {_formula(spec)}")
+            config.append(f"\\* PROPERTY Property{i}")
+        else:
+            lines.append(f"Invariant{i} == {_formula(_invariant(spec))}")
+            config.append(f"\\* INVARIANT Invariant{i}")
+    lines.extend(config)
+    lines.append("====")
+    return "\n".join(lines) + "\n"
+
+
+def _invariant(e):
+    # TLC checks an INVARIANT in every state itself, so it's written as a
+    # state predicate, without the []
+    while isinstance(e, Always):
+        e = e.pred
+    return e
+
+
+def _formula(e) -> str:
+    if isinstance(e, Predicate):
+        return _ident(e.name)
+    assert isinstance(e, LTLOp)
+    key = getattr(e, "_tree_key", None)
+    if isinstance(e, LTLBinOp) and key in _BINARY:
+        return f"({_formula(e.pred)}) {_BINARY[key]} ({_formula(e.pred2)})"
+    if key in _UNARY:
+        return f"{_UNARY[key]}({_formula(e.pred)})"
+    return f"TRUE (* TODO: {e} *)"
+
+
+def _ident(s: str) -> str:
+    ident = re.sub(r"[^A-Za-z0-9_]", "_", s)
+    if not re.search(r"[A-Za-z]", ident):
+        ident = "v" + ident
+    return ident
@@ -0,0 +1,83 @@
+import os
+
+from typing import Any
+from typing import List
+from typing import Optional
+
+from timewinder.result import CheckResult
+from timewinder.result import Location
+from timewinder.result import Status
+
+
+def format_tlc(result: CheckResult) -> str:
+    """Formats a CheckResult the way TLC reports errors, so existing habits
+    (and tools that scrape TLC output) carry over."""
+    lines: List[str] = []
+    if result.status == Status.PASS:
+        lines.append("Model checking completed. No error has been found.")
+    elif result.status == Status.ERROR:
+        lines.append(f"Error: {result.error}")
+    else:
+        failed = [p for p in result.properties if p.status == "fail"]
+        if failed and failed[0].liveness:
+            lines.append("Error: Temporal properties were violated.")
+        else:
+            lines.append(f"Error: Invariant {result.violation} is violated.")
+        lines.append("Error: The behavior up to this point is:")
+        for i, step in enumerate(result.trace, 1):
+            if step.thread is None:
+                lines.append(f"State {i}: <Initial predicate>")
+            else:
+                lines.append(f"State {i}: <{_action(step.action, step.location)}>")
+            for name in sorted(step.state):
+                if name.startswith("_thread_"):
+                    continue
+                lines.append(f"/\\ {name} = {tla_value(step.state[name])}")
+            lines.append("")
+    lines.append(f"{result.stats.states} distinct states found.")
+    return "\n".join(lines) + "\n"
+
+
+# The escapes TLA+ strings have
+_ESCAPES = {
+    "\\": "\\\\",
+    '"': '\\"',
+    "\n": "\\n",
+    "\t": "\\t",
+    "\r": "\\r",
+    "\f": "\\f",
+}
+
+
+def tla_value(v: Any) -> str:
+    """Renders a state value using TLA+ literal syntax."""
+    if isinstance(v, bool):
+        return "TRUE" if v else "FALSE"
+    if v is None:
+        return "NULL"
+    if isinstance(v, str):
+        return '"' + "".join(_ESCAPES.get(c, c) for c in v) + '"'
+    if isinstance(v, dict):
+        if len(v) == 0:
+            return "[x \\in {} |-> NULL]"
+        if all(isinstance(k, str) and k.isidentifier() for k in v):
+            fields = ", ".join(f"{k} |-> {tla_value(v[k])}" for k in sorted(v))
+            return f"[{fields}]"
+        pairs = " @@ ".join(
+            f"{tla_value(k)} :> {tla_value(v[k])}" for k in sorted(v, key=repr)
+        )
+        return f"({pairs})"
+    if isinstance(v, (list, tuple)):
+        return "<<" + ", ".join(tla_value(x) for x in v) + ">>"
+    if isinstance(v, (set, frozenset)):
+        return "{" + ", ".join(tla_value(x) for x in sorted(v, key=repr)) + "}"
+    if isinstance(v, bytes):
+        return f'"{v.hex()}"'
+    return str(v)
+
+
+def _action(action: Optional[str], location: Location) -> str:
+    if location is None:
+        return str(action)
+    module = os.path.splitext(os.path.basename(location[0]))[0]
+    return f"{action} line {location[1]} of module {module}"
@@ -26,6 +26,13 @@ def Await(b: bool):
     return b
 
 
+@add_abi_tag("done")
+def Done() -> None:
+    """Ends the process, wherever it's called: it has finished its work.
+    Unlike a process stuck on an Await, a finished one isn't deadlocked."""
+    return None
+
+
 @add_abi_tag("either")
 def Either() -> Iterable[bool]:
     return (x for x in [True, False])
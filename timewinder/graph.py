@@ -0,0 +1,160 @@
+from collections import deque
+from dataclasses import dataclass
+from dataclasses import field
+
+from typing import Deque
+from typing import Dict
+from typing import Iterator
+from typing import List
+from typing import Set
+from typing import Tuple
+
+from timewinder.statetree import Hash
+
+
+@dataclass(frozen=True)
+class Edge:
+    src: Hash
+    dst: Hash
+    thread: int
+    action: str
+
+
+@dataclass
+class Component:
+    """A strongly connected component of a StateGraph: states that can
+    each reach all the others."""
+
+    states: List[Hash]
+    # The edges between its states
+    edges: List[Edge] = field(default_factory=list)
+    # How many edges lead out of it
+    exits: int = 0
+    # Threads that can move in every one of its states
+    always_enabled: Set[int] = field(default_factory=set)
+
+    @property
+    def size(self) -> int:
+        return len(self.states)
+
+    @property
+    def cyclic(self) -> bool:
+        """Whether a behavior can stay in it forever."""
+        return len(self.edges) != 0
+
+    @property
+    def terminal(self) -> bool:
+        """Whether a behavior that enters it can never leave."""
+        return self.exits == 0
+
+    def fair_cycle(self, fair_threads: Set[int]) -> bool:
+        """Whether a behavior can stay in it forever while treating
+        fair_threads weakly fairly: each of them that can always move must
+        have a step to take without leaving. Such cycles are where a
+        liveness property can fail."""
+        if not self.cyclic:
+            return False
+        moving = {e.thread for e in self.edges}
+        return all(t in moving for t in self.always_enabled & fair_threads)
+
+
+class StateGraph:
+    """The explored states and the thread executions connecting them."""
+
+    def __init__(self):
+        self.initial: List[Hash] = []
+        self.nodes: Set[Hash] = set()
+        self.edges: Dict[Hash, List[Edge]] = {}
+
+    def add_initial(self, h: Hash) -> None:
+        if h not in self.nodes:
+            self.initial.append(h)
+        self.nodes.add(h)
+
+    def add_edge(self, edge: Edge) -> None:
+        self.nodes.add(edge.src)
+        self.nodes.add(edge.dst)
+        out = self.edges.setdefault(edge.src, [])
+        if edge not in out:
+            out.append(edge)
+
+    def successors(self, h: Hash) -> List[Edge]:
+        return self.edges.get(h, [])
+
+    def all_edges(self) -> Iterator[Edge]:
+        for out in self.edges.values():
+            yield from out
+
+    def bfs(self) -> Iterator[Hash]:
+        """Visits every reachable node once, nearest the initial states first."""
+        seen: Set[Hash] = set(self.initial)
+        queue: Deque[Hash] = deque(self.initial)
+        while queue:
+            h = queue.popleft()
+            yield h
+            for e in self.successors(h):
+                if e.dst not in seen:
+                    seen.add(e.dst)
+                    queue.append(e.dst)
+
+    def components(self) -> List[Component]:
+        """The strongly connected components reachable from the initial
+        states, each listed before those it leads to."""
+        # Tarjan's algorithm, iteratively, since a long behavior would
+        # overflow Python's stack
+        index: Dict[Hash, int] = {}
+        low: Dict[Hash, int] = {}
+        stack: List[Hash] = []
+        on_stack: Set[Hash] = set()
+        found: List[List[Hash]] = []
+        for root in self.initial:
+            if root in index:
+                continue
+            work: List[Tuple[Hash, int]] = [(root, 0)]
+            while work:
+                h, i = work.pop()
+                if i == 0:
+                    index[h] = low[h] = len(index)
+                    stack.append(h)
+                    on_stack.add(h)
+                out = self.successors(h)
+                if i > 0:
+                    low[h] = min(low[h], low[out[i - 1].dst])
+                while i < len(out):
+                    dst = out[i].dst
+                    if dst not in index:
+                        break
+                    if dst in on_stack:
+                        low[h] = min(low[h], index[dst])
+                    i += 1
+                if i < len(out):
+                    work.append((h, i + 1))
+                    work.append((out[i].dst, 0))
+                    continue
+                if low[h] == index[h]:
+                    states = []
+                    while True:
+                        s = stack.pop()
+                        on_stack.discard(s)
+                        states.append(s)
+                        if s == h:
+                            break
+                    found.append(states)
+        return [self._component(states) for states in reversed(found)]
+
+    def _component(self, states: List[Hash]) -> Component:
+        c = Component(states)
+        members = set(states)
+        for i, h in enumerate(states):
+            out = self.successors(h)
+            enabled = {e.thread for e in out}
+            c.always_enabled = enabled if i == 0 else c.always_enabled & enabled
+            for e in out:
+                if e.dst in members:
+                    c.edges.append(e)
+                else:
+                    c.exits += 1
+        return c
+
+    def __len__(self) -> int:
+        return len(self.nodes)
@@ -0,0 +1,172 @@
+import dis
+import warnings
+
+from dataclasses import dataclass
+
+from typing import Callable
+from typing import Collection
+from typing import Dict
+from typing import List
+from typing import Optional
+
+from .evaluation import Evaluator
+from .loader import load_model
+from .reinterp.process import BytecodeProcess
+from .reinterp.verify import Diagnostic
+from .reinterp.verify import OPT_IN_RULES
+from .reinterp.verify import TimewinderWarning
+from .reinterp.verify import lint as lint_bytecode
+from .reinterp.verify import source_lines
+
+
+# What each rule looks for; the first three are the warnings processes
+# raise when they're built, except those in OPT_IN_RULES
+RULES = {
+    "unused-variable": "locals that are assigned but never read (off unless enabled)",
+    "shadowing": "locals that shadow a global",
+    "unreachable": "code that can never run",
+    "unguarded-action": "processes that change state without checking any first",
+    "constant-invariant": "properties that never look at the state",
+    "unbounded-growth": "attributes a loop adds to that nothing compares",
+}
+
+_CONDITIONAL = ("POP_JUMP_IF", "JUMP_IF")
+
+
+@dataclass
+class Finding:
+    rule: str
+    filename: str
+    line: int
+    msg: str
+
+    def __str__(self) -> str:
+        return f"{self.filename}:{self.line}: {self.msg} [{self.rule}]"
+
+
+def lint_path(
+    path: str,
+    entry: Optional[str] = None,
+    constants: Optional[Dict] = None,
+    disabled: Collection[str] = OPT_IN_RULES,
+) -> List[Finding]:
+    """Lints the model a file builds. Raises what loading it raises."""
+    with warnings.catch_warnings():
+        # The same rules are reported here
+        warnings.simplefilter("ignore", TimewinderWarning)
+        ev = load_model(path, entry, constants)
+    return lint_model(ev, disabled)
+
+
+def lint_model(
+    ev: Evaluator, disabled: Collection[str] = OPT_IN_RULES
+) -> List[Finding]:
+    """Lints the processes of a model, once per function, and its specs,
+    skipping the rules disabled."""
+    out = []
+    seen = set()
+    for t in ev.threads:
+        if not isinstance(t, BytecodeProcess) or t.interp.func.__code__ in seen:
+            continue
+        func = t.interp.func
+        seen.add(func.__code__)
+        lines = source_lines(dis.get_instructions(func))
+        for d in lint_process(func):
+            line = lines[d.pc] or func.__code__.co_firstlineno
+            out.append(Finding(d.rule, func.__code__.co_filename, line, d.msg))
+    for spec in ev.specs:
+        for p in spec.get_predicates():
+            func = getattr(p, "func", None) or getattr(p, "pred", None)
+            if func is None or not hasattr(func, "__code__") or func.__code__ in seen:
+                continue
+            seen.add(func.__code__)
+            if not reads_state(func):
+                code = func.__code__
+                out.append(
+                    Finding(
+                        "constant-invariant",
+                        code.co_filename,
+                        code.co_firstlineno,
+                        f"{p.name} doesn't read its arguments or any global, so it "
+                        "can't depend on the state",
+                    )
+                )
+    out = [f for f in out if f.rule not in disabled]
+    return sorted(out, key=lambda f: (f.filename, f.line, f.rule))
+
+
+def lint_process(func: Callable) -> List[Diagnostic]:
+    """The compiler's warnings for a process, and those about how it
+    changes state."""
+    insts = list(dis.get_instructions(func))
+    out = lint_bytecode(func)
+    out.extend(_unguarded(func, insts))
+    out.extend(_unbounded(insts))
+    return sorted(out, key=lambda d: d.pc)
+
+
+def reads_state(func: Callable) -> bool:
+    """Whether a function reads its arguments, a global or a closure, any of
+    which could be the state."""
+    code = func.__code__
+    args = set(code.co_varnames[: code.co_argcount + code.co_kwonlyargcount])
+    for inst in dis.get_instructions(func):
+        if inst.opname == "LOAD_FAST" and inst.argval in args:
+            return True
+        if inst.opname in ("LOAD_GLOBAL", "LOAD_NAME", "LOAD_DEREF", "LOAD_CLASSDEREF"):
+            return True
+    return False
+
+
+def _unguarded(func: Callable, insts: List[dis.Instruction]) -> List[Diagnostic]:
+    writes = [
+        pc for pc, i in enumerate(insts) if i.opname in ("STORE_ATTR", "STORE_SUBSCR")
+    ]
+    if len(writes) == 0:
+        return []
+    for inst in insts:
+        if inst.opname.startswith(_CONDITIONAL) or inst.argval == "Await":
+            return []
+    name = func.__name__
+    msg = f"{name} changes state without checking any first, so it can always run"
+    return [Diagnostic("unguarded-action", writes[0], msg)]
+
+
+def _unbounded(insts: List[dis.Instruction]) -> List[Diagnostic]:
+    offsets = {inst.offset: pc for pc, inst in enumerate(insts)}
+    in_loop = set()
+    for pc, inst in enumerate(insts):
+        if inst.opname == "JUMP_ABSOLUTE" and inst.argval in offsets:
+            in_loop.update(range(offsets[inst.argval], pc))
+
+    compared = set()
+    for pc, inst in enumerate(insts):
+        ahead = [i.opname for i in insts[pc + 1 : pc + 4]]
+        if inst.opname == "LOAD_ATTR" and "COMPARE_OP" in ahead:
+            compared.add(inst.argval)
+
+    out = []
+    for pc in sorted(in_loop):
+        # x.n = x.n + 1, or x.n += 1
+        if pc + 3 > len(insts):
+            continue
+        load, const, add = insts[pc : pc + 3]
+        if load.opname != "LOAD_ATTR" or const.opname != "LOAD_CONST":
+            continue
+        if add.opname not in ("BINARY_ADD", "INPLACE_ADD"):
+            continue
+        if not isinstance(const.argval, int) or const.argval <= 0:
+            continue
+        stores = [i for i in insts[pc + 3 : pc + 6] if i.opname == "STORE_ATTR"]
+        name = load.argval
+        if stores and stores[0].argval == name and name not in compared:
+            out.append(
+                Diagnostic(
+                    "unbounded-growth",
+                    pc,
+                    f"{name} grows each time round a loop, and nothing here "
+                    "compares it, so it may grow without bound, and the states "
+                    "with it",
+                )
+            )
+    return out
@@ -0,0 +1,207 @@
+import ast
+import importlib.util
+import os
+import re
+import sys
+import warnings
+
+from dataclasses import dataclass
+from dataclasses import field
+from types import ModuleType
+from typing import Any
+from typing import Dict
+from typing import List
+from typing import Optional
+from typing import Tuple
+
+from .evaluation import Evaluator
+from .pause import Fairness
+from .reinterp.verify import TimewinderWarning
+from .statetree import Symbol
+
+
+class LoadError(Exception):
+    pass
+
+
+def load_module(path: str) -> ModuleType:
+    """Imports a model file, with its directory importable for helpers."""
+    path = os.path.abspath(path)
+    name = "_timewinder_model_" + os.path.splitext(os.path.basename(path))[0]
+    spec = importlib.util.spec_from_file_location(name, path)
+    if spec is None or spec.loader is None:
+        raise LoadError(f"{path} is not a Python file")
+    module = importlib.util.module_from_spec(spec)
+    sys.modules[name] = module
+    sys.path.insert(0, os.path.dirname(path))
+    try:
+        spec.loader.exec_module(module)  # type: ignore
+    finally:
+        sys.path.remove(os.path.dirname(path))
+    return module
+
+
+def load_model(
+    path: str, entry: Optional[str] = None, constants: Optional[Dict[str, Any]] = None
+) -> Evaluator:
+    """Builds the Evaluator for a model file.
+
+    The model is found, in order, by the entry name given, a `model`
+    function or a module-level Evaluator. Functions are called with the
+    constants as keyword arguments, so they can be varied per run. Any
+    properties the module declares, such as with @invariant, are added to
+    its specs."""
+    if constants is None:
+        constants = {}
+    module = load_module(path)
+
+    ev = _build(module, path, entry, constants)
+    for spec in declared_properties(module):
+        ev.add_spec(spec)
+    return ev
+
+
+def configure(
+    ev: Evaluator,
+    fairness: Optional[Fairness] = None,
+    deadlock: Optional[bool] = None,
+    termination: Optional[bool] = None,
+    properties: Optional[List[str]] = None,
+) -> Evaluator:
+    """Applies the checking options the command line offers to a loaded
+    model; those left as None keep what the model chose."""
+    if fairness is not None:
+        ev.fairness = fairness
+    if deadlock is not None:
+        ev.deadlock = deadlock
+    if termination is not None:
+        ev.termination = termination
+    if properties:
+        try:
+            ev.select_specs(properties)
+        except ValueError as e:
+            raise LoadError(str(e))
+    return ev
+
+
+def declared_properties(module: ModuleType) -> List[Any]:
+    """The properties a module declares with @invariant, @always or
+    @eventually."""
+    return [v for v in vars(module).values() if getattr(v, "declared", False) is True]
+
+
+def _build(module: ModuleType, path: str, entry: Optional[str], constants) -> Evaluator:
+    if entry is None and hasattr(module, "model"):
+        entry = "model"
+    if entry is not None:
+        target = getattr(module, entry, None)
+        if target is None:
+            raise LoadError(f"{path} has no {entry}")
+        if callable(target) and not isinstance(target, Evaluator):
+            target = target(**constants)
+        elif len(constants) != 0:
+            raise LoadError(f"{entry} isn't a function, so it can't take constants")
+        if not isinstance(target, Evaluator):
+            raise LoadError(f"{entry} doesn't build an Evaluator, got {type(target)}")
+        return target
+
+    found = [v for v in vars(module).values() if isinstance(v, Evaluator)]
+    if len(found) != 1:
+        raise LoadError(
+            f"{path} needs a model() function or exactly one Evaluator, "
+            f"found {len(found)}"
+        )
+    if len(constants) != 0:
+        raise LoadError("constants need a model() function to pass them to")
+    return found[0]
+
+
+def parse_constant(s: str) -> Tuple[str, Any]:
+    """Parses a `NAME=value` definition, where value is a Python literal,
+    a Symbol written `@r1` (or symbols, `@r1,@r2`), or else a bare string."""
+    if "=" not in s:
+        raise LoadError(f"constant {s} must look like NAME=value")
+    name, val = s.split("=", 1)
+    if re.fullmatch(r"@\w+(\s*,\s*@\w+)*", val):
+        syms = tuple(Symbol(n.strip()[1:]) for n in val.split(","))
+        return name, syms[0] if len(syms) == 1 else syms
+    try:
+        return name, ast.literal_eval(val)
+    except (ValueError, SyntaxError):
+        return name, val
+
+
+@dataclass
+class Compiled:
+    """What came of loading one model file: its Evaluator, or why it
+    couldn't be built, and the warnings its processes raised."""
+
+    path: str
+    evaluator: Optional[Evaluator] = None
+    error: Optional[str] = None
+    warnings: List[str] = field(default_factory=list)
+
+    @property
+    def ok(self) -> bool:
+        return self.error is None
+
+
+def compile_path(
+    path: str, entry: Optional[str] = None, constants: Optional[Dict[str, Any]] = None
+) -> Compiled:
+    """Loads a model file, catching anything that goes wrong rather than
+    raising it, so many files can be checked at once."""
+    with warnings.catch_warnings(record=True) as caught:
+        warnings.simplefilter("always", TimewinderWarning)
+        try:
+            ev = load_model(path, entry, constants)
+            for t in ev.threads:
+                t.verify()
+            out = Compiled(path, evaluator=ev)
+        except Exception as e:
+            out = Compiled(path, error=f"{type(e).__name__}: {e}")
+    for w in caught:
+        if issubclass(w.category, TimewinderWarning):
+            out.warnings.append(f"{w.filename}:{w.lineno}: warning: {w.message}")
+    return out
+
+
+def compile_dir(path: str) -> List[Compiled]:
+    """Loads every model file under a directory; see model_files."""
+    return [compile_path(p) for p in model_files(path)]
+
+
+def model_files(path: str) -> List[str]:
+    """The Python files under a directory, at any depth, that look like
+    models: they define a model() function or build an Evaluator. Tests,
+    hidden directories and caches are skipped."""
+    out = []
+    for root, dirs, files in os.walk(path):
+        dirs[:] = sorted(d for d in dirs if not d.startswith((".", "__")))
+        for name in sorted(files):
+            if name.startswith("test_") or name == "conftest.py":
+                continue
+            if not name.endswith(".py"):
+                continue
+            full = os.path.join(root, name)
+            if _looks_like_model(full):
+                out.append(full)
+    return out
+
+
+def _looks_like_model(path: str) -> bool:
+    try:
+        with open(path) as f:
+            tree = ast.parse(f.read(), path)
+    except (OSError, SyntaxError, ValueError):
+        # Reported when it's loaded
+        return True
+    for node in ast.walk(tree):
+        if isinstance(node, ast.FunctionDef) and node.name == "model":
+            return True
+        if isinstance(node, ast.Call):
+            f = node.func
+            name = f.attr if isinstance(f, ast.Attribute) else getattr(f, "id", "")
+            if name == "Evaluator":
+                return True
+    return False
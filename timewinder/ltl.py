@@ -1,9 +1,14 @@
+import re
+
 from abc import ABC
 from abc import abstractmethod
 
 from .predicate import Predicate
 
+from typing import Iterable
 from typing import List
+from typing import Optional
+from typing import Tuple
 from typing import Union
 
 from dataclasses import dataclass
@@ -54,6 +59,27 @@ def leads_to(p, q: List[bool]) -> List[bool]:
     return always(implies(p, eventually(q)))
 
 
+# A behavior that stops repeats its last state forever, so that's the state
+# after the last one, and it decides an until that hasn't been settled
+
+def next_(l: List[bool]) -> List[bool]:
+    return l[1:] + l[-1:]
+
+
+def until(p, q: List[bool]) -> List[bool]:
+    assert len(p) == len(q)
+    out = []
+    v = False
+    for a, b in zip(reversed(p), reversed(q)):
+        v = b or (a and v)
+        out.append(v)
+    return out[::-1]
+
+
+def release(p, q: List[bool]) -> List[bool]:
+    return inverse(until(inverse(p), inverse(q)))
+
+
 class LTLOp(ABC):
     def __init__(self, pred: "LTLExpression"):
         self.pred = pred
@@ -79,6 +105,13 @@ class LTLOp(ABC):
     def is_liveness(self) -> bool:
         pass
 
+    def location(self) -> Optional[Tuple[str, int]]:
+        for p in self.get_predicates():
+            loc = p.location()
+            if loc is not None:
+                return loc
+        return None
+
 
 class LTLBinOp(LTLOp):
     def __init__(self, pred: "LTLExpression", pred2: "LTLExpression"):
@@ -152,6 +185,46 @@ class Not(LTLOp):
         return "!(%s)" % str(self.pred)
 
 
+class Next(LTLOp):
+    _tree_key = "next"
+
+    def eval_traces(self, traces: List[TTrace]) -> List[bool]:
+        return next_(self.pred.eval_traces(traces))
+
+    def is_liveness(self) -> bool:
+        # Only known once the behavior has a next state, or stops
+        return True
+
+    def __repr__(self) -> str:
+        return "X(%s)" % str(self.pred)
+
+
+class Until(LTLBinOp):
+    _tree_key = "until"
+
+    def eval_traces(self, traces: List[TTrace]) -> List[bool]:
+        return until(self.pred.eval_traces(traces), self.pred2.eval_traces(traces))
+
+    def is_liveness(self) -> bool:
+        return True
+
+    def __repr__(self) -> str:
+        return "(%s) U (%s)" % (str(self.pred), str(self.pred2))
+
+
+class Release(LTLBinOp):
+    _tree_key = "release"
+
+    def eval_traces(self, traces: List[TTrace]) -> List[bool]:
+        return release(self.pred.eval_traces(traces), self.pred2.eval_traces(traces))
+
+    def is_liveness(self) -> bool:
+        return self.pred.is_liveness() or self.pred2.is_liveness()
+
+    def __repr__(self) -> str:
+        return "(%s) R (%s)" % (str(self.pred), str(self.pred2))
+
+
 class LeadsTo(LTLBinOp):
     _tree_key = "leads_to"
 
@@ -163,3 +236,68 @@ class LeadsTo(LTLBinOp):
 
     def __repr__(self) -> str:
         return "(%s) ~> (%s)" % (str(self.pred), str(self.pred2))
+
+
+_TOKEN = re.compile(r"\s*(\[\]|<>|~>|~|!|\(|\)|[A-Za-z_][A-Za-z0-9_]*)")
+_UNARY = {"[]": Always, "<>": Eventually, "~": Not, "!": Not, "X": Next}
+_BINARY = {"U": Until, "R": Release}
+
+
+def parse(text: str, predicates: Iterable[Predicate]) -> LTLExpression:
+    """Parses a temporal property written over the names of predicates:
+
+        parse("[](busy U done)", [busy, done])
+
+    Prefix operators are [] (always), <> (eventually), X (next) and ~ or !
+    (not). U (until) and R (release) bind tighter than ~> (leads to), and
+    both group to the right."""
+    names = {p.name: p for p in predicates}
+    tokens = []
+    pos = 0
+    text = text.rstrip()
+    while pos < len(text):
+        m = _TOKEN.match(text, pos)
+        if m is None:
+            raise ValueError(f"can't parse {text[pos:].strip()!r} in {text!r}")
+        tokens.append(m.group(1))
+        pos = m.end()
+    tokens.append(None)
+
+    def peek():
+        return tokens[0]
+
+    def take():
+        return tokens.pop(0)
+
+    def leads_to() -> LTLExpression:
+        left = binary()
+        if peek() == "~>":
+            take()
+            return LeadsTo(left, leads_to())
+        return left
+
+    def binary() -> LTLExpression:
+        left = unary()
+        if peek() in _BINARY:
+            return _BINARY[take()](left, binary())
+        return left
+
+    def unary() -> LTLExpression:
+        tok = take()
+        if tok in _UNARY:
+            return _UNARY[tok](unary())
+        if tok == "(":
+            e = leads_to()
+            if take() != ")":
+                raise ValueError(f"missing ) in {text!r}")
+            return e
+        if tok in names:
+            return names[tok]
+        if tok is None:
+            raise ValueError(f"{text!r} ends too soon")
+        raise ValueError(f"{tok!r} in {text!r} isn't a known predicate")
+
+    e = leads_to()
+    if peek() is not None:
+        raise ValueError(f"unexpected {peek()!r} in {text!r}")
+    return e
@@ -0,0 +1,54 @@
+from dataclasses import dataclass
+
+from typing import Any
+from typing import Callable
+from typing import Dict
+from typing import Optional
+
+
+@dataclass
+class Metric:
+    """A number measured where behaviors end, such as the messages a
+    protocol sent or the retries it took. Count them in the model's objects,
+    and read the count here; measure is given the objects by name.
+
+    Checking measures each distinct final state once, however many
+    behaviors end there, so the summary is over those states rather than
+    over behaviors; simulating measures the end of every run.
+
+        Metric("sent", lambda objs: objs["net"].sent)
+    """
+
+    name: str
+    measure: Callable[[Dict[str, Any]], float]
+
+
+@dataclass
+class MetricSummary:
+    name: str
+    count: int = 0
+    min: Optional[float] = None
+    max: Optional[float] = None
+    total: float = 0
+    # What was measured, for the summary
+    over: str = "distinct final states"
+
+    def add(self, v: float) -> None:
+        self.count += 1
+        self.total += v
+        self.min = v if self.min is None else min(self.min, v)
+        self.max = v if self.max is None else max(self.max, v)
+
+    @property
+    def mean(self) -> Optional[float]:
+        if self.count == 0:
+            return None
+        return self.total / self.count
+
+    def __str__(self) -> str:
+        if self.count == 0:
+            return f"{self.name}: not measured"
+        return (
+            f"{self.name}: min {self.min}, max {self.max}, mean {self.mean:.4g} "
+            f"over {self.count} {self.over}"
+        )
@@ -8,6 +8,10 @@ class PauseReason(Enum):
     PC_JUMPED = 1
     YIELD = 2
     DONE = 3
+    # Waiting on an Await; execution resumes by re-checking the condition
+    BLOCKED = 4
+    # Waiting on the embedder to supply the result of a call
+    HOST_CALL = 5
 
 
 class Fairness(Enum):
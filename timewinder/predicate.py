@@ -2,8 +2,14 @@ from abc import ABC
 from abc import abstractmethod
 from abc import abstractproperty
 
+from inspect import isfunction
+
+from typing import Any
 from typing import Callable
+from typing import Dict
 from typing import List
+from typing import Optional
+from typing import Tuple
 from typing import TYPE_CHECKING
 
 from varname import varname
@@ -20,6 +26,28 @@ def predicate(f):
     return Closure(f, FuncPredicate)
 
 
+def invariant(objecttype=None):
+    """Decorator declaring that every object of a type satisfies a check, at
+    every step. Models loaded from a file check declared invariants without
+    listing them in their specs.
+
+        @timewinder.invariant(Account)
+        def no_overdrafts(account):
+            return account.acc >= 0
+
+    Used bare, the check applies to every object."""
+    if isfunction(objecttype) and not hasattr(objecttype, "_cls"):
+        # Object types are wrapper functions too, but carry their class
+        return invariant(None)(objecttype)
+
+    def wrap(f):
+        inv = ForAll(objecttype, f, name=f.__name__)
+        inv.declared = True
+        return inv
+
+    return wrap
+
+
 class Predicate(ABC):
     @abstractmethod
     def check(self, sc: "StateController") -> bool:
@@ -41,6 +69,10 @@ class Predicate(ABC):
     def is_liveness(self) -> bool:
         return False
 
+    def location(self) -> Optional[Tuple[str, int]]:
+        """The source file and line defining this predicate, if known."""
+        return None
+
 
 class FuncPredicate(Predicate):
     def __init__(self, func, args, kwargs):
@@ -60,15 +92,22 @@ class FuncPredicate(Predicate):
     def name(self) -> str:
         return self.func.__name__
 
+    def location(self) -> Optional[Tuple[str, int]]:
+        return code_location(self.func)
+
     def __repr__(self) -> str:
         return self.func.__name__
 
 
 class ForAll(Predicate):
-    def __init__(self, object, pred: Callable[[ClassObject], bool]):
+    def __init__(
+        self, object, pred: Callable[[ClassObject], bool], name: Optional[str] = None
+    ):
         self.objecttype = object
         self.pred = pred
-        self._name = varname()
+        self._name = name if name is not None else varname()
+        # Set by @invariant, for loaders to find
+        self.declared = False
 
     @property
     def name(self) -> str:
@@ -87,5 +126,39 @@ class ForAll(Predicate):
                 return False
         return True
 
+    def location(self) -> Optional[Tuple[str, int]]:
+        return code_location(self.pred)
+
     def __repr__(self) -> str:
         return f"{self._name}:ForAll({self.pred.__name__})"
+
+
+class Expression(Predicate):
+    """A Python expression over the model's objects, by the names they're
+    mounted at, such as "alice.acc < 0"."""
+
+    def __init__(self, expr: str):
+        self.expr = expr
+        self.code = compile(expr, "<expression>", "eval")
+
+    @property
+    def name(self) -> str:
+        return self.expr
+
+    def check(self, sc: "StateController") -> bool:
+        return bool(eval(self.code, {}, model_objects(sc)))
+
+    def __repr__(self) -> str:
+        return self.expr
+
+
+def model_objects(sc: "StateController") -> Dict[str, Any]:
+    """The model's objects, by the names they're mounted at."""
+    return {k: v for k, v in sc.tree.items() if not k.startswith("_thread_")}
+
+
+def code_location(f) -> Optional[Tuple[str, int]]:
+    code = getattr(f, "__code__", None)
+    if code is None:
+        return None
+    return (code.co_filename, code.co_firstlineno)
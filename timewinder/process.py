@@ -1,3 +1,6 @@
+import os
+import traceback
+
 from abc import ABC
 from abc import abstractmethod
 
@@ -6,12 +9,16 @@ from .closure import Closure
 
 from inspect import isfunction
 
+from typing import Any
 from typing import List
+from typing import Optional
+from typing import Tuple
 
 from timewinder.statetree import CAS
 from timewinder.statetree import Hash
 from timewinder.statetree import TreeableType
 from timewinder.pause import Continue
+from timewinder.pause import Fairness
 from timewinder.pause import PauseReason
 
 
@@ -40,6 +47,44 @@ class Process(Object, ABC):
     def on_register_evaluator(self, idx: int) -> None:
         pass
 
+    def step_name(self) -> str:
+        """The name of the action this process takes next."""
+        return self.name
+
+    def step_names(self) -> List[str]:
+        """All the actions this process may take, as named by step_name."""
+        return [self.step_name()]
+
+    def location(self) -> Optional[Tuple[str, int]]:
+        """The source file and line this process resumes from, if known."""
+        return None
+
+    def variable(self, name: str) -> Any:
+        """The value of one of this process's variables in the current state.
+        Raises KeyError if it has none by that name."""
+        raise KeyError(name)
+
+    def fairness(self) -> Optional[Fairness]:
+        """Whether a behavior may stop while this process can still run.
+        Unless it's fair, it may, so liveness properties must already hold.
+        None leaves it to the Evaluator's default."""
+        return None
+
+    def verify(self) -> None:
+        """Raises if the model as it stands can't run this process, such as
+        for globals it reads that still aren't defined. Called before each
+        evaluation."""
+        pass
+
+    def set_profiler(self, profiler) -> None:
+        """Attaches a Profiler to tally finer-grained costs than whole actions."""
+        pass
+
+    def set_random(self, rng) -> None:
+        """Supplies the random.Random a process may draw from, or None when
+        every behavior is explored and randomness would hide some of them."""
+        pass
+
 
 class Step:
     def __init__(self, func, args, kwargs):
@@ -84,6 +129,25 @@ class FuncProcess(Process):
             return False
         return self.pc < len(self.steps)
 
+    def variable(self, name: str) -> Any:
+        if not isinstance(self.state, dict):
+            raise KeyError(name)
+        return self.state[name]
+
+    def step_name(self) -> str:
+        if not self.can_execute():
+            return "Done"
+        return self.steps[self.pc].func.__name__
+
+    def step_names(self) -> List[str]:
+        return [s.func.__name__ for s in self.steps]
+
+    def location(self) -> Optional[Tuple[str, int]]:
+        if not self.can_execute():
+            return None
+        code = self.steps[self.pc].func.__code__
+        return (code.co_filename, code.co_firstlineno)
+
     def execute(self, state_controller) -> Continue:
         assert self.can_execute()
         try:
@@ -98,10 +162,39 @@ class FuncProcess(Process):
         return f"FuncProcess([{funcs}])@{self.pc}:{self.state}"
 
 
+# A frame of model code: function name, file and line
+Frame = Tuple[str, str, int]
+
+
 class ProcessException(BaseException):
-    def __init__(self, info, wrapped):
+    def __init__(self, info, wrapped, backtrace: Optional[List[Frame]] = None):
         self.info = info
         self.wrapped = wrapped
+        # Outermost first, ending where the exception was raised
+        self.backtrace: List[Frame] = backtrace or []
+
+    def format_backtrace(self) -> str:
+        lines = ["Backtrace (most recent call last):"]
+        for name, filename, lineno in self.backtrace:
+            lines.append(f"  {name} at {filename}:{lineno}")
+        return "\n".join(lines)
 
     def __repr__(self):
-        return f"{self.info} caught an exception: {self.wrapped}"
+        out = f"{self.info} caught an exception: {self.wrapped}"
+        if self.backtrace:
+            out += "\n" + self.format_backtrace()
+        return out
+
+
+_PACKAGE_DIR = os.path.dirname(os.path.abspath(__file__)) + os.sep
+
+
+def model_frames(tb) -> List[Frame]:
+    """The frames of a traceback that are in model code, skipping
+    timewinder's own."""
+    out = []
+    for fs in traceback.extract_tb(tb):
+        if os.path.abspath(fs.filename).startswith(_PACKAGE_DIR):
+            continue
+        out.append((fs.name, fs.filename, fs.lineno))
+    return out
@@ -0,0 +1,126 @@
+import linecache
+import os
+import time
+
+from dataclasses import dataclass
+
+from typing import Callable
+from typing import Dict
+from typing import List
+from typing import Optional
+from typing import Tuple
+
+
+Location = Tuple[str, int]
+
+
+@dataclass
+class Tally:
+    count: int = 0
+    seconds: float = 0.0
+
+    def add(self, seconds: float) -> None:
+        self.count += 1
+        self.seconds += seconds
+
+
+@dataclass
+class Successors:
+    """The states an action led to, and how many of them hadn't been
+    explored yet."""
+
+    total: int = 0
+    new: int = 0
+
+
+class Profiler:
+    """Tallies executions and time per source line and per action, to show
+    which parts of a model dominate the cost of exploring it."""
+
+    def __init__(self, clock: Callable[[], float] = time.perf_counter):
+        self.clock = clock
+        self.spans: Dict[Location, Tally] = {}
+        self.actions: Dict[str, Tally] = {}
+        self.predicates: Dict[str, Tally] = {}
+        self.successors: Dict[str, Successors] = {}
+        # Time per line under the action that ran it, for flamegraphs
+        self.stacks: Dict[Tuple[str, Location], Tally] = {}
+        # The action running now, which lines are recorded under
+        self.action: Optional[str] = None
+
+    def record_instruction(self, location: Optional[Location], seconds: float) -> None:
+        if location is None:
+            return
+        self.spans.setdefault(location, Tally()).add(seconds)
+        if self.action is not None:
+            self.stacks.setdefault((self.action, location), Tally()).add(seconds)
+
+    def record_action(self, action: str, seconds: float) -> None:
+        self.actions.setdefault(action, Tally()).add(seconds)
+
+    def record_predicate(self, name: str, seconds: float) -> None:
+        self.predicates.setdefault(name, Tally()).add(seconds)
+
+    def record_successor(self, action: str, new: bool) -> None:
+        s = self.successors.setdefault(action, Successors())
+        s.total += 1
+        if new:
+            s.new += 1
+
+    def hot_spans(self) -> List[Tuple[Location, Tally]]:
+        return sorted(self.spans.items(), key=lambda kv: (-kv[1].seconds, kv[0]))
+
+    def hot_actions(self) -> List[Tuple[str, Tally]]:
+        return sorted(self.actions.items(), key=lambda kv: (-kv[1].seconds, kv[0]))
+
+    def report(self, top: int = 20) -> str:
+        header = f"{'time (ms)':>10} {'count':>8} {'succ':>8} {'new':>8}  action"
+        lines = ["Actions:", header]
+        for name, t in self.hot_actions()[:top]:
+            s = self.successors.get(name, Successors())
+            counts = f"{t.count:>8} {s.total:>8} {s.new:>8}"
+            lines.append(f"{t.seconds * 1000:>10.2f} {counts}  {name}")
+        lines.append("")
+        lines.append("Lines:")
+        lines.append(f"{'time (ms)':>10} {'count':>8}  location")
+        for (filename, lineno), t in self.hot_spans()[:top]:
+            source = linecache.getline(filename, lineno).strip()
+            lines.append(
+                f"{t.seconds * 1000:>10.2f} {t.count:>8}  {filename}:{lineno}: {source}"
+            )
+        return "\n".join(lines) + "\n"
+
+    def folded(self, weight: str = "time") -> str:
+        """The profile as folded stacks, one `frame;frame count` line each,
+        as inferno and flamegraph.pl read them. Weighted by "time" (in
+        microseconds), each action's lines sit under it and each predicate
+        under "predicates"; weighted by "states", each action counts the
+        unexplored states it led to, which is where a state space blows up."""
+        if weight == "states":
+            stacks = [((a,), s.new) for a, s in self.successors.items()]
+        elif weight == "time":
+            stacks = [
+                ((a, f"{os.path.basename(f)}:{n}"), _micros(t))
+                for (a, (f, n)), t in self.stacks.items()
+            ]
+            stacks.extend(
+                (("predicates", name), _micros(t))
+                for name, t in self.predicates.items()
+            )
+        else:
+            raise ValueError(f"can't weigh a flamegraph by {weight}")
+        lines = [
+            f"{';'.join(_frame(f) for f in frames)} {count}"
+            for frames, count in sorted(stacks)
+            if count > 0
+        ]
+        return "".join(line + "\n" for line in lines)
+
+
+def _micros(t: Tally) -> int:
+    return round(t.seconds * 1_000_000)
+
+
+def _frame(name: str) -> str:
+    # Semicolons separate frames, and the count follows the last space
+    return name.replace(";", ",").replace(" ", "_")
@@ -0,0 +1,42 @@
+from inspect import isfunction
+
+from .ltl import Always
+from .ltl import Eventually
+from .predicate import ForAll
+
+
+def always(objecttype=None):
+    """Decorator declaring that every object of a type satisfies a check in
+    every state of every behavior.
+
+        @timewinder.always(Account)
+        def no_overdrafts(account):
+            return account.acc >= 0
+    """
+    return _declare(Always, objecttype)
+
+
+def eventually(objecttype=None):
+    """Decorator declaring that every behavior reaches a state where every
+    object of a type satisfies a check.
+
+        @timewinder.eventually(Account)
+        def settled(account):
+            return account.pending == 0
+    """
+    return _declare(Eventually, objecttype)
+
+
+def _declare(op, objecttype):
+    # Used bare, the check applies to every object. Object types are wrapper
+    # functions too, but carry their class.
+    if isfunction(objecttype) and not hasattr(objecttype, "_cls"):
+        return _declare(op, None)(objecttype)
+
+    def wrap(f):
+        spec = op(ForAll(objecttype, f, name=f.__name__))
+        # For loaders to find
+        spec.declared = True
+        return spec
+
+    return wrap
@@ -1,6 +1,29 @@
+from functools import partial
+
+from typing import Dict
+from typing import Optional
+
 from .process import BytecodeProcess
 from timewinder.closure import Closure
+from timewinder.pause import Fairness
+
 
+def interp(
+    f=None,
+    *,
+    fairness: Optional[Fairness] = None,
+    actions: Optional[Dict[str, Fairness]] = None,
+):
+    """Decorator making a generator function a process. Whether behaviors
+    may stop while it can still run defaults to the Evaluator's fairness,
+    or is given for the whole process and, by step name, for its actions:
 
-def interp(f):
-    return Closure(f, BytecodeProcess)
+        @timewinder.process(
+            fairness=Fairness.WEAKLY_FAIR, actions={"retry": Fairness.NORMAL}
+        )
+    """
+    if f is None:
+        return lambda f: interp(f, fairness=fairness, actions=actions)
+    if fairness is None and actions is None:
+        return Closure(f, BytecodeProcess)
+    return Closure(f, partial(BytecodeProcess, fairness=fairness, actions=actions))
@@ -0,0 +1,138 @@
+from typing import Any
+from typing import Dict
+from typing import List
+from typing import Optional
+from typing import Set
+
+from timewinder.pause import Continue
+from timewinder.pause import PauseReason
+from timewinder.statetree import Hash
+
+from .process import BytecodeProcess
+
+
+_PAUSES = (
+    PauseReason.YIELD,
+    PauseReason.DONE,
+    PauseReason.BLOCKED,
+    PauseReason.HOST_CALL,
+)
+
+
+class DebugSession:
+    """Steps through a process's bytecode, as the evaluator would execute it.
+
+    Calls to ordinary functions run natively within a single instruction, so
+    stepping by instruction or by line already steps over them.
+
+    Stepping backwards restores the state saved before the first step, then
+    replays every instruction but the last; the stack between yields can
+    hold values that can't be saved, so there's no snapshot per instruction."""
+
+    def __init__(self, process: BytecodeProcess, state_controller):
+        self.process = process
+        self.interp = process.interp
+        self.state_controller = state_controller
+        self.breakpoints: Set[int] = set()
+        self.watches: List[str] = []
+        self._checkpoint: Optional[Hash] = None
+        # Instructions executed since the checkpoint
+        self.executed = 0
+
+    @property
+    def done(self) -> bool:
+        return not self.process.can_execute()
+
+    def line(self) -> Optional[int]:
+        loc = self.interp.location()
+        if loc is None:
+            return None
+        return loc[1]
+
+    def add_breakpoint(self, line: int) -> None:
+        self.breakpoints.add(line)
+
+    def remove_breakpoint(self, line: int) -> None:
+        self.breakpoints.discard(line)
+
+    def step(self) -> Continue:
+        """Executes a single instruction."""
+        if self.done:
+            return Continue(PauseReason.DONE)
+        if self._checkpoint is None:
+            self._checkpoint = list(self.state_controller.commit())[0]
+        # The saved state no longer describes the process
+        self.process.set_hash = None
+        self.executed += 1
+        self.interp.state_controller = self.state_controller
+        try:
+            cont = self.interp.interpret_instruction()
+        except Exception as e:
+            raise self.process.error(e)
+        finally:
+            self.interp.state_controller = None
+        if cont.kind == PauseReason.YIELD and cont.yield_msg != "":
+            self.process._stepname = cont.yield_msg
+        return cont
+
+    def step_back(self) -> bool:
+        """Undoes the last instruction, returning False if there's none."""
+        if self._checkpoint is None or self.executed == 0:
+            return False
+        target = self.executed - 1
+        self.state_controller.restore(self._checkpoint)
+        self.executed = 0
+        while self.executed < target:
+            self.step()
+        return True
+
+    def step_line(self) -> Continue:
+        """Executes until the source line changes or the process pauses."""
+        start = self.line()
+        while True:
+            cont = self.step()
+            if cont.kind in _PAUSES or self.done or self.line() != start:
+                return cont
+
+    def cont(self) -> Continue:
+        """Executes until a breakpoint is reached or the process pauses."""
+        cont = self.step()
+        while cont.kind not in _PAUSES and not self.done:
+            if self._at_breakpoint():
+                return cont
+            cont = self.step()
+        return cont
+
+    def variables(self) -> Dict[str, Any]:
+        """The process's local variables, with bound objects resolved."""
+        self.interp.state_controller = self.state_controller
+        try:
+            out = dict(self.interp.state)
+            for name in self.interp.binds:
+                out[name] = self.interp.resolve_var_by_name(name)
+            return out
+        finally:
+            self.interp.state_controller = None
+
+    def stack(self) -> List[Any]:
+        return list(self.interp.ops.stack)
+
+    def evaluate(self, expr: str) -> Any:
+        """Evaluates a Python expression against the process's variables."""
+        return eval(expr, dict(self.interp.func.__globals__), self.variables())
+
+    def watch(self, expr: str) -> None:
+        self.watches.append(expr)
+
+    def watch_values(self) -> Dict[str, Any]:
+        out: Dict[str, Any] = {}
+        for expr in self.watches:
+            try:
+                out[expr] = self.evaluate(expr)
+            except Exception as e:
+                out[expr] = e
+        return out
+
+    def _at_breakpoint(self) -> bool:
+        inst = self.interp.instructions[self.interp.pc]
+        return inst.starts_line is not None and inst.starts_line in self.breakpoints
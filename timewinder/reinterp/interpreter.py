@@ -1,7 +1,14 @@
 import builtins
+import collections.abc
 import dis
+import functools
+import itertools
+import json
 import sys
-import inspect
+import linecache
+import math
+import random
+import types
 from dataclasses import dataclass
 
 from timewinder.object import Object
@@ -9,7 +16,11 @@ from timewinder.pause import Continue
 from timewinder.pause import PauseReason
 from timewinder.pause import Fairness
 from timewinder.generators import NonDeterministicSet
+from timewinder.statetree import SavedFunction
+from timewinder.statetree import SavedIterator
+from timewinder.statetree import SavedModule
 
+from .opcodes import InterpreterError
 from .opcodes import OpcodeInterpreter
 
 from typing import Any
@@ -17,12 +28,48 @@ from typing import Callable
 from typing import Dict
 from typing import List
 from typing import Optional
+from typing import Tuple
 
 
 OBJECT_PREFIX = "__object__"
 
+# The modules a process may import. Each must behave the same every time it's
+# called, or checking would find different states on different runs. The
+# exception is random, which draws from the simulator's seeded generator.
+MODULES: Dict[str, types.ModuleType] = {
+    "functools": functools,
+    "json": json,
+    "math": math,
+}
+
+# The most values an iterator may produce. They're all drawn out when it's
+# made, so it can be saved, and one that never ends would never finish.
+MAX_ITERATOR_ITEMS = 10000
+
 
 # Method delegates called resolve_* return the value for the stack
+@dataclass
+class HostCall:
+    """A call to a timewinder function the interpreter leaves to its host."""
+
+    tag: str
+    args: List[Any]
+
+
+class _Blocked(Exception):
+    def __init__(self, stub: "TagStub"):
+        self.stub = stub
+
+
+class _Done(Exception):
+    pass
+
+
+class _PendingHostCall(Exception):
+    def __init__(self, call: HostCall):
+        self.call = call
+
+
 # Method delegates called on_* return the Optional[Continue|PauseReason] (None is normal exec)
 class Interpreter:
     def __init__(self, func: Callable, in_args=None, in_kwargs=None):
@@ -31,9 +78,11 @@ class Interpreter:
         self.ops = OpcodeInterpreter(self, instructions)
         self.binds: Dict[str, Any] = {}
         self.state: Dict[str, Any] = {}
+        self.random: Optional[random.Random] = None
         self.return_val = None
         self.done = False
         self.thread_idx: Optional[int] = None
+        self.host_call: Optional[HostCall] = None
 
         # Set from the outside
         self.state_controller = None
@@ -52,7 +101,27 @@ class Interpreter:
                 self.state[name] = a
 
     def interpret_instruction(self) -> Continue:
-        return self.ops.interpret_instruction()
+        try:
+            return self.ops.interpret_instruction()
+        except _Blocked as b:
+            # The call consumed the function and its arguments, leaving the
+            # stack as it was before the Await was loaded, so start over there.
+            self.ops.pc = b.stub.pc
+            return Continue(PauseReason.BLOCKED)
+        except _Done:
+            self.ops.pc = -1
+            return Continue(PauseReason.DONE)
+        except _PendingHostCall as p:
+            self.host_call = p.call
+            self.ops.pc += 1
+            return Continue(PauseReason.HOST_CALL)
+
+    def resume_host_call(self, value) -> None:
+        """Supplies the result of the pending host call."""
+        if self.host_call is None:
+            raise ValueError("no host call is pending")
+        self.host_call = None
+        self.ops.push_stack(value)
 
     def on_return(self, val):
         self.return_val = val
@@ -68,22 +137,109 @@ class Interpreter:
             )
         return None
 
-    def resolve_call_function(self, func, args):
+    def resolve_call_function(self, func, args, kwargs=None):
+        if kwargs is None:
+            kwargs = {}
         if isinstance(func, TagStub):
             if func.tag == "thread_id":
                 return self.thread_idx
-            raise NotImplementedError()
-        else:
-            return func(*args)
+            if func.tag == "await":
+                if args[0]:
+                    return True
+                raise _Blocked(func)
+            if func.tag == "done":
+                raise _Done()
+            raise _PendingHostCall(HostCall(func.tag, list(args)))
+        args = [self.materialize(a) for a in args]
+        kwargs = {k: self.materialize(v) for k, v in kwargs.items()}
+        ret = self.materialize(func)(*args, **kwargs)
+        if isinstance(ret, collections.abc.Iterator):
+            # Such as map or filter
+            return self.save_iterator(ret)
+        return ret
+
+    def save_iterator(self, values) -> SavedIterator:
+        """Draws out the values of an iterable, so they can be saved."""
+        items = tuple(itertools.islice(values, MAX_ITERATOR_ITEMS + 1))
+        if len(items) > MAX_ITERATOR_ITEMS:
+            raise InterpreterError(
+                self.pc,
+                f"an iterator may produce at most {MAX_ITERATOR_ITEMS} values, "
+                "as they're drawn out to be saved",
+            )
+        return SavedIterator(items)
+
+    def make_function(self, code, name, defaults, kwdefaults, closure):
+        for i, c in enumerate(self.func.__code__.co_consts):
+            if c is code:
+                return SavedFunction(
+                    i, name, tuple(defaults), kwdefaults, tuple(closure)
+                )
+        raise ValueError(f"{name} isn't defined in {self.func.__name__}")
+
+    def capture_var(self, varname: str):
+        """Like resolve_var_by_name, but leaves objects as references, which
+        can be saved."""
+        if varname in self.binds:
+            return self.binds[varname]
+        return self.resolve_var_by_name(varname)
+
+    def resolve_import(self, name: str, level: int):
+        if level == 0 and name == "random":
+            if self.random is None:
+                raise ImportError(
+                    "random is only available when simulating; "
+                    "use timewinder.generators.Set to check every choice"
+                )
+            return SavedModule(name)
+        if level != 0 or name not in MODULES:
+            raise ImportError(
+                f"processes can't import {name}; available modules are "
+                + ", ".join(sorted(MODULES))
+            )
+        return SavedModule(name)
+
+    def resolve_import_from(self, module, name: str):
+        val = getattr(self.materialize(module), name)
+        if callable(val):
+            return SavedModule(module.name, name)
+        return val
+
+    def materialize(self, val):
+        """Turns a function made by make_function into one Python can call,
+        an iterator into a Python iterator and an import into its module."""
+        if isinstance(val, SavedIterator):
+            return iter(val.items)
+        if isinstance(val, SavedFunction):
+            return self._make_callable(val)
+        if isinstance(val, SavedModule):
+            module = self.random if val.name == "random" else MODULES[val.name]
+            if val.attr is not None:
+                return getattr(module, val.attr)
+            return module
+        return val
+
+    def _make_callable(self, val: SavedFunction):
+        code = self.func.__code__.co_consts[val.const]
+        defaults = [self.materialize(self.resolve_var(v)) for v in val.defaults]
+        cells = [
+            types.CellType(self.materialize(self.resolve_var(v)))
+            for v in val.closure
+        ]
+        f = types.FunctionType(
+            code,
+            self.func.__globals__,
+            val.name,
+            tuple(defaults) or None,
+            tuple(cells) or None,
+        )
+        f.__kwdefaults__ = val.kwdefaults
+        return f
 
     def resolve_load_method(self, obj, name):
-        method = getattr(obj, name)
-        is_bound = False
-        if inspect.isbuiltin(method):
-            is_bound = True
-        if inspect.ismethod(method):
-            is_bound = True
-        return (is_bound, method)
+        # getattr already binds methods to obj; anything else, like a module's
+        # function, mustn't be passed obj either.
+        return (True, getattr(self.materialize(obj), name))
 
     def resolve_var_by_name(self, varname: str):
         if varname in self.state:
@@ -91,6 +247,18 @@ class Interpreter:
         if varname in self.binds:
             ref = self.binds[varname]
             return self._get_from_tree(ref)
+        return self.resolve_free_var(varname)
+
+    def resolve_free_var(self, varname: str):
+        """A variable the process closes over, from the function enclosing its
+        definition. Like globals, these are read when used and never saved."""
+        code = self.func.__code__
+        if varname in code.co_freevars and self.func.__closure__ is not None:
+            cell = self.func.__closure__[code.co_freevars.index(varname)]
+            try:
+                return cell.cell_contents
+            except ValueError:
+                pass
         raise LookupError(f"Couldn't find variable {varname}")
 
     def resolve_var(self, var):
@@ -108,12 +276,52 @@ class Interpreter:
     def instructions(self) -> List:
         return self.ops.instructions
 
+    def location(self) -> Optional[Tuple[str, int]]:
+        """Returns the source file and line of the current instruction."""
+        pc = self.pc
+        if pc < 0 or pc >= len(self.ops.instructions):
+            return None
+        while pc >= 0:
+            line = self.ops.instructions[pc].starts_line
+            if line is not None:
+                return (self.func.__code__.co_filename, line)
+            pc -= 1
+        return (self.func.__code__.co_filename, self.func.__code__.co_firstlineno)
+
+    def disassemble(self, with_source: bool = False) -> str:
+        """Lists the instructions in the style of `dis`, marking the current one.
+
+        With `with_source`, each line of source precedes its instructions."""
+        filename = self.func.__code__.co_filename
+        out = []
+        for i in range(len(self.ops.instructions)):
+            inst = self.ops.instructions[i]
+            lineno = ""
+            if inst.starts_line is not None:
+                if with_source:
+                    if i != 0:
+                        out.append("")
+                    source = linecache.getline(filename, inst.starts_line).rstrip()
+                    out.append(f"{inst.starts_line:>4} {source}")
+                else:
+                    lineno = str(inst.starts_line)
+            current = "-->" if i == self.pc else ""
+            target = ">>" if inst.is_jump_target else ""
+            line = f"{lineno:>4} {current:>3} {target:>2} {inst.offset:>4}"
+            line += f" {inst.opname:<20}"
+            if inst.arg is not None:
+                line += f" {inst.arg:>3}"
+            if inst.argrepr:
+                line += f" ({inst.argrepr})"
+            out.append(line.rstrip())
+        return "\n".join(out) + "\n"
+
     def resolve_getattr(self, base, attr):
         if isinstance(base, str):
             if base.startswith(OBJECT_PREFIX):
                 object = self._get_from_tree(base)
                 return getattr(object, attr)
-        return getattr(base, attr)
+        return getattr(self.materialize(base), attr)
 
     def on_setattr(self, base, attr, val):
         if isinstance(base, str):
@@ -137,12 +345,19 @@ class Interpreter:
         print("\n\n")
 
     def resolve_global(self, name):
-        func_mod = sys.modules[self.func.__module__]
-        g = getattr(func_mod, name, None)
-        if g is None:
-            g = self.func.__globals__.get(name, None)
-        if g is None:
+        # Module scope first, then builtins, as Python does
+        func_mod = sys.modules.get(self.func.__module__)
+        if func_mod is not None and hasattr(func_mod, name):
+            g = getattr(func_mod, name)
+        elif name in self.func.__globals__:
+            g = self.func.__globals__[name]
+        elif hasattr(builtins, name):
             g = getattr(builtins, name)
+        else:
+            raise LookupError(f"Couldn't find global {name}")
+        if g is random:
+            # Draw from the simulator's generator, not the global one
+            return self.resolve_import("random", 0)
         tag = getattr(g, "__timewinder_tag", None)
         if tag is None:
             return g
@@ -2,6 +2,7 @@ import dis
 import operator
 from timewinder.pause import Continue
 from timewinder.pause import PauseReason
+from timewinder.statetree import SavedIterator
 
 from typing import Any
 from typing import List
@@ -18,11 +19,31 @@ ProgressType = Optional[Union[PauseReason, Continue]]
 DEFAULT_CONTINUE = Continue()
 
 
+class InterpreterError(Exception):
+    """The bytecode can't be executed as written, such as a jump to an offset
+    that doesn't exist or an instruction popping from an empty stack."""
+
+    def __init__(self, pc: int, msg: str):
+        super().__init__(f"pc {pc}: {msg}")
+        self.pc = pc
+        self.msg = msg
+
+
+class UnsupportedInstruction(InterpreterError, NotImplementedError):
+    pass
+
+
 class OpcodeInterpreter:
     def __init__(self, proc: "Interpreter", instructions):
         self.proc = proc
         self.stack: List[Any] = []
-        self.instructions: Dict[int, dis.Instruction] = {i: inst for (i, inst) in enumerate(instructions)}
+        self.instructions: Dict[int, dis.Instruction] = {
+            i: inst for (i, inst) in enumerate(instructions)
+        }
+        # Jump targets are byte offsets; map them to instruction indices once
+        self.offsets: Dict[int, int] = {
+            inst.offset: i for (i, inst) in self.instructions.items()
+        }
         self.pc = 0
 
     def push_stack(self, v: Any):
@@ -31,25 +52,33 @@ class OpcodeInterpreter:
         self.stack.append(v)
 
     def pop_stack(self) -> Any:
-        assert len(self.stack) > 0
+        if len(self.stack) == 0:
+            raise InterpreterError(self.pc, "pop from an empty stack")
         v = self.stack[-1]
         self.stack = self.stack[:-1]
         return v
 
-    def find_pc_for_offset(self, offset):
-        for i, x in self.instructions.items():
-            if x.offset == offset:
-                return i
+    def peek_stack(self) -> Any:
+        if len(self.stack) == 0:
+            raise InterpreterError(self.pc, "read from an empty stack")
+        return self.stack[-1]
+
+    def find_pc_for_offset(self, offset) -> int:
+        if offset in self.offsets:
+            return self.offsets[offset]
+        raise InterpreterError(
+            self.pc, f"jump to {offset!r}, which isn't an instruction"
+        )
 
     def interpret_instruction(self) -> Continue:
+        if self.pc not in self.instructions:
+            raise InterpreterError(self.pc, "no instruction to execute")
         inst = self.instructions[self.pc]
         methodname = "exec_" + inst.opname.lower()
-        try:
-            method = self.__getattribute__(methodname)
-            ret: ProgressType = method(inst)
-        except AttributeError as e:
-            print(e)
+        method = getattr(self, methodname, None)
+        if method is None:
             return self._exec_debug(inst)
+        ret: ProgressType = method(inst)
         # Unify return types into Continue
         # None case
         out: Continue
@@ -70,8 +99,8 @@ class OpcodeInterpreter:
 
     def _exec_debug(self, inst):
         self.proc.debug_print()
-        raise NotImplementedError(
-            f"instructions of type {inst.opname} aren't supported yet"
+        raise UnsupportedInstruction(
+            self.pc, f"instructions of type {inst.opname} aren't supported yet"
         )
 
     def exec_load_const(self, inst):
@@ -84,6 +113,25 @@ class OpcodeInterpreter:
         val = self.proc.resolve_global(inst.argval)
         self.push_stack(val)
 
+    def exec_store_global(self, inst):
+        # Module globals aren't part of the model's state, so a write would be
+        # shared by every behavior and undone by none.
+        raise InterpreterError(
+            self.pc,
+            f"can't assign global {inst.argval}; "
+            "keep shared state in a timewinder.object",
+        )
+
+    exec_delete_global = exec_store_global
+
+    def exec_import_name(self, inst):
+        self.pop_stack()  # fromlist
+        level = self.pop_stack()
+        self.push_stack(self.proc.resolve_import(inst.argval, level))
+
+    def exec_import_from(self, inst):
+        self.push_stack(self.proc.resolve_import_from(self.peek_stack(), inst.argval))
+
     def exec_load_attr(self, inst):
         val = self.proc.resolve_getattr(self.pop_stack(), inst.argval)
         self.push_stack(val)
@@ -126,18 +174,80 @@ class OpcodeInterpreter:
         v = self.proc.resolve_var(tos1)
         self.push_stack(v[tos])
 
+    def _pop_n(self, inst, n) -> List[Any]:
+        if n > len(self.stack):
+            raise InterpreterError(
+                self.pc, f"{inst.opname} of {n} values from a shorter stack"
+            )
+        vals = [self.pop_stack() for _ in range(n)]
+        vals.reverse()
+        return vals
+
+    def exec_build_list(self, inst):
+        self._check_count(inst)
+        self.push_stack(self._pop_n(inst, inst.argval))
+
+    def exec_build_map(self, inst):
+        self._check_count(inst)
+        vals = self._pop_n(inst, 2 * inst.argval)
+        self.push_stack(dict(zip(vals[::2], vals[1::2])))
+
+    def exec_build_const_key_map(self, inst):
+        self._check_count(inst)
+        keys = self.pop_stack()
+        if not isinstance(keys, tuple) or len(keys) != inst.argval:
+            raise InterpreterError(self.pc, f"BUILD_CONST_KEY_MAP with keys {keys!r}")
+        self.push_stack(dict(zip(keys, self._pop_n(inst, inst.argval))))
+
+    def exec_list_extend(self, inst):
+        tos = self.pop_stack()
+        if not isinstance(inst.argval, int) or not 0 < inst.argval <= len(self.stack):
+            raise InterpreterError(self.pc, f"LIST_EXTEND of {inst.argval!r}")
+        self.stack[-inst.argval].extend(self.proc.resolve_var(tos))
+
+    def exec_dict_update(self, inst):
+        tos = self.pop_stack()
+        if not isinstance(inst.argval, int) or not 0 < inst.argval <= len(self.stack):
+            raise InterpreterError(self.pc, f"DICT_UPDATE of {inst.argval!r}")
+        self.stack[-inst.argval].update(self.proc.resolve_var(tos))
+
+    def exec_list_to_tuple(self, inst):
+        self.push_stack(tuple(self.pop_stack()))
+
+    # Python 3.8 unpacks `[*a, *b]`, `(*a, *b)` and `{**a, **b}` in one
+    # instruction, where 3.9 builds and then extends or updates
+
+    def exec_build_list_unpack(self, inst):
+        self._check_count(inst)
+        out: List[Any] = []
+        for v in self._pop_n(inst, inst.argval):
+            out.extend(self.proc.resolve_var(v))
+        self.push_stack(out)
+
+    def exec_build_tuple_unpack(self, inst):
+        self.exec_build_list_unpack(inst)
+        self.exec_list_to_tuple(inst)
+
+    def exec_build_map_unpack(self, inst):
+        self._check_count(inst)
+        out: Dict[Any, Any] = {}
+        for v in self._pop_n(inst, inst.argval):
+            out.update(self.proc.resolve_var(v))
+        self.push_stack(out)
+
     def exec_build_slice(self, inst):
+        if inst.argval not in (2, 3):
+            raise InterpreterError(self.pc, f"BUILD_SLICE of {inst.argval!r} values")
         tos = self.pop_stack()
         tos1 = self.pop_stack()
         if inst.argval == 2:
             self.push_stack(slice(tos1, tos))
-        elif inst.argval == 3:
+        else:
             tos2 = self.pop_stack()
             self.push_stack(slice(tos2, tos1, tos))
-        else:
-            assert False, "instruction has invalid argval"
 
     def exec_call_method(self, inst):
+        self._check_count(inst)
         args = []
         for i in range(inst.argval):
             args.append(self.pop_stack())
@@ -152,6 +262,7 @@ class OpcodeInterpreter:
         self.push_stack(ret)
 
     def exec_call_function(self, inst):
+        self._check_count(inst)
         args = []
         for i in range(inst.argval):
             args.append(self.pop_stack())
@@ -161,6 +272,66 @@ class OpcodeInterpreter:
         ret = self.proc.resolve_call_function(func, args)
         self.push_stack(ret)
 
+    def exec_call_function_kw(self, inst):
+        self._check_count(inst)
+        names = self.pop_stack()
+        if not isinstance(names, tuple) or len(names) > inst.argval:
+            raise InterpreterError(self.pc, f"CALL_FUNCTION_KW with names {names!r}")
+        args = []
+        for i in range(inst.argval):
+            args.append(self.pop_stack())
+        args.reverse()
+        func = self.pop_stack()
+        split = len(args) - len(names)
+        kwargs = dict(zip(names, args[split:]))
+        ret = self.proc.resolve_call_function(func, args[:split], kwargs)
+        self.push_stack(ret)
+
+    def exec_make_function(self, inst):
+        name = self.pop_stack()
+        code = self.pop_stack()
+        closure = self.pop_stack() if inst.argval & 0x08 else ()
+        if inst.argval & 0x04:
+            # Annotations don't affect behavior
+            self.pop_stack()
+        kwdefaults = self.pop_stack() if inst.argval & 0x02 else None
+        defaults = self.pop_stack() if inst.argval & 0x01 else ()
+        self.push_stack(
+            self.proc.make_function(code, name, defaults, kwdefaults, closure)
+        )
+
+    def exec_load_closure(self, inst):
+        # Captured by value; see SavedFunction
+        self.push_stack(self.proc.capture_var(inst.argval))
+
+    def exec_store_deref(self, inst):
+        return self.proc.on_store_fast(inst.argval, self.pop_stack())
+
+    def exec_load_deref(self, inst):
+        self.push_stack(self.proc.resolve_var_by_name(inst.argval))
+
+    def exec_build_tuple(self, inst):
+        self._check_count(inst)
+        self.push_stack(tuple(self._pop_n(inst, inst.argval)))
+
+    def exec_format_value(self, inst):
+        # The low bits choose a conversion, as for f"{x!r}"; bit 2 says a
+        # format spec is on the stack, as for f"{x:>5}"
+        spec = self.pop_stack() if inst.arg & 0x04 else ""
+        value = self.pop_stack()
+        convert = {0: None, 1: str, 2: repr, 3: ascii}[inst.arg & 0x03]
+        if convert is not None:
+            value = convert(value)
+        self.push_stack(format(value, spec))
+
+    def exec_build_string(self, inst):
+        self._check_count(inst)
+        self.push_stack("".join(self._pop_n(inst, inst.argval)))
+
+    def _check_count(self, inst):
+        if not isinstance(inst.argval, int) or inst.argval < 0:
+            raise InterpreterError(self.pc, f"{inst.opname} of {inst.argval!r} values")
+
     def exec_pop_top(self, inst):
         self.pop_stack()
 
@@ -200,8 +371,27 @@ class OpcodeInterpreter:
         elif inst.argval == ">=":
             self.push_stack(tos1 >= tos)
         else:
-            self.proc.debug_print()
-            assert False, f"unknown operator {inst.argval}"
+            raise InterpreterError(self.pc, f"unknown comparison {inst.argval!r}")
+
+    def exec_get_iter(self, inst):
+        # Iterators live on the stack across yields, so they're kept as the
+        # values left to produce, which the state tree can save
+        tos = self.proc.resolve_var(self.pop_stack())
+        if isinstance(tos, SavedIterator):
+            self.push_stack(tos)
+        else:
+            self.push_stack(self.proc.save_iterator(tos))
+
+    def exec_for_iter(self, inst):
+        tos = self.pop_stack()
+        if not isinstance(tos, SavedIterator):
+            raise InterpreterError(self.pc, f"FOR_ITER over {tos!r}, not an iterator")
+        remaining = tos.items
+        if len(remaining) == 0:
+            self.pc = self.find_pc_for_offset(inst.argval)
+            return PauseReason.PC_JUMPED
+        self.push_stack(SavedIterator(remaining[1:]))
+        self.push_stack(remaining[0])
 
     def exec_pop_jump_if_false(self, inst):
         if not self.pop_stack():
@@ -219,9 +409,14 @@ class OpcodeInterpreter:
         self.pc = self.find_pc_for_offset(inst.argval)
         return PauseReason.PC_JUMPED
 
+    def exec_jump_forward(self, inst):
+        # dis resolves the relative jump into an offset already
+        self.pc = self.find_pc_for_offset(inst.argval)
+        return PauseReason.PC_JUMPED
+
     def exec_return_value(self, inst):
         self.pc = -1
         return self.proc.on_return(self.pop_stack())
 
     def exec_yield_value(self, inst):
-        return Continue(PauseReason.YIELD, self.stack[-1])
+        return Continue(PauseReason.YIELD, self.peek_stack())
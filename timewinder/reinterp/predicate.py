@@ -1,4 +1,5 @@
 from timewinder.predicate import Predicate
+from timewinder.predicate import code_location
 from timewinder.statetree import StateController
 from timewinder.pause import Continue
 from timewinder.pause import PauseReason
@@ -22,3 +23,6 @@ class InterpretedPredicate(Predicate):
     @property
     def name(self) -> str:
         return self._name
+
+    def location(self):
+        return code_location(self.interp.func)
@@ -1,23 +1,132 @@
+import copy
+import linecache
+import logging
+import os
+import pprint
+import warnings
+import weakref
+
+from dataclasses import dataclass
+from enum import Enum
+
 from timewinder.process import Process
 from timewinder.process import ProcessException
+from timewinder.process import model_frames
 from timewinder.statetree import CAS
 from timewinder.statetree import Hash
+from timewinder.statetree import SavedFunction
+from timewinder.statetree import SavedIterator
 from timewinder.statetree import TreeableType
 from timewinder.pause import Continue
+from timewinder.pause import Fairness
 from timewinder.pause import PauseReason
 
+from .interpreter import HostCall
 from .interpreter import Interpreter
+from .interpreter import OBJECT_PREFIX
+from .verify import OPT_IN_RULES
+from .verify import TimewinderWarning
+from .verify import VerificationError
+from .verify import check_globals
+from .verify import check_locals
+from .verify import lint
+from .verify import source_lines
+from .verify import verify
 
+from typing import Any
 from typing import Callable
+from typing import Dict
+from typing import List
 from typing import Optional
+from typing import Tuple
+
+
+logger = logging.getLogger(__name__)
+
+# Logs every instruction executed, at DEBUG. It's off even when the rest of
+# timewinder logs at DEBUG, since it's so verbose; turn it on with
+# trace_logger.setLevel(logging.DEBUG).
+trace_logger = logging.getLogger("timewinder.instructions")
+trace_logger.setLevel(logging.INFO)
+
+# The code of processes already linted, so each function's warnings are given
+# once rather than for every process built from it
+_linted: "weakref.WeakSet[Any]" = weakref.WeakSet()
+
+
+class StepKind(Enum):
+    DONE = "done"
+    YIELDED = "yielded"
+    BLOCKED = "blocked"
+    HOST_CALL = "host_call"
+    OUT_OF_FUEL = "out_of_fuel"
+
+
+@dataclass
+class StepOutcome:
+    """Why run_step stopped. Every kind but DONE can be resumed by calling
+    run_step again (after resume_host_call, for a HOST_CALL)."""
+
+    kind: StepKind
+    # The step name reached, when YIELDED
+    label: str = ""
+    # The source location of the Await, when BLOCKED
+    guard: Optional[Tuple[str, int]] = None
+    call: Optional[HostCall] = None
+    fairness: Fairness = Fairness.NORMAL
 
 
 class BytecodeProcess(Process):
-    def __init__(self, func: Callable, in_args=None, in_kwargs=None):
+    def __init__(
+        self,
+        func: Callable,
+        in_args=None,
+        in_kwargs=None,
+        fairness: Optional[Fairness] = None,
+        actions: Optional[Dict[str, Fairness]] = None,
+    ):
         self._funcname = func.__name__
         self._stepname = "start"
+        self._fairness = fairness
+        # By step name, overriding the process's fairness for that action
+        self._action_fairness = actions or {}
         self.interp = Interpreter(func, in_args, in_kwargs)
+        self._verify(func)
+        self._globals_verified = False
         self.set_hash: Optional[Hash] = None
+        self.profiler = None
+
+    def _verify(self, func: Callable) -> None:
+        """Fails fast on bytecode that can't run, and warns about likely
+        mistakes, before any state is explored."""
+        insts = list(self.interp.instructions.values())
+        lines = source_lines(insts)
+        filename = func.__code__.co_filename
+        problems = verify(insts) + check_locals(func)
+        if problems:
+            raise VerificationError(func.__name__, filename, problems, lines)
+        if func.__code__ in _linted:
+            return
+        _linted.add(func.__code__)
+        for d in lint(func):
+            if d.rule in OPT_IN_RULES:
+                continue
+            line = lines[d.pc] or func.__code__.co_firstlineno
+            msg = f"{d.msg} [{d.rule}]"
+            warnings.warn_explicit(msg, TimewinderWarning, filename, line)
+
+    def verify(self) -> None:
+        # Globals are checked late, so the module may define them after the
+        # process is made
+        if self._globals_verified:
+            return
+        func = self.interp.func
+        problems = check_globals(func)
+        if problems:
+            lines = source_lines(self.interp.instructions.values())
+            filename = func.__code__.co_filename
+            raise VerificationError(func.__name__, filename, problems, lines)
+        self._globals_verified = True
 
     def on_register_evaluator(self, idx: int) -> None:
         self.interp.thread_idx = idx
@@ -31,23 +140,118 @@ class BytecodeProcess(Process):
             return False
         return self.interp.pc < len(self.interp.instructions)
 
+    def step_names(self) -> List[str]:
+        labels = ["start"]
+        insts = self.interp.instructions
+        for i in range(1, len(insts)):
+            prev = insts[i - 1]
+            if insts[i].opname == "YIELD_VALUE" and prev.opname == "LOAD_CONST":
+                if isinstance(prev.argval, str) and prev.argval not in labels:
+                    labels.append(prev.argval)
+        return [f"{self._funcname}@{label}" for label in labels]
+
+    def location(self) -> Optional[Tuple[str, int]]:
+        if not self.can_execute():
+            return None
+        return self.interp.location()
+
+    def variable(self, name: str) -> Any:
+        return self.interp.state[name]
+
+    def fairness(self) -> Optional[Fairness]:
+        return self._action_fairness.get(self._stepname, self._fairness)
+
+    def set_profiler(self, profiler) -> None:
+        self.profiler = profiler
+
+    def set_random(self, rng) -> None:
+        self.interp.random = rng
+
     def execute(self, state_controller):
+        outcome = self.run_step(state_controller)
+        if outcome.kind == StepKind.HOST_CALL:
+            assert outcome.call is not None
+            raise ProcessException(
+                f"{self.name}@{self.interp.pc}",
+                NotImplementedError(f"{outcome.call.tag} isn't supported here"),
+            )
+        if outcome.kind == StepKind.BLOCKED:
+            return Continue(PauseReason.BLOCKED)
+        if outcome.kind == StepKind.YIELDED:
+            return Continue(PauseReason.YIELD, outcome.label, outcome.fairness)
+        return Continue(PauseReason.DONE)
+
+    def run_step(self, state_controller, fuel: Optional[int] = None) -> StepOutcome:
+        """Executes until the process yields, blocks, finishes or needs its
+        host, or until `fuel` instructions have run."""
         self.set_hash = None
         self.interp.state_controller = state_controller
-        cont = Continue()
-        while self.interp.pc < len(self.interp.instructions):
+        try:
+            outcome = self._run(fuel)
+        finally:
+            self.interp.state_controller = None
+        logger.debug(
+            "%s paused at pc %d: %s", self.name, self.interp.pc, outcome.kind.name
+        )
+        return outcome
+
+    def resume_host_call(self, value) -> None:
+        self.interp.resume_host_call(value)
+
+    def _trace_instruction(self) -> None:
+        inst = self.interp.instructions[self.interp.pc]
+        filename, lineno = self.interp.location() or ("?", 0)
+        trace_logger.debug(
+            "%s pc %d %s:%d depth %d %s %s",
+            self.name,
+            self.interp.pc,
+            filename,
+            lineno,
+            len(self.interp.ops.stack),
+            inst.opname,
+            inst.argrepr,
+        )
+
+    def error(self, e: Exception) -> ProcessException:
+        """Wraps an exception from the current instruction, with a backtrace
+        through the process and any functions it was calling."""
+        backtrace = []
+        loc = self.interp.location()
+        if loc is not None:
+            backtrace.append((self._funcname, loc[0], loc[1]))
+        backtrace.extend(model_frames(e.__traceback__))
+        return ProcessException(f"{self.name}@{self.interp.pc}", e, backtrace)
+
+    def _run(self, fuel: Optional[int]) -> StepOutcome:
+        executed = 0
+        while self.can_execute():
+            if fuel is not None and executed >= fuel:
+                return StepOutcome(StepKind.OUT_OF_FUEL)
+            executed += 1
+            if self.profiler is not None:
+                loc = self.interp.location()
+                start = self.profiler.clock()
+            if trace_logger.isEnabledFor(logging.DEBUG):
+                self._trace_instruction()
             try:
                 cont = self.interp.interpret_instruction()
             except Exception as e:
-                raise ProcessException(f"{self.name}@{self.interp.pc}", e)
-            if cont.kind == PauseReason.DONE or cont.kind == PauseReason.YIELD:
+                raise self.error(e)
+            if self.profiler is not None:
+                self.profiler.record_instruction(loc, self.profiler.clock() - start)
+            if cont.kind == PauseReason.YIELD:
+                if cont.yield_msg != "":
+                    self._stepname = cont.yield_msg
+                return StepOutcome(
+                    StepKind.YIELDED, self._stepname, fairness=cont.fairness
+                )
+            if cont.kind == PauseReason.BLOCKED:
+                return StepOutcome(StepKind.BLOCKED, guard=self.interp.location())
+            if cont.kind == PauseReason.HOST_CALL:
+                return StepOutcome(StepKind.HOST_CALL, call=self.interp.host_call)
+            if cont.kind == PauseReason.DONE:
                 break
-
-        if cont.kind == PauseReason.YIELD:
-            if cont.yield_msg != "":
-                self._stepname = cont.yield_msg
-        self.interp.state_controller = None
-        return cont
+        return StepOutcome(StepKind.DONE)
 
     def register_cas(self, cas: CAS) -> None:
         self._cas = cas
@@ -56,12 +260,12 @@ class BytecodeProcess(Process):
         if self.set_hash is not None:
             return self.set_hash
         save_state = {
-            k: v for (k, v) in self.interp.state.items()
+            k: _unshared(v) for (k, v) in self.interp.state.items()
             if not k.startswith("_")
         }
         return {
             "state": save_state,
-            "stack": self.interp.ops.stack,
+            "stack": _unshared(self.interp.ops.stack),
             "pc": self.interp.ops.pc,
             "_stepname": self._stepname,
             "_funcname": self._funcname,
@@ -73,11 +277,54 @@ class BytecodeProcess(Process):
         self.set_hash = hash
         state = self._cas.restore(hash)
         assert isinstance(state, dict)
-        self.interp.state = state["state"]
-        self.interp.ops.stack = state["stack"]
+        self.interp.state = _unshared(state["state"])
+        self.interp.ops.stack = _unshared(state["stack"])
         self.interp.ops.pc = state["pc"]
         self._stepname = state["_stepname"]
         self._funcname = state["_funcname"]
 
+    def pretty(self) -> str:
+        """A readable, multi-line description of where the process is and
+        what it holds."""
+        head = self.name
+        loc = self.location()
+        if loc is not None:
+            filename = os.path.relpath(loc[0])
+            if filename.startswith(os.pardir):
+                filename = loc[0]
+            source = linecache.getline(loc[0], loc[1]).strip()
+            head += f" at {filename}:{loc[1]}: {source}"
+        elif not self.can_execute():
+            head += " (finished)"
+        lines = [head]
+        names = sorted(set(self.interp.state) | set(self.interp.binds))
+        for name in names:
+            if name in self.interp.binds:
+                val = self.interp.binds[name][len(OBJECT_PREFIX) :]
+            else:
+                val = pprint.pformat(self.interp.state[name], width=60)
+            indent = "\n" + " " * (len(name) + 5)
+            lines.append(f"  {name} = " + val.replace("\n", indent))
+        if self.interp.ops.stack:
+            stack = ", ".join(repr(v) for v in self.interp.ops.stack)
+            lines.append(f"  stack: [{stack}]")
+        return "\n".join(lines)
+
+    def __str__(self) -> str:
+        return self.pretty()
+
     def __repr__(self) -> str:
         return f"{self.name}: {self.interp.state}"
+
+
+def _unshared(v):
+    # Saved iterators and functions are stored whole, holding the values they
+    # were given, which may be lists or dicts the process still changes. Each
+    # state gets its own copy, as the state tree gives lists and dicts.
+    if isinstance(v, (SavedIterator, SavedFunction)):
+        return copy.deepcopy(v)
+    if type(v) is list:
+        return [_unshared(x) for x in v]
+    if type(v) is dict:
+        return {k: _unshared(x) for k, x in v.items()}
+    return v
@@ -0,0 +1,247 @@
+import builtins
+import dis
+import inspect
+
+from dataclasses import dataclass
+
+from typing import Callable
+from typing import Dict
+from typing import FrozenSet
+from typing import Iterable
+from typing import List
+from typing import Optional
+from typing import Tuple
+
+from .opcodes import InterpreterError
+from .opcodes import OpcodeInterpreter
+from .opcodes import UnsupportedInstruction
+
+
+# Instructions after which execution never falls through to the next one
+_NO_FALLTHROUGH = {
+    "JUMP_ABSOLUTE",
+    "JUMP_FORWARD",
+    "RETURN_VALUE",
+    "RAISE_VARARGS",
+    "RERAISE",
+}
+
+_JUMPS = set(dis.hasjrel) | set(dis.hasjabs)
+
+
+class VerificationError(Exception):
+    """A process's bytecode can't be interpreted, found before running it."""
+
+    def __init__(
+        self, name: str, filename: str, problems: List[InterpreterError], lines
+    ):
+        self.problems = problems
+        out = [f"{name} can't be interpreted:"]
+        for p in problems:
+            out.append(f"  {filename}:{lines.get(p.pc, '?')} (pc {p.pc}): {p.msg}")
+        super().__init__("\n".join(out))
+
+
+def verify(instructions: Iterable[dis.Instruction]) -> List[InterpreterError]:
+    """Checks bytecode before it runs: every instruction must be supported,
+    every jump must land on an instruction, and the stack must never be
+    popped while empty or reach an instruction at different depths."""
+    insts = list(instructions)
+    offsets = {inst.offset: i for i, inst in enumerate(insts)}
+    problems: Dict[int, InterpreterError] = {}
+
+    for pc, inst in enumerate(insts):
+        if not hasattr(OpcodeInterpreter, "exec_" + inst.opname.lower()):
+            problems[pc] = UnsupportedInstruction(
+                pc, f"{inst.opname} isn't supported yet"
+            )
+        elif inst.opcode in _JUMPS and inst.argval not in offsets:
+            problems[pc] = InterpreterError(
+                pc, f"jump to {inst.argval!r}, which isn't an instruction"
+            )
+
+    depths: Dict[int, int] = {}
+    todo = [(0, 0)] if insts else []
+    while todo:
+        pc, depth = todo.pop()
+        if pc in depths:
+            if depths[pc] != depth and pc not in problems:
+                problems[pc] = InterpreterError(
+                    pc,
+                    f"reached with {depth} values on the stack, "
+                    f"and elsewhere {depths[pc]}",
+                )
+            continue
+        depths[pc] = depth
+        inst = insts[pc]
+        effect = _effect(inst, jump=False)
+        if effect is not None and depth + effect < 0:
+            if pc not in problems:
+                problems[pc] = InterpreterError(
+                    pc, f"{inst.opname} pops from an empty stack"
+                )
+            continue
+        for target, jump in _successors(insts, offsets, pc):
+            effect = _effect(inst, jump=jump)
+            if effect is not None:
+                todo.append((target, depth + effect))
+
+    return [problems[pc] for pc in sorted(problems)]
+
+
+def check_names(func: Callable) -> List[InterpreterError]:
+    """Finds names a process reads that can't have a value: globals that
+    aren't defined in its module or builtins, and locals that no path to
+    the read assigns."""
+    return sorted(check_globals(func) + check_locals(func), key=lambda p: p.pc)
+
+
+def check_globals(func: Callable) -> List[InterpreterError]:
+    """Finds globals a process reads that aren't defined in its module or
+    builtins. The module may define them after the process is made, so this
+    waits until the model is evaluated."""
+    problems = []
+    for pc, inst in enumerate(dis.get_instructions(func)):
+        if inst.opname == "LOAD_GLOBAL":
+            name = inst.argval
+            if name not in func.__globals__ and not hasattr(builtins, name):
+                problems.append(InterpreterError(pc, f"{name} isn't defined"))
+    return problems
+
+
+def check_locals(func: Callable) -> List[InterpreterError]:
+    """Finds locals a process reads that no path to the read assigns."""
+    insts = list(dis.get_instructions(func))
+    offsets = {inst.offset: i for i, inst in enumerate(insts)}
+    problems = []
+
+    # The locals that some path to each instruction may have assigned
+    code = func.__code__
+    nargs = code.co_argcount + code.co_kwonlyargcount
+    nargs += bool(code.co_flags & inspect.CO_VARARGS)
+    nargs += bool(code.co_flags & inspect.CO_VARKEYWORDS)
+    assigned: Dict[int, FrozenSet[str]] = {}
+    todo = [(0, frozenset(code.co_varnames[:nargs]))] if insts else []
+    while todo:
+        pc, names = todo.pop()
+        if pc in assigned:
+            if names <= assigned[pc]:
+                continue
+            names = names | assigned[pc]
+        assigned[pc] = names
+        inst = insts[pc]
+        if inst.opname == "STORE_FAST":
+            names = names | {inst.argval}
+        elif inst.opname == "DELETE_FAST":
+            names = names - {inst.argval}
+        for target, _ in _successors(insts, offsets, pc):
+            todo.append((target, names))
+
+    for pc, inst in enumerate(insts):
+        if inst.opname != "LOAD_FAST" or pc not in assigned:
+            continue
+        if inst.argval not in assigned[pc]:
+            msg = f"{inst.argval} is read before it's assigned"
+            problems.append(InterpreterError(pc, msg))
+
+    return sorted(problems, key=lambda p: p.pc)
+
+
+@dataclass
+class Diagnostic:
+    """A warning about a process that doesn't stop it from running."""
+
+    rule: str
+    pc: int
+    msg: str
+
+
+class TimewinderWarning(UserWarning):
+    pass
+
+
+# Rules that are only applied when asked for. Specs and checks may read a
+# process's locals from outside, through Process.variable, so a local the
+# process never reads itself may still be used.
+OPT_IN_RULES = ("unused-variable",)
+
+
+def lint(func: Callable) -> List[Diagnostic]:
+    """Finds likely mistakes in a process: locals that are assigned but never
+    read, code that can't be reached, and locals that shadow a global."""
+    insts = list(dis.get_instructions(func))
+    offsets = {inst.offset: i for i, inst in enumerate(insts)}
+    out = []
+
+    read = {
+        inst.argval for inst in insts if inst.opname in ("LOAD_FAST", "DELETE_FAST")
+    }
+    reported = set()
+    for pc, inst in enumerate(insts):
+        if inst.opname != "STORE_FAST" or inst.argval in reported:
+            continue
+        name = inst.argval
+        reported.add(name)
+        if name not in read and not name.startswith("_"):
+            out.append(
+                Diagnostic(
+                    "unused-variable",
+                    pc,
+                    f"{name} is assigned but never read, "
+                    "though it still makes states distinct",
+                )
+            )
+        if name in func.__globals__ or hasattr(builtins, name):
+            msg = f"{name} shadows a global of the same name"
+            out.append(Diagnostic("shadowing", pc, msg))
+
+    reachable = set()
+    todo = [0] if insts else []
+    while todo:
+        pc = todo.pop()
+        if pc in reachable:
+            continue
+        reachable.add(pc)
+        todo.extend(target for target, _ in _successors(insts, offsets, pc))
+    # Once per stretch of unreachable code that came from a line of source;
+    # the `return None` ending an infinite loop doesn't count
+    in_stretch = False
+    for pc, inst in enumerate(insts):
+        if pc in reachable:
+            in_stretch = False
+        elif inst.starts_line is not None and not in_stretch:
+            in_stretch = True
+            out.append(Diagnostic("unreachable", pc, "this code can never run"))
+
+    return sorted(out, key=lambda d: d.pc)
+
+
+def _successors(insts, offsets, pc) -> List[Tuple[int, bool]]:
+    """The instructions execution can move to from pc, and whether it jumps."""
+    inst = insts[pc]
+    out = []
+    if inst.opcode in _JUMPS and inst.argval in offsets:
+        out.append((offsets[inst.argval], True))
+    if inst.opname not in _NO_FALLTHROUGH and pc + 1 < len(insts):
+        out.append((pc + 1, False))
+    return out
+
+
+def source_lines(instructions: Iterable[dis.Instruction]) -> Dict[int, int]:
+    """The source line of each instruction, by index."""
+    out = {}
+    line = None
+    for pc, inst in enumerate(instructions):
+        if inst.starts_line is not None:
+            line = inst.starts_line
+        out[pc] = line
+    return out
+
+
+def _effect(inst: dis.Instruction, jump: bool) -> Optional[int]:
+    try:
+        if inst.opcode < dis.HAVE_ARGUMENT:
+            return dis.stack_effect(inst.opcode)
+        return dis.stack_effect(inst.opcode, inst.arg or 0, jump=jump)
+    except ValueError:
+        return None
@@ -0,0 +1,269 @@
+import json
+
+from dataclasses import asdict
+from dataclasses import replace
+from dataclasses import dataclass
+from dataclasses import field
+from enum import Enum
+from fnmatch import fnmatchcase
+
+from typing import Any
+from typing import Dict
+from typing import IO
+from typing import Iterable
+from typing import List
+from typing import Optional
+from typing import Tuple
+from typing import TYPE_CHECKING
+
+from timewinder.statetree import Hash
+
+if TYPE_CHECKING:
+    from .evaluation import Evaluator
+    from .evaluation import EvaluatorStats
+    from .evaluation import EvalThunk
+    from .metrics import MetricSummary
+    from .evaluation import ConstraintError
+    from .process import ProcessException
+
+
+# Bumped whenever the shape of CheckResult.to_dict() changes incompatibly.
+RESULT_VERSION = 2
+
+Location = Optional[Tuple[str, int]]
+
+
+class Status(Enum):
+    PASS = "pass"
+    FAIL = "fail"
+    ERROR = "error"
+
+
+@dataclass
+class PropertyResult:
+    name: str
+    liveness: bool
+    # One of "pass", "fail", "bounded" (held in every state explored, but
+    # a bound stopped exploration) or "unknown" (not checked to the end)
+    status: str
+    location: Location = None
+
+
+@dataclass
+class TraceStep:
+    """One state of a counterexample, and the action that led to it."""
+
+    hash: str
+    state: Dict[str, Any]
+    thread: Optional[int] = None
+    action: Optional[str] = None
+    location: Location = None
+
+
+@dataclass
+class CheckResult:
+    status: Status
+    stats: "EvaluatorStats"
+    properties: List[PropertyResult]
+    bounded: bool = False
+    # Which bound stopped exploration: "steps", "max_states" or "simulation"
+    bound: Optional[str] = None
+    violation: Optional[str] = None
+    trace: List[TraceStep] = field(default_factory=list)
+    error: Optional[str] = None
+    metrics: List["MetricSummary"] = field(default_factory=list)
+
+    @classmethod
+    def from_success(cls, ev: "Evaluator") -> "CheckResult":
+        return cls(
+            status=Status.PASS,
+            stats=ev.stats,
+            metrics=ev.metric_summaries,
+            properties=_properties(ev, None, "bounded" if ev.bounded else "pass"),
+            bounded=ev.bounded,
+            bound=ev._bound,
+        )
+
+    @classmethod
+    def from_violation(cls, ev: "Evaluator", err: "ConstraintError") -> "CheckResult":
+        return cls(
+            status=Status.FAIL,
+            stats=ev.stats,
+            metrics=ev.metric_summaries,
+            properties=_properties(ev, err.spec, "unknown"),
+            violation=err.name,
+            trace=build_trace(ev, err.thunk),
+        )
+
+    @classmethod
+    def from_error(cls, ev: "Evaluator", err: "ProcessException") -> "CheckResult":
+        return cls(
+            status=Status.ERROR,
+            stats=ev.stats,
+            metrics=ev.metric_summaries,
+            properties=_properties(ev, None, "unknown"),
+            error=repr(err),
+        )
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {
+            "version": RESULT_VERSION,
+            "status": self.status.value,
+            "bounded": self.bounded,
+            "bound": self.bound,
+            "stats": asdict(self.stats),
+            "properties": [
+                {
+                    "name": p.name,
+                    "liveness": p.liveness,
+                    "status": p.status,
+                    "location": _location_dict(p.location),
+                }
+                for p in self.properties
+            ],
+            "violation": self.violation,
+            "error": self.error,
+            "trace": trace_to_dicts(self.trace),
+            "metrics": [
+                {
+                    "name": m.name,
+                    "count": m.count,
+                    "over": m.over,
+                    "min": m.min,
+                    "max": m.max,
+                    "mean": m.mean,
+                }
+                for m in self.metrics
+            ],
+        }
+
+    def project(self, variables: Iterable[str]) -> "CheckResult":
+        """This result with its trace cut down by project_trace."""
+        return replace(self, trace=project_trace(self.trace, variables))
+
+    def dumps(self, **kwargs) -> str:
+        return json.dumps(self.to_dict(), default=encode_value, **kwargs)
+
+    def dump(self, fp: IO[str], **kwargs) -> None:
+        json.dump(self.to_dict(), fp, default=encode_value, **kwargs)
+
+
+def trace_to_dicts(trace: List[TraceStep]) -> List[Dict[str, Any]]:
+    return [
+        {
+            "hash": s.hash,
+            "thread": s.thread,
+            "action": s.action,
+            "location": _location_dict(s.location),
+            "state": s.state,
+        }
+        for s in trace
+    ]
+
+
+def build_trace(ev: "Evaluator", thunk: "EvalThunk") -> List[TraceStep]:
+    """Restores each state in the thunk, pairing it with the action that
+    produced it."""
+    sc = ev.state_controller
+    out = [
+        TraceStep(
+            hash=thunk.initial_hash().hex(),
+            state=sc.cas.restore(thunk.initial_hash()),
+        )
+    ]
+    prev_hash = thunk.initial_hash()
+    for tid, hash in zip(thunk.trace, thunk.hashes[1:]):
+        sc.restore(prev_hash)
+        thread = ev.threads[tid]
+        action = thread.step_name()
+        location = thread.location()
+        out.append(
+            TraceStep(
+                hash=hash.hex(),
+                state=sc.cas.restore(hash),
+                thread=tid,
+                action=action,
+                location=location,
+            )
+        )
+        prev_hash = hash
+    sc.restore(prev_hash)
+    return out
+
+
+def project_trace(trace: List[TraceStep], variables: Iterable[str]) -> List[TraceStep]:
+    """Cuts each state of a trace down to the values at the given dotted
+    paths, matched part by part as globs (eg, "alice.acc" or "_thread_*.pc").
+    After the initial state, only the values a step changed are kept, so a
+    long trace reads as the actions and what each did to the variables of
+    interest."""
+    patterns = [v.split(".") for v in variables]
+    out = []
+    prev = None
+    for step in trace:
+        state = _select(step.state, [], patterns)
+        shown = state if prev is None else _changes(prev, state)
+        out.append(replace(step, state=shown))
+        prev = state
+    return out
+
+
+def _select(v: Any, path: List[str], patterns: List[List[str]]) -> Any:
+    items = v.items() if isinstance(v, dict) else enumerate(v)
+    out = {}
+    for k, x in items:
+        p = path + [str(k)]
+        if any(_matches(p[: len(pat)], pat) for pat in patterns if len(pat) <= len(p)):
+            out[k] = x
+        elif isinstance(x, (dict, list)) and any(
+            _matches(p, pat[: len(p)]) for pat in patterns if len(pat) > len(p)
+        ):
+            out[k] = _select(x, p, patterns)
+    return out
+
+
+def _matches(path: List[str], pattern: List[str]) -> bool:
+    return all(fnmatchcase(p, pat) for p, pat in zip(path, pattern))
+
+
+def _changes(old: Dict[Any, Any], new: Dict[Any, Any]) -> Dict[Any, Any]:
+    out = {}
+    for k, v in new.items():
+        if k not in old:
+            out[k] = v
+        elif isinstance(v, dict) and isinstance(old[k], dict) and len(v) != 0:
+            changed = _changes(old[k], v)
+            if changed:
+                out[k] = changed
+        elif v != old[k]:
+            out[k] = v
+    return out
+
+
+def encode_value(v):
+    """JSON fallback for state values that aren't natively serializable."""
+    if isinstance(v, Hash):
+        return v.hex()
+    if isinstance(v, bytes):
+        return v.hex()
+    if isinstance(v, (set, frozenset)):
+        return sorted(v, key=repr)
+    return repr(v)
+
+
+def _properties(ev, failed, default) -> List[PropertyResult]:
+    out = []
+    for spec in ev.specs:
+        status = default
+        if failed is not None and spec is failed:
+            status = "fail"
+        out.append(
+            PropertyResult(str(spec), spec.is_liveness(), status, spec.location())
+        )
+    return out
+
+
+def _location_dict(loc: Location) -> Optional[Dict[str, Any]]:
+    if loc is None:
+        return None
+    return {"file": loc[0], "line": loc[1]}
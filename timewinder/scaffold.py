@@ -0,0 +1,110 @@
+import os
+
+from typing import Dict
+from typing import List
+
+
+MODEL = '''"""{name}: a starter model.
+
+Two transfers draw from the same account. Each checks the balance before
+withdrawing, but the check and the withdrawal are separate steps, so
+another transfer can run in between."""
+
+import timewinder
+
+
+@timewinder.object
+class Account:
+    def __init__(self, name, amt):
+        self.name = name
+        self.acc = amt
+
+
+@timewinder.process
+def transfer(sender, reciever, amount):
+    if sender.acc >= amount:
+        yield "withdraw"
+        sender.acc = sender.acc - amount
+        yield "deposit"
+        reciever.acc = reciever.acc + amount
+
+
+def model(amount=3):
+    alice = Account("alice", 5)
+    bob = Account("bob", 5)
+
+    # At every step, no account may be overdrawn
+    no_overdrafts = timewinder.ForAll(Account, lambda a: a.acc >= 0)
+
+    return timewinder.Evaluator(
+        objects=[alice, bob],
+        threads=[transfer(alice, bob, amount), transfer(alice, bob, amount)],
+        specs=[no_overdrafts],
+    )
+'''
+
+TEST = """from timewinder.testing import expect_fail
+from timewinder.testing import expect_pass
+
+from model import model
+
+
+def test_concurrent_transfers_overdraw():
+    expect_fail(model(), violation="no_overdrafts")
+
+
+def test_small_transfers_are_safe():
+    expect_pass(model(amount=2))
+"""
+
+README = """# {name}
+
+A [Timewinder](https://github.com/timewinder-dev/timewinder) model.
+
+    timewinder check              # find the overdraft
+    timewinder check -D amount=2  # small transfers are safe
+    timewinder simulate           # check random behaviors
+    pytest                        # run the model's tests
+
+`timewinder.toml` holds the defaults for these commands.
+"""
+
+CONFIG = """# Defaults for timewinder commands run in this directory, or below it.
+# Options given on the command line win.
+model = "model.py"
+
+[constants]
+amount = 3
+
+[check]
+fairness = "none"
+"""
+
+
+def project_files(name: str) -> Dict[str, str]:
+    return {
+        "model.py": MODEL.format(name=name),
+        "test_model.py": TEST,
+        "README.md": README.format(name=name),
+        "timewinder.toml": CONFIG,
+    }
+
+
+def create_project(path: str) -> List[str]:
+    """Writes a starter project into `path`, returning the files created.
+
+    Refuses to overwrite anything, so it's safe to run in an existing
+    directory."""
+    name = os.path.basename(os.path.abspath(path))
+    files = project_files(name)
+    existing = [f for f in files if os.path.exists(os.path.join(path, f))]
+    if existing:
+        raise FileExistsError(f"{path} already has {', '.join(existing)}")
+    os.makedirs(path, exist_ok=True)
+    out = []
+    for filename, contents in files.items():
+        full = os.path.join(path, filename)
+        with open(full, "w") as f:
+            f.write(contents)
+        out.append(full)
+    return out
@@ -0,0 +1,97 @@
+from collections import deque
+
+from typing import Deque
+from typing import Dict
+from typing import List
+from typing import Optional
+from typing import Set
+from typing import TYPE_CHECKING
+
+from .evaluation import EvalThunk
+from .graph import Edge
+from .graph import StateGraph
+from .result import TraceStep
+from .result import build_trace
+from .statetree import Hash
+
+if TYPE_CHECKING:
+    from .evaluation import Evaluator
+
+
+COVER = ["transitions", "actions"]
+
+
+def covering_sequences(
+    ev: "Evaluator", steps: Optional[int] = None, cover: str = "transitions"
+) -> List[List[TraceStep]]:
+    """Explores the model, ignoring its specs, and returns behaviors that
+    together take every transition between states at least once, or with
+    cover="actions", every action. Each is a trace from an initial state,
+    ready to drive an implementation through (see check_conformance).
+
+    Sequences are built greedily, each extended to the nearest transition
+    not yet taken until none is reachable from where it ends, so there are
+    few of them, though not necessarily the fewest."""
+    if cover not in COVER:
+        raise ValueError(f"can't cover {cover!r}; choose from {COVER}")
+    specs = ev.specs
+    ev.specs = []
+    ev.record_graph = True
+    try:
+        ev.evaluate(steps)
+    finally:
+        ev.specs = specs
+    graph = ev.graph
+    assert graph is not None
+
+    def wanted(e: Edge) -> bool:
+        if cover == "actions":
+            return e.action not in covered_actions
+        return e not in covered_edges
+
+    covered_edges: Set[Edge] = set()
+    covered_actions: Set[str] = set()
+    out = []
+    while True:
+        path = None
+        for start in graph.initial:
+            path = _path_to(graph, start, wanted)
+            if path is not None:
+                break
+        if path is None:
+            break
+        edges = path
+        while path is not None:
+            for e in path:
+                covered_edges.add(e)
+                covered_actions.add(e.action)
+            path = _path_to(graph, edges[-1].dst, wanted)
+            if path is not None:
+                edges = edges + path
+        thunk = EvalThunk(
+            trace=[e.thread for e in edges],
+            hashes=[edges[0].src] + [e.dst for e in edges],
+            predicate_traces=[],
+        )
+        out.append(build_trace(ev, thunk))
+    return out
+
+
+def _path_to(graph: StateGraph, start: Hash, wanted) -> Optional[List[Edge]]:
+    """The shortest path from start whose last edge is wanted."""
+    via: Dict[Hash, Optional[Edge]] = {start: None}
+    queue: Deque[Hash] = deque([start])
+    while queue:
+        h = queue.popleft()
+        for e in graph.successors(h):
+            if wanted(e):
+                path = [e]
+                prev = via[h]
+                while prev is not None:
+                    path.append(prev)
+                    prev = via[prev.src]
+                return path[::-1]
+            if e.dst not in via:
+                via[e.dst] = e
+                queue.append(e.dst)
+    return None
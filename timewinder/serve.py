@@ -0,0 +1,278 @@
+import json
+import re
+import sqlite3
+
+from http.server import BaseHTTPRequestHandler
+from http.server import ThreadingHTTPServer
+from urllib.parse import parse_qs
+from urllib.parse import urlparse
+
+from typing import Any
+from typing import Dict
+from typing import List
+from typing import Optional
+
+
+# The most states a search returns
+MAX_LIMIT = 1000
+
+
+class Archive:
+    """Reads the runs stored by export.archive_run."""
+
+    def __init__(self, path: str):
+        self.conn = sqlite3.connect(path, check_same_thread=False)
+        self.conn.row_factory = sqlite3.Row
+
+    def runs(self) -> List[Dict[str, Any]]:
+        rows = self.conn.execute(
+            "SELECT id, created, status, bounded, violation, error FROM runs "
+            "ORDER BY id DESC"
+        )
+        return [dict(r, bounded=bool(r["bounded"])) for r in rows]
+
+    def run(self, run_id: int) -> Optional[Dict[str, Any]]:
+        """A run with its stats, properties and counterexample."""
+        row = self.conn.execute("SELECT * FROM runs WHERE id = ?", (run_id,)).fetchone()
+        if row is None:
+            return None
+        out = dict(row, bounded=bool(row["bounded"]), stats=json.loads(row["stats"]))
+        out["properties"] = [
+            dict(p, liveness=bool(p["liveness"]))
+            for p in self.conn.execute(
+                "SELECT name, liveness, status FROM properties WHERE run_id = ?",
+                (run_id,),
+            )
+        ]
+        out["trace"] = [
+            dict(s)
+            for s in self.conn.execute(
+                "SELECT step, hash, thread, action FROM counterexamples "
+                "WHERE run_id = ? ORDER BY step",
+                (run_id,),
+            )
+        ]
+        (out["states"],) = self.conn.execute(
+            "SELECT count(*) FROM states WHERE run_id = ?", (run_id,)
+        ).fetchone()
+        return out
+
+    def states(
+        self, run_id: int, query: str = "", limit: int = 100
+    ) -> List[Dict[str, Any]]:
+        """The states of a run whose hash starts with, or whose JSON contains,
+        the query."""
+        rows = self.conn.execute(
+            "SELECT hash, initial, state FROM states WHERE run_id = ? "
+            "AND (hash LIKE ? ESCAPE '\\' OR state LIKE ? ESCAPE '\\') "
+            "ORDER BY initial DESC, hash LIMIT ?",
+            (run_id, _like(query) + "%", "%" + _like(query) + "%", limit),
+        )
+        return [_state(r) for r in rows]
+
+    def state(self, run_id: int, h: str) -> Optional[Dict[str, Any]]:
+        """A state, and the transitions into and out of it."""
+        row = self.conn.execute(
+            "SELECT hash, initial, state FROM states WHERE run_id = ? AND hash = ?",
+            (run_id, h),
+        ).fetchone()
+        if row is None:
+            return None
+        out = _state(row)
+        out["incoming"] = [
+            dict(t)
+            for t in self.conn.execute(
+                "SELECT src, thread, action FROM transitions "
+                "WHERE run_id = ? AND dst = ?",
+                (run_id, h),
+            )
+        ]
+        out["outgoing"] = [
+            dict(t)
+            for t in self.conn.execute(
+                "SELECT dst, thread, action FROM transitions "
+                "WHERE run_id = ? AND src = ?",
+                (run_id, h),
+            )
+        ]
+        return out
+
+
+def _like(s: str) -> str:
+    return s.replace("\\", "\\\\").replace("%", "\\%").replace("_", "\\_")
+
+
+def _state(row) -> Dict[str, Any]:
+    return {
+        "hash": row["hash"],
+        "initial": bool(row["initial"]),
+        "state": json.loads(row["state"]),
+    }
+
+
+class Handler(BaseHTTPRequestHandler):
+    """Serves the web UI, and the archive as JSON under /api."""
+
+    archive: Archive
+
+    def do_GET(self):
+        url = urlparse(self.path)
+        query = parse_qs(url.query)
+        if url.path == "/":
+            return self._send(200, "text/html; charset=utf-8", INDEX.encode())
+        if url.path == "/api/runs":
+            return self._json(self.archive.runs())
+        m = re.fullmatch(r"/api/runs/(\d+)", url.path)
+        if m:
+            return self._json(self.archive.run(int(m.group(1))))
+        m = re.fullmatch(r"/api/runs/(\d+)/states", url.path)
+        if m:
+            q = query.get("q", [""])[0]
+            limit = query.get("limit", ["100"])[0]
+            if not limit.isdigit():
+                return self._error(400, "limit must be a whole number")
+            limit = min(int(limit), MAX_LIMIT)
+            return self._json(self.archive.states(int(m.group(1)), q, limit))
+        m = re.fullmatch(r"/api/runs/(\d+)/states/([0-9a-f]+)", url.path)
+        if m:
+            return self._json(self.archive.state(int(m.group(1)), m.group(2)))
+        self._json(None)
+
+    def _json(self, data) -> None:
+        if data is None:
+            return self._error(404, "not found")
+        self._send(200, "application/json", json.dumps(data).encode())
+
+    def _error(self, code: int, msg: str) -> None:
+        self._send(code, "application/json", json.dumps({"error": msg}).encode())
+
+    def _send(self, code: int, content_type: str, body: bytes) -> None:
+        self.send_response(code)
+        self.send_header("Content-Type", content_type)
+        self.send_header("Content-Length", str(len(body)))
+        self.end_headers()
+        self.wfile.write(body)
+
+    def log_message(self, format, *args):
+        pass
+
+
+def make_server(
+    path: str, host: str = "127.0.0.1", port: int = 8000
+) -> ThreadingHTTPServer:
+    """A server for the web UI over the archive at path; port 0 picks a free
+    one. Call serve_forever() on it to start."""
+    handler = type("ArchiveHandler", (Handler,), {"archive": Archive(path)})
+    return ThreadingHTTPServer((host, port), handler)
+
+
+INDEX = """<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>timewinder</title>
+<style>
+body { font-family: sans-serif; margin: 0; display: flex; height: 100vh; }
+nav { width: 16em; border-right: 1px solid #ccc; overflow: auto; padding: 0.5em; }
+main { flex: 1; overflow: auto; padding: 0 1em; }
+a { color: #05c; cursor: pointer; text-decoration: none; }
+table { border-collapse: collapse; margin-bottom: 1em; }
+th, td { border: 1px solid #ccc; padding: 0.2em 0.6em; text-align: left; }
+pre { background: #f6f6f6; padding: 0.5em; }
+.pass { color: #070; }
+.fail, .error { color: #b00; }
+.bounded, .unknown { color: #a60; }
+svg text { font-size: 11px; }
+</style>
+</head>
+<body>
+<nav><h3>Runs</h3><div id=runs></div></nav>
+<main><div id=run><p>Pick a run.</p></div><div id=state></div></main>
+<script>
+var run = null;
+function esc(s) {
+  return String(s).replace(/&/g, "&amp;").replace(/</g, "&lt;").replace(/>/g, "&gt;");
+}
+function get(url, then) {
+  fetch(url).then(function (r) { return r.json(); }).then(then);
+}
+function short(h) { return h.slice(0, 7); }
+function link(h) {
+  return "<a onclick=\\"showState('" + h + "')\\">" + short(h) + "</a>";
+}
+
+get("/api/runs", function (runs) {
+  document.getElementById("runs").innerHTML = runs.map(function (r) {
+    var status = r.status === "pass" && r.bounded ? "bounded" : r.status;
+    return "<div><a onclick='showRun(" + r.id + ")'>#" + r.id + "</a> <span class=" +
+      status + ">" + status + "</span><br><small>" + esc(r.created) + "</small></div>";
+  }).join("");
+});
+
+function showRun(id) {
+  get("/api/runs/" + id, function (r) {
+    run = r;
+    var out = "<h2>Run " + r.id + " <span class=" + r.status + ">" + r.status +
+      "</span></h2>";
+    if (r.violation) out += "<p>Violation: " + esc(r.violation) + "</p>";
+    if (r.error) out += "<p>Error: " + esc(r.error) + "</p>";
+    out += "<table><tr><th>Property</th><th>Kind</th><th>Status</th></tr>";
+    r.properties.forEach(function (p) {
+      var kind = p.liveness ? "liveness" : "safety";
+      out += "<tr><td>" + esc(p.name) + "</td><td>" + kind + "</td><td class=" +
+        p.status + ">" + p.status + "</td></tr>";
+    });
+    out += "</table>";
+    if (r.trace.length) {
+      out += "<h3>Counterexample</h3><ol start=0>";
+      r.trace.forEach(function (s) {
+        out += "<li>" + link(s.hash) + " " + esc(s.action || "Initial state") + "</li>";
+      });
+      out += "</ol>";
+    }
+    out += "<h3>States (" + r.states + ")</h3>" +
+      "<input id=q placeholder='hash or value' oninput='search()'>" +
+      "<div id=results></div>";
+    document.getElementById("run").innerHTML = out;
+    document.getElementById("state").innerHTML = "";
+    search();
+  });
+}
+
+function search() {
+  var q = encodeURIComponent(document.getElementById("q").value);
+  get("/api/runs/" + run.id + "/states?q=" + q, function (states) {
+    document.getElementById("results").innerHTML = states.map(function (s) {
+      return link(s.hash) + (s.initial ? " (initial)" : "");
+    }).join(" ");
+  });
+}
+
+function neighborhood(s) {
+  var rows = Math.max(s.incoming.length, s.outgoing.length, 1), height = rows * 24 + 8;
+  var mid = height / 2, out = "<svg width=640 height=" + height + ">";
+  function node(x, y, h, label, dir) {
+    var line = dir < 0 ? [x + 60, y, 260, mid] : [380, mid, x, y];
+    out += "<line x1=" + line[0] + " y1=" + line[1] + " x2=" + line[2] +
+      " y2=" + line[3] + " stroke=#999 />" +
+      "<text x=" + (x + (dir < 0 ? 0 : 4)) + " y=" + (y + 4) + ">" +
+      link(h) + " " + esc(label) + "</text>";
+  }
+  s.incoming.forEach(function (t, i) { node(0, 16 + i * 24, t.src, t.action, -1); });
+  s.outgoing.forEach(function (t, i) { node(400, 16 + i * 24, t.dst, t.action, 1); });
+  return out + "<rect x=260 y=" + (mid - 10) + " width=120 height=20 fill=#ffe680 />" +
+    "<text x=296 y=" + (mid + 4) + ">" + short(s.hash) + "</text></svg>";
+}
+
+function showState(h) {
+  get("/api/runs/" + run.id + "/states/" + h, function (s) {
+    var out = "<h3>State " + short(s.hash) + (s.initial ? " (initial)" : "") + "</h3>";
+    out += neighborhood(s);
+    out += "<pre>" + esc(JSON.stringify(s.state, null, 2)) + "</pre>";
+    document.getElementById("state").innerHTML = out;
+  });
+}
+</script>
+</body>
+</html>
+"""
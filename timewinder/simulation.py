@@ -0,0 +1,201 @@
+import random
+
+from typing import List
+from typing import Optional
+from typing import Set
+from typing import TYPE_CHECKING
+
+from timewinder.pause import Fairness
+from timewinder.pause import PauseReason
+
+from .evaluation import ConstraintError
+from .evaluation import EvalThunk
+from .ltl import TTrace
+from .process import ProcessException
+from .result import CheckResult
+
+if TYPE_CHECKING:
+    from .evaluation import Evaluator
+
+
+class ReplayError(Exception):
+    pass
+
+
+def encode_choices(choices: List[int]) -> str:
+    return ",".join(str(c) for c in choices)
+
+
+def decode_choices(log: str) -> List[int]:
+    try:
+        return [int(c) for c in log.split(",") if c.strip() != ""]
+    except ValueError:
+        raise ReplayError(f"{log!r} isn't a choice log")
+
+
+class Simulator:
+    """Checks random behaviors of a model rather than exploring every state.
+
+    Useful when the state space is too large to exhaust: each run picks a
+    random initial state, then random threads and nondeterministic outcomes,
+    checking the specs as it goes.
+
+    Every decision a run makes is logged in `choices`. Given that log as
+    `replay`, the simulator makes the same decisions again, reproducing the
+    run without needing the seed or the runs before it.
+
+    A guided simulator learns from its runs, as coverage-guided fuzzers do:
+    the decisions leading up to each newly found state are kept in a corpus,
+    and later runs repeat one of those before deciding at random, preferring
+    threads whose next action hasn't been taken yet. Runs then build on the
+    states found so far instead of starting from nothing, and go up to depth
+    steps further."""
+
+    def __init__(
+        self,
+        ev: "Evaluator",
+        seed: Optional[int] = None,
+        replay: Optional[List[int]] = None,
+        guided: bool = False,
+    ):
+        self.ev = ev
+        self.seed = seed
+        self.rng = random.Random(seed)
+        self.replay = list(replay) if replay is not None else None
+        # Drawn from by processes that import random; reseeded for every run
+        self.process_rng = random.Random()
+        # The decisions made by the most recent run
+        self.choices: List[int] = []
+        # The behavior followed by the most recent run
+        self.last_thunk: Optional[EvalThunk] = None
+        self.guided = guided
+        # Choice logs leading to new states, for guided runs to start from
+        self.corpus: List[List[int]] = []
+        self.actions: Set[str] = set()
+        self._prefix: List[int] = []
+
+    def run(self, runs: int = 100, depth: int = 100) -> CheckResult:
+        ev = self.ev
+        ev._initialize_evaluation()
+        for summary in ev._metric_summaries:
+            summary.over = "runs"
+        for thread in ev.threads:
+            thread.set_random(self.process_rng)
+        if self.replay is not None:
+            runs = 1
+        initial = list(ev.state_controller.commit())
+        seen = set()
+        try:
+            for _ in range(runs):
+                self._run_once(initial, depth, seen)
+        except ConstraintError as e:
+            return CheckResult.from_violation(ev, e)
+        except ProcessException as e:
+            return CheckResult.from_error(ev, e)
+        # Simulation never proves the absence of a violation
+        ev._bound = "simulation"
+        return CheckResult.from_success(ev)
+
+    def _choose(self, n: int, prefer: Optional[List[int]] = None) -> int:
+        if self.replay is None:
+            if len(self._prefix) != 0 and self._prefix[0] < n:
+                i = self._prefix.pop(0)
+            else:
+                self._prefix = []
+                i = self.rng.choice(prefer) if prefer else self.rng.randrange(n)
+        elif len(self.replay) == 0:
+            raise ReplayError("the choice log ended before the run did")
+        else:
+            i = self.replay.pop(0)
+            if not 0 <= i < n:
+                raise ReplayError(f"choice {i} of {n} doesn't fit this model")
+        self.choices.append(i)
+        return i
+
+    def _run_once(self, initial, depth, seen):
+        ev = self.ev
+        sc = ev.state_controller
+        self.choices = []
+        if self.guided and len(self.corpus) != 0:
+            self._prefix = list(self.corpus[self.rng.randrange(len(self.corpus))])
+        new_at = 0
+        # Steps repeated from the corpus don't count towards the depth
+        repeated = 0
+        self.process_rng.seed(self._choose(2 ** 32))
+        t = EvalThunk(
+            trace=[],
+            hashes=[initial[self._choose(len(initial))]],
+            predicate_traces=[TTrace([]) for _ in ev.preds],
+        )
+        self.last_thunk = t
+        while True:
+            sc.restore(t.state_hash())
+            fingerprint = ev.fingerprint(t.state_hash())
+            if fingerprint not in seen:
+                seen.add(fingerprint)
+                ev._stats.states += 1
+                new_at = len(self.choices)
+            ev._eval_preds(t)
+            ev._check_constraints(t)
+            ev._stats.steps = max(ev._stats.steps, len(t.trace))
+            if len(self._prefix) != 0:
+                repeated = len(t.trace)
+            if len(t.trace) - repeated >= depth:
+                ev._measure()
+                return self._keep(new_at)
+            if self.replay is not None and len(self.replay) == 0:
+                return
+            runnable = self._runnable(t)
+            if len(runnable) == 0:
+                ev._stats.final_states += 1
+                ev._measure()
+                if ev.deadlock and not ev.termination:
+                    ev._deadlocked(t)
+                return self._keep(new_at)
+            if not self._step(t, runnable):
+                # Every thread that can run is blocked, so the behavior
+                # stops here, as it would when evaluating
+                ev._stuck(t)
+                return self._keep(new_at)
+
+    def _step(self, t: EvalThunk, runnable: List[int]) -> bool:
+        """Runs threads chosen from runnable until one moves, extending the
+        behavior with its step; False if they're all blocked."""
+        ev = self.ev
+        sc = ev.state_controller
+        blocked: Set[int] = set()
+        while True:
+            candidates = [i for i in runnable if i not in blocked]
+            if len(candidates) == 0:
+                return False
+            prefer = None
+            if self.guided:
+                fresh = [
+                    ev.threads[i].step_name() not in self.actions for i in candidates
+                ]
+                prefer = [i for i, f in enumerate(fresh) if f]
+            tid = candidates[self._choose(len(candidates), prefer)]
+            self.actions.add(ev.threads[tid].step_name())
+            cont = ev._run_thread(ev.threads[tid])
+            next_hashes = list(sc.commit())
+            h = next_hashes[self._choose(len(next_hashes))]
+            if h == t.state_hash() and cont.kind == PauseReason.BLOCKED:
+                # Not a step
+                blocked.add(tid)
+                sc.restore(t.state_hash())
+                continue
+            t.trace.append(tid)
+            t.hashes.append(h)
+            t.must_run = [tid] if cont.fairness == Fairness.IMMEDIATE else []
+            return True
+
+    def _keep(self, new_at: int) -> None:
+        """Adds the decisions up to the last new state a run found to the
+        corpus."""
+        if self.guided and new_at != 0:
+            self.corpus.append(self.choices[:new_at])
+
+    def _runnable(self, t: EvalThunk) -> List[int]:
+        if len(t.must_run) != 0:
+            return t.must_run
+        return [i for i, thread in enumerate(self.ev.threads) if thread.can_execute()]
@@ -4,3 +4,15 @@ from .controller import StateController
 from .cas import CAS
 from .cas import MemoryCAS
 from .tree import Hash
+from .symbol import Symbol
+from .symbol import symbols
+from .record import Record
+from .saved import SavedFunction
+from .saved import SavedIterator
+from .saved import SavedModule
+from .sorted import SortedMap
+from .sorted import SortedSet
+from .diff import StateDiff
+from .diff import diff_states
+from .canonical import Canonicalizer
+from .size import approx_size_bytes
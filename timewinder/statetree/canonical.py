@@ -0,0 +1,57 @@
+from fnmatch import fnmatchcase
+from hashlib import sha256
+
+import msgpack
+
+from typing import Any
+from typing import Iterable
+from typing import List
+
+from .cas import CAS
+from .sorted import SortedMap
+from .tree import Hash
+from .tree import msgpack_ext_default
+
+
+class Canonicalizer:
+    """Decides which parts of a state count towards its identity.
+
+    The evaluator explores states with the same fingerprint only once. By
+    default every value counts, but values matching an `ignore` pattern
+    don't: a pattern is a dotted path, such as "_thread_*.state.retries",
+    where each part is matched as a glob. Ignoring a value that affects later
+    behavior can hide violations, so only ignore bookkeeping."""
+
+    def __init__(self, ignore: Iterable[str] = ()):
+        self.ignore: List[List[str]] = [p.split(".") for p in ignore]
+
+    def canonical(self, state: Any) -> Any:
+        """The state with ignored values removed and dicts as SortedMaps."""
+        return self._canonical(state, [])
+
+    def fingerprint(self, cas: CAS, h: Hash) -> bytes:
+        if len(self.ignore) == 0:
+            return h.bytes
+        canonical = self.canonical(cas.restore(h))
+        packed = msgpack.packb(canonical, default=msgpack_ext_default)
+        return sha256(packed).digest()
+
+    def _canonical(self, v: Any, path: List[str]) -> Any:
+        if isinstance(v, dict):
+            out = SortedMap()
+            for k in v:
+                p = path + [str(k)]
+                if not self._ignored(p):
+                    out[k] = self._canonical(v[k], p)
+            return out
+        if isinstance(v, list):
+            return [self._canonical(x, path + [str(i)]) for i, x in enumerate(v)]
+        return v
+
+    def _ignored(self, path: List[str]) -> bool:
+        for pattern in self.ignore:
+            if len(pattern) == len(path) and all(
+                fnmatchcase(p, pat) for p, pat in zip(path, pattern)
+            ):
+                return True
+        return False
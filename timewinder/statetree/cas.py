@@ -9,6 +9,7 @@ from typing import Iterable
 from .tree import TreeType
 from .tree import Hash
 from .tree import non_flat_keys
+from .size import approx_size_bytes
 
 
 _DEBUG = False
@@ -31,6 +32,17 @@ class CAS(ABC):
     def size(self) -> int:
         pass
 
+    @abstractmethod
+    def bytes_used(self) -> int:
+        """Roughly how much memory the stored nodes take."""
+        pass
+
+    @abstractmethod
+    def tree_size(self, sha: Hash) -> int:
+        """Roughly how much memory the tree rooted at sha takes, counting
+        nodes it shares with other trees."""
+        pass
+
     def restore(self, sha: Hash) -> TreeType:
         data = self.get(sha)
         items: Iterable
@@ -49,6 +61,9 @@ class CAS(ABC):
 class MemoryCAS(CAS):
     def __init__(self):
         self.store: Dict[bytes, TreeType] = {}
+        self._bytes = 0
+        # Of the tree rooted at each node, filled in as they're asked for
+        self._tree_sizes: Dict[bytes, int] = {}
 
     def put(self, sha: Hash, data: TreeType):
         if _DEBUG:
@@ -56,6 +71,7 @@ class MemoryCAS(CAS):
         if sha.bytes in self.store:
             return
         self.store[sha.bytes] = copy.copy(data)
+        self._bytes += approx_size_bytes(data)
 
     def get(self, sha: Hash) -> TreeType:
         return copy.copy(self.store[sha.bytes])
@@ -71,3 +87,17 @@ class MemoryCAS(CAS):
 
     def size(self) -> int:
         return len(self.store)
+
+    def bytes_used(self) -> int:
+        return self._bytes
+
+    def tree_size(self, sha: Hash) -> int:
+        size = self._tree_sizes.get(sha.bytes)
+        if size is None:
+            data = self.store[sha.bytes]
+            values = data if isinstance(data, list) else data.values()
+            size = approx_size_bytes(data) + sum(
+                self.tree_size(v) for v in values if isinstance(v, Hash)
+            )
+            self._tree_sizes[sha.bytes] = size
+        return size
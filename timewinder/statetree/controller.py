@@ -2,6 +2,8 @@ import itertools
 import copy
 
 from .cas import CAS
+from .diff import StateDiff
+from .diff import diff_states
 from .tree import non_flat_keys
 from .tree import Hash
 from .tree import hash_flat_tree
@@ -40,10 +42,15 @@ class StateController:
         for k, v in top_restored.items():
             self.tree[k].set_state(v)
 
+    def diff(self, old: Hash, new: Hash) -> StateDiff:
+        """Lists what changed, per object and thread, between two states."""
+        return diff_states(self.cas.restore(old), self.cas.restore(new))
+
     def state_to_str(self):
         out = ""
         for k, m in self.tree.items():
-            out += f"{k}:\n\t{m}\n"
+            text = str(m).replace("\n", "\n\t")
+            out += f"{k}:\n\t{text}\n"
         return out
 
 
@@ -0,0 +1,77 @@
+from dataclasses import dataclass
+from dataclasses import field
+
+from typing import Any
+from typing import Dict
+from typing import List
+from typing import Tuple
+
+
+@dataclass
+class EnvDiff:
+    """Changes to one mounted object or thread. Nested dicts are compared key
+    by key, with their paths joined by dots (eg, "state.total")."""
+
+    added: Dict[str, Any] = field(default_factory=dict)
+    removed: Dict[str, Any] = field(default_factory=dict)
+    changed: Dict[str, Tuple[Any, Any]] = field(default_factory=dict)
+
+    def __bool__(self) -> bool:
+        return bool(self.added or self.removed or self.changed)
+
+
+@dataclass
+class StateDiff:
+    envs: Dict[str, EnvDiff] = field(default_factory=dict)
+
+    def __bool__(self) -> bool:
+        return len(self.envs) != 0
+
+    def paths(self) -> List[str]:
+        """Every changed path, as "env.variable"."""
+        out = []
+        for env, d in self.envs.items():
+            for var in sorted({**d.added, **d.removed, **d.changed}):
+                out.append(f"{env}.{var}")
+        return out
+
+    def __str__(self) -> str:
+        lines = []
+        for env, d in self.envs.items():
+            for var, val in sorted(d.added.items()):
+                lines.append(f"+ {env}.{var} = {val!r}")
+            for var, val in sorted(d.removed.items()):
+                lines.append(f"- {env}.{var} = {val!r}")
+            for var, (old, new) in sorted(d.changed.items()):
+                lines.append(f"  {env}.{var}: {old!r} -> {new!r}")
+        return "\n".join(lines)
+
+
+def diff_states(old: Dict[str, Any], new: Dict[str, Any]) -> StateDiff:
+    """Compares two restored states, as returned by CAS.restore."""
+    out = StateDiff()
+    for env in sorted(set(old) | set(new)):
+        d = EnvDiff()
+        _diff_into(d, "", _as_dict(old.get(env, {})), _as_dict(new.get(env, {})))
+        if d:
+            out.envs[env] = d
+    return out
+
+
+def _as_dict(v) -> Dict[str, Any]:
+    if isinstance(v, dict):
+        return v
+    return {"value": v}
+
+
+def _diff_into(d: EnvDiff, prefix: str, old: Dict, new: Dict) -> None:
+    for key in sorted(set(old) | set(new), key=str):
+        path = f"{prefix}{key}"
+        if key not in new:
+            d.removed[path] = old[key]
+        elif key not in old:
+            d.added[path] = new[key]
+        elif isinstance(old[key], dict) and isinstance(new[key], dict):
+            _diff_into(d, path + ".", old[key], new[key])
+        elif old[key] != new[key]:
+            d.changed[path] = (old[key], new[key])
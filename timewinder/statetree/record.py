@@ -0,0 +1,55 @@
+from typing import Any
+from typing import Dict
+
+
+class Record:
+    """An immutable value with named fields, compared and hashed by those
+    fields, like a TLA+ record. Changing a field makes a new record:
+
+        pos = Record(x=1, y=2)
+        pos = pos.replace(x=pos.x + 1)
+
+    Fields should hold immutable values too, so that a record stays the
+    same once it's in a state."""
+
+    __slots__ = ("_fields",)
+
+    def __init__(self, **fields):
+        object.__setattr__(self, "_fields", tuple(sorted(fields.items())))
+
+    def __getattr__(self, name: str) -> Any:
+        for k, v in object.__getattribute__(self, "_fields"):
+            if k == name:
+                return v
+        raise AttributeError(f"record has no field {name}")
+
+    def __setattr__(self, name: str, value: Any) -> None:
+        raise AttributeError(f"records are immutable; use replace({name}=...)")
+
+    def replace(self, **changes) -> "Record":
+        """A copy with the given fields changed, each of which must exist."""
+        fields = self.asdict()
+        for k in changes:
+            if k not in fields:
+                raise AttributeError(f"record has no field {k}")
+        fields.update(changes)
+        return Record(**fields)
+
+    def asdict(self) -> Dict[str, Any]:
+        return dict(self._fields)
+
+    def __eq__(self, other) -> bool:
+        return isinstance(other, Record) and self._fields == other._fields
+
+    def __hash__(self) -> int:
+        return hash(self._fields)
+
+    def __reduce__(self):
+        return (_record, (self._fields,))
+
+    def __repr__(self) -> str:
+        return "Record(" + ", ".join(f"{k}={v!r}" for k, v in self._fields) + ")"
+
+
+def _record(fields) -> Record:
+    return Record(**dict(fields))
@@ -0,0 +1,39 @@
+from dataclasses import dataclass
+
+from typing import Any
+from typing import Dict
+from typing import Optional
+from typing import Tuple
+
+
+# Values a process holds that can't be kept in a state as they are. Each
+# holds what's needed to make the value again, and is stored whole, as one
+# value, so a dict or list the model builds is never mistaken for one.
+
+
+@dataclass(frozen=True)
+class SavedIterator:
+    """The values an iterator has left to produce."""
+
+    items: Tuple[Any, ...]
+
+
+@dataclass(frozen=True)
+class SavedFunction:
+    """A function a process made, by the index of its code in the process's
+    constants. It captures variables by value when it's made, rather than
+    sharing cells with the process."""
+
+    const: int
+    name: str
+    defaults: Tuple[Any, ...]
+    kwdefaults: Optional[Dict[str, Any]]
+    closure: Tuple[Any, ...]
+
+
+@dataclass(frozen=True)
+class SavedModule:
+    """A module a process imported, or a function taken from one, by name."""
+
+    name: str
+    attr: Optional[str] = None
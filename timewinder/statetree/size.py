@@ -0,0 +1,31 @@
+import sys
+
+from typing import Any
+
+from .record import Record
+from .saved import SavedFunction
+from .saved import SavedIterator
+from .tree import Hash
+
+# What a reference to another node costs within a node
+_POINTER = 8
+
+
+def approx_size_bytes(v: Any) -> int:
+    """Roughly how much memory a value takes, counting what it contains.
+    A Hash counts only as a reference, since the CAS stores what it points
+    to once, however many states share it."""
+    if isinstance(v, Hash):
+        return _POINTER
+    size = sys.getsizeof(v)
+    if isinstance(v, dict):
+        size += sum(approx_size_bytes(k) + approx_size_bytes(x) for k, x in v.items())
+    elif isinstance(v, (list, tuple, set, frozenset)):
+        size += sum(approx_size_bytes(x) for x in v)
+    elif isinstance(v, Record):
+        size += approx_size_bytes(v.asdict())
+    elif isinstance(v, SavedIterator):
+        size += approx_size_bytes(v.items)
+    elif isinstance(v, SavedFunction):
+        size += approx_size_bytes([v.defaults, v.kwdefaults, v.closure])
+    return size
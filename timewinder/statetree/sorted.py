@@ -0,0 +1,82 @@
+from typing import Any
+from typing import Iterable
+from typing import List
+
+
+def sorted_values(values: Iterable) -> List:
+    """Sorted naturally if the values can be, or else by repr, so that any
+    collection has one order."""
+    values = list(values)
+    try:
+        return sorted(values)
+    except TypeError:
+        return sorted(values, key=repr)
+
+
+class SortedSet(frozenset):
+    """A set that iterates in sorted order, so that a process looping over
+    it behaves the same however it was built. It's immutable, like any
+    value shared between states: `including` and `excluding` return new
+    sets.
+
+        held = SortedSet()
+        held = held.including("r2", "r1")
+        first = held[0]  # "r1"
+    """
+
+    def __iter__(self):
+        return iter(sorted_values(frozenset.__iter__(self)))
+
+    def __getitem__(self, i: int) -> Any:
+        return sorted_values(frozenset.__iter__(self))[i]
+
+    def including(self, *values) -> "SortedSet":
+        return SortedSet(frozenset.union(self, values))
+
+    def excluding(self, *values) -> "SortedSet":
+        return SortedSet(frozenset.difference(self, values))
+
+    def union(self, *others) -> "SortedSet":
+        return SortedSet(frozenset.union(self, *others))
+
+    def intersection(self, *others) -> "SortedSet":
+        return SortedSet(frozenset.intersection(self, *others))
+
+    def difference(self, *others) -> "SortedSet":
+        return SortedSet(frozenset.difference(self, *others))
+
+    def symmetric_difference(self, other) -> "SortedSet":
+        return SortedSet(frozenset.symmetric_difference(self, other))
+
+    __or__ = union
+    __and__ = intersection
+    __sub__ = difference
+    __xor__ = symmetric_difference
+
+    def __reduce__(self):
+        return (SortedSet, (list(self),))
+
+    def __repr__(self) -> str:
+        return f"SortedSet({list(self)!r})"
+
+
+class SortedMap(dict):
+    """A dict that iterates in sorted key order, however it was built. Plain
+    dicts iterate in insertion order, which can differ between states that
+    are otherwise the same, and so are explored only once."""
+
+    def __iter__(self):
+        return iter(sorted_values(dict.keys(self)))
+
+    def keys(self):
+        return list(self)
+
+    def values(self):
+        return [self[k] for k in self]
+
+    def items(self):
+        return [(k, self[k]) for k in self]
+
+    def __repr__(self) -> str:
+        items = ", ".join(f"{k!r}: {v!r}" for k, v in self.items())
+        return "SortedMap({" + items + "})"
@@ -0,0 +1,44 @@
+import re
+
+from typing import Dict
+from typing import List
+
+
+class Symbol:
+    """A model value: a unique atom, like `r1` for a resource, that can
+    only be compared for equality. Using one rather than a number or a
+    string for an identity keeps a model from doing arithmetic on it, or
+    confusing it with data.
+
+    Symbols are interned, so `Symbol("r1") is Symbol("r1")`."""
+
+    __slots__ = ("name",)
+    _interned: Dict[str, "Symbol"] = {}
+
+    def __new__(cls, name: str):
+        if not name.isidentifier():
+            raise ValueError(f"symbol {name!r} must be an identifier")
+        sym = cls._interned.get(name)
+        if sym is None:
+            sym = super().__new__(cls)
+            sym.name = name
+            cls._interned[name] = sym
+        return sym
+
+    def __reduce__(self):
+        return (Symbol, (self.name,))
+
+    def __copy__(self):
+        return self
+
+    def __deepcopy__(self, memo):
+        return self
+
+    def __repr__(self) -> str:
+        return self.name
+
+
+def symbols(names: str) -> List[Symbol]:
+    """Symbols for names separated by spaces or commas, as in
+    `r1, r2, r3 = symbols("r1 r2 r3")`."""
+    return [Symbol(n) for n in re.split(r"[\s,]+", names.strip()) if n]
@@ -4,6 +4,13 @@ import msgpack
 
 from timewinder.generators import NonDeterministicSet
 
+from .record import Record
+from .saved import SavedFunction
+from .saved import SavedIterator
+from .saved import SavedModule
+from .sorted import sorted_values
+from .symbol import Symbol
+
 from typing import Iterable
 from typing import List
 from typing import Tuple
@@ -32,7 +39,21 @@ class Hash:
 
 
 TreeType = Union[dict, list]
-FlatValueType = Union[Hash, str, int, bool, float, None, bytes]
+FlatValueType = Union[
+    Hash,
+    Symbol,
+    Record,
+    SavedIterator,
+    SavedFunction,
+    SavedModule,
+    frozenset,
+    str,
+    int,
+    bool,
+    float,
+    None,
+    bytes,
+]
 ValidValueType = Union[FlatValueType, NonDeterministicSet]
 TreeableType = Union[ValidValueType, dict, list]
 HashType = Union[Hash, dict, list]
@@ -47,6 +68,24 @@ def _is_deep_type(v) -> bool:
 def msgpack_ext_default(obj):
     if isinstance(obj, Hash):
         return msgpack.ExtType(1, obj.bytes)
+    if isinstance(obj, Symbol):
+        return msgpack.ExtType(2, obj.name.encode())
+    if isinstance(obj, frozenset):
+        # Including SortedSet; a mutable set could be changed after it's stored
+        packed = msgpack.packb(sorted_values(obj), default=msgpack_ext_default)
+        return msgpack.ExtType(3, packed)
+    if isinstance(obj, Record):
+        packed = msgpack.packb(obj._fields, default=msgpack_ext_default)
+        return msgpack.ExtType(4, packed)
+    if isinstance(obj, SavedIterator):
+        packed = msgpack.packb(obj.items, default=msgpack_ext_default)
+        return msgpack.ExtType(5, packed)
+    if isinstance(obj, SavedFunction):
+        fields = [obj.const, obj.name, obj.defaults, obj.kwdefaults, obj.closure]
+        packed = msgpack.packb(fields, default=msgpack_ext_default)
+        return msgpack.ExtType(6, packed)
+    if isinstance(obj, SavedModule):
+        return msgpack.ExtType(7, msgpack.packb([obj.name, obj.attr]))
     raise TypeError(f"Unsupported type for serializing tree: {type(obj)}")
 
 
@@ -0,0 +1,108 @@
+import ast
+import contextlib
+import itertools
+import os
+
+from concurrent.futures import ProcessPoolExecutor
+from dataclasses import dataclass
+
+from typing import Any
+from typing import Dict
+from typing import List
+from typing import Optional
+from typing import Tuple
+
+from .loader import LoadError
+from .loader import configure
+from .loader import load_model
+from .loader import parse_constant
+from .result import Status
+
+
+@dataclass
+class SweepRow:
+    """How the model fared with one combination of constants."""
+
+    constants: Dict[str, Any]
+    status: Status
+    states: int = 0
+    violation: Optional[str] = None
+    error: Optional[str] = None
+    bounded: bool = False
+
+
+def parse_axis(s: str) -> Tuple[str, List[Any]]:
+    """Parses the values a constant takes in a sweep: `N=2..6` for a range
+    of integers (inclusive), `N=1,2,4` for a list, or a single value as for
+    `-D`."""
+    name, val = parse_constant(s)
+    if isinstance(val, str) and ".." in val:
+        lo, _, hi = val.partition("..")
+        try:
+            return name, list(range(ast.literal_eval(lo), ast.literal_eval(hi) + 1))
+        except (ValueError, SyntaxError, TypeError):
+            raise LoadError(f"range {val} must look like LOW..HIGH")
+    if isinstance(val, (tuple, list)):
+        return name, list(val)
+    return name, [val]
+
+
+def grid_points(grid: Dict[str, List[Any]]) -> List[Dict[str, Any]]:
+    names = list(grid)
+    return [dict(zip(names, values)) for values in itertools.product(*grid.values())]
+
+
+def sweep(
+    path: str,
+    grid: Dict[str, List[Any]],
+    entry: Optional[str] = None,
+    steps: Optional[int] = None,
+    jobs: int = 1,
+    options: Optional[Dict[str, Any]] = None,
+) -> List[SweepRow]:
+    """Checks a model file with every combination of the constants' values,
+    in up to `jobs` processes at once, configuring each model with the
+    options of loader.configure. Rows come back in the order of the grid."""
+    points = grid_points(grid)
+    args = [(path, entry, constants, steps, options or {}) for constants in points]
+    if jobs <= 1:
+        return [_check_point(a) for a in args]
+    with ProcessPoolExecutor(max_workers=jobs) as pool:
+        return list(pool.map(_check_point, args))
+
+
+def _check_point(args) -> SweepRow:
+    path, entry, constants, steps, options = args
+    try:
+        with open(os.devnull, "w") as devnull, contextlib.redirect_stdout(devnull):
+            ev = configure(load_model(path, entry, constants), **options)
+            result = ev.check(steps=steps)
+    except Exception as e:
+        return SweepRow(constants, Status.ERROR, error=repr(e))
+    return SweepRow(
+        constants,
+        result.status,
+        result.stats.states,
+        result.violation,
+        result.error,
+        result.bounded,
+    )
+
+
+def format_matrix(rows: List[SweepRow]) -> str:
+    """A table with a row per combination of constants."""
+    if len(rows) == 0:
+        return ""
+    names = list(rows[0].constants)
+    table = [names + ["status", "states", "violation"]]
+    for r in rows:
+        detail = r.violation or r.error or ""
+        status = "bounded" if r.status == Status.PASS and r.bounded else r.status.value
+        values = [repr(r.constants[n]) for n in names]
+        table.append(values + [status, str(r.states), detail])
+    widths = [max(len(row[i]) for row in table) for i in range(len(table[0]) - 1)]
+    lines = []
+    for row in table:
+        cells = [c.ljust(w) for c, w in zip(row, widths)] + [row[-1]]
+        lines.append("  ".join(cells).rstrip())
+    return "\n".join(lines) + "\n"
@@ -0,0 +1,123 @@
+"""Helpers for testing models themselves, so regressions in a spec suite are
+caught by pytest.
+
+    def test_transfer():
+        expect_fail(model(), violation="no_overdrafts", actions=["withdraw@start"])
+
+    def test_transfer_golden():
+        assert_golden(model().check(steps=None), "golden/transfer.json")
+
+Golden files are written when missing, and rewritten when the
+TIMEWINDER_UPDATE_GOLDEN environment variable is set."""
+
+import difflib
+import json
+import os
+
+from typing import Any
+from typing import Dict
+from typing import List
+from typing import Optional
+
+from .evaluation import Evaluator
+from .result import CheckResult
+from .result import Status
+from .result import encode_value
+
+
+UPDATE_ENV = "TIMEWINDER_UPDATE_GOLDEN"
+
+
+def expect_pass(ev: Evaluator, steps: Optional[int] = None) -> CheckResult:
+    """Checks the model, failing the test unless no spec is violated."""
+    result = ev.check(steps=steps)
+    if result.status != Status.PASS:
+        raise AssertionError(f"expected the model to pass, got:\n{_describe(result)}")
+    return result
+
+
+def expect_fail(
+    ev: Evaluator,
+    violation: Optional[str] = None,
+    actions: Optional[List[str]] = None,
+    steps: Optional[int] = None,
+) -> CheckResult:
+    """Checks the model, failing the test unless a spec is violated.
+
+    `violation` must appear in the name of the violated spec, and `actions`
+    must be a prefix of the actions in the counterexample."""
+    result = ev.check(steps=steps)
+    if result.status != Status.FAIL:
+        raise AssertionError(f"expected a violation, got:\n{_describe(result)}")
+    assert result.violation is not None
+    if violation is not None and violation not in result.violation:
+        raise AssertionError(
+            f"expected {violation} to fail, but {result.violation} did"
+        )
+    if actions is not None:
+        got = trace_actions(result)
+        if got[: len(actions)] != actions:
+            raise AssertionError(f"expected the trace to start {actions}, got {got}")
+    return result
+
+
+def trace_actions(result: CheckResult) -> List[str]:
+    return [s.action for s in result.trace if s.action is not None]
+
+
+def snapshot(result: CheckResult) -> Dict[str, Any]:
+    """The parts of a result that should stay fixed as a model evolves.
+
+    Hashes, statistics and source locations are left out: they change with
+    unrelated edits, or between machines."""
+    out = {
+        "status": result.status.value,
+        "violation": result.violation,
+        "error": result.error,
+        "trace": [
+            {"thread": s.thread, "action": s.action, "state": s.state}
+            for s in result.trace
+        ],
+    }
+    # Round trip, so values compare the same as they would be read back
+    return json.loads(json.dumps(out, default=encode_value))
+
+
+def assert_golden(
+    result: CheckResult, path: str, update: Optional[bool] = None
+) -> None:
+    """Compares a result with the snapshot saved at `path`."""
+    if update is None:
+        update = os.environ.get(UPDATE_ENV, "") not in ("", "0")
+    got = snapshot(result)
+    if update or not os.path.exists(path):
+        directory = os.path.dirname(path)
+        if directory:
+            os.makedirs(directory, exist_ok=True)
+        with open(path, "w") as f:
+            json.dump(got, f, indent=2, sort_keys=True)
+            f.write("\n")
+        return
+    with open(path) as f:
+        expected = json.load(f)
+    if got == expected:
+        return
+    diff = difflib.unified_diff(
+        json.dumps(expected, indent=2, sort_keys=True).splitlines(),
+        json.dumps(got, indent=2, sort_keys=True).splitlines(),
+        fromfile=path,
+        tofile="result",
+        lineterm="",
+    )
+    raise AssertionError(
+        f"result differs from {path} (set {UPDATE_ENV}=1 to update):\n"
+        + "\n".join(diff)
+    )
+
+
+def _describe(result: CheckResult) -> str:
+    if result.status == Status.FAIL:
+        return f"{result.violation} failed after {trace_actions(result)}"
+    if result.status == Status.ERROR:
+        return f"error: {result.error}"
+    return "no violations"
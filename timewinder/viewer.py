@@ -0,0 +1,157 @@
+import json
+import linecache
+
+from typing import Any
+from typing import Dict
+from typing import List
+from typing import Optional
+from typing import Set
+from typing import Tuple
+
+
+# A rendered line, and the path of the state it shows (if it can be expanded)
+Line = Tuple[str, Optional[Tuple[str, ...]]]
+
+
+def load_trace(path: str) -> List[Dict[str, Any]]:
+    """Reads the trace from a report written by `timewinder check --format json`."""
+    with open(path) as f:
+        data = json.load(f)
+    trace = data.get("trace")
+    if not trace:
+        raise ValueError(f"{path} has no trace")
+    return trace
+
+
+class TraceView:
+    """The state of the trace viewer: the step shown, which nested values are
+    expanded, and whether to highlight changes from the previous step."""
+
+    def __init__(self, trace: List[Dict[str, Any]]):
+        self.trace = trace
+        self.index = 0
+        self.cursor = 0
+        self.expanded: Set[Tuple[str, ...]] = set()
+        self.show_diff = True
+
+    def next(self) -> None:
+        self.index = min(self.index + 1, len(self.trace) - 1)
+
+    def prev(self) -> None:
+        self.index = max(self.index - 1, 0)
+
+    def toggle(self, path: Tuple[str, ...]) -> None:
+        if path in self.expanded:
+            self.expanded.remove(path)
+        else:
+            self.expanded.add(path)
+
+    def toggle_cursor(self) -> None:
+        lines = self.lines()
+        if 0 <= self.cursor < len(lines):
+            path = lines[self.cursor][1]
+            if path is not None:
+                self.toggle(path)
+
+    def header(self) -> List[str]:
+        step = self.trace[self.index]
+        n = len(self.trace) - 1
+        if step.get("thread") is None:
+            out = [f"Initial state (step 0/{n})"]
+        else:
+            thread, action = step["thread"], step["action"]
+            out = [f"Step {self.index}/{n}: thread {thread} executes {action}"]
+        loc = step.get("location")
+        if loc is not None:
+            source = linecache.getline(loc["file"], loc["line"]).strip()
+            out.append(f"{loc['file']}:{loc['line']}: {source}")
+        return out
+
+    def lines(self) -> List[Line]:
+        state = self.trace[self.index]["state"]
+        prev = None
+        if self.show_diff and self.index > 0:
+            prev = self.trace[self.index - 1]["state"]
+        out: List[Line] = []
+        self._render(state, prev, (), 0, out)
+        return out
+
+    def _render(self, value, prev, path, depth, out) -> None:
+        for key in sorted(value, key=str):
+            if depth == 0 and str(key).startswith("_thread_"):
+                continue
+            v = value[key]
+            p = _child(prev, key)
+            mark = "*" if prev is not None and p != v else " "
+            indent = "  " * depth
+            kpath = path + (str(key),)
+            if isinstance(v, dict) and len(v) != 0:
+                if kpath in self.expanded:
+                    out.append((f"{mark} {indent}- {key}", kpath))
+                    self._render(
+                        v, p if isinstance(p, dict) else None, kpath, depth + 1, out
+                    )
+                else:
+                    out.append((f"{mark} {indent}+ {key}: {_summary(v)}", kpath))
+            else:
+                out.append((f"{mark} {indent}  {key}: {v!r}", None))
+
+
+def _child(prev, key):
+    if prev is None:
+        return None
+    return prev.get(key)
+
+
+def _summary(v: Dict[str, Any]) -> str:
+    text = json.dumps(v, default=repr)
+    if len(text) > 60:
+        text = text[:57] + "..."
+    return text
+
+
+HELP = "n/p: step  up/down: select  enter: expand  d: diff  q: quit"
+
+
+def run_curses(view: TraceView) -> None:
+    import curses
+
+    curses.wrapper(_loop, view)
+
+
+def _loop(stdscr, view: TraceView) -> None:
+    import curses
+
+    curses.curs_set(0)
+    while True:
+        stdscr.erase()
+        height, width = stdscr.getmaxyx()
+        header = view.header()
+        lines = view.lines()
+        view.cursor = max(0, min(view.cursor, len(lines) - 1))
+        for y, text in enumerate(header):
+            stdscr.addnstr(y, 0, text, width - 1, curses.A_BOLD)
+        top = len(header) + 1
+        visible = height - top - 1
+        start = max(0, view.cursor - visible + 1)
+        for y, (text, _) in enumerate(lines[start : start + visible]):
+            attr = curses.A_REVERSE if start + y == view.cursor else curses.A_NORMAL
+            stdscr.addnstr(top + y, 0, text, width - 1, attr)
+        stdscr.addnstr(height - 1, 0, HELP, width - 1, curses.A_DIM)
+        stdscr.refresh()
+
+        key = stdscr.getch()
+        if key in (ord("q"), 27):
+            return
+        elif key in (ord("n"), curses.KEY_RIGHT):
+            view.next()
+        elif key in (ord("p"), curses.KEY_LEFT):
+            view.prev()
+        elif key in (ord("j"), curses.KEY_DOWN):
+            view.cursor += 1
+        elif key in (ord("k"), curses.KEY_UP):
+            view.cursor -= 1
+        elif key in (ord("\n"), ord(" "), curses.KEY_ENTER):
+            view.toggle_cursor()
+        elif key == ord("d"):
+            view.show_diff = not view.show_diff
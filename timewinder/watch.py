@@ -0,0 +1,71 @@
+import os
+import time
+
+from typing import Callable
+from typing import Dict
+from typing import List
+from typing import Optional
+
+from .result import CheckResult
+from .result import Status
+
+
+class Watcher:
+    """Polls a set of files, waiting until one changes and then settles.
+
+    Editors often write a file in several steps, so a change only counts once
+    the modification times have stayed put for `debounce` seconds."""
+
+    def __init__(
+        self,
+        paths: List[str],
+        interval: float = 0.5,
+        debounce: float = 0.3,
+        sleep: Callable[[float], None] = time.sleep,
+    ):
+        self.paths = paths
+        self.interval = interval
+        self.debounce = debounce
+        self.sleep = sleep
+        self.last = self._mtimes()
+
+    def _mtimes(self) -> Dict[str, Optional[float]]:
+        out: Dict[str, Optional[float]] = {}
+        for p in self.paths:
+            try:
+                out[p] = os.stat(p).st_mtime
+            except OSError:
+                out[p] = None
+        return out
+
+    def wait_for_change(self) -> List[str]:
+        """Blocks until a file changes, returning the paths that changed."""
+        while True:
+            self.sleep(self.interval)
+            current = self._mtimes()
+            if current != self.last:
+                break
+        while True:
+            self.sleep(self.debounce)
+            settled = self._mtimes()
+            if settled == current:
+                break
+            current = settled
+        changed = [p for p in self.paths if current[p] != self.last[p]]
+        self.last = current
+        return changed
+
+
+def summarize(result: CheckResult, previous: Optional[CheckResult] = None) -> str:
+    """A one-line summary of a check, noting how the state count moved."""
+    stats = result.stats
+    states = f"{stats.states} states"
+    if previous is not None and previous.stats.states != stats.states:
+        states += f" ({stats.states - previous.stats.states:+d})"
+    counts = f"{states}, {stats.steps} steps"
+    if result.status == Status.PASS:
+        bound = ", bounded" if result.bounded else ""
+        return f"PASS {counts}{bound}"
+    if result.status == Status.FAIL:
+        return f"FAIL {result.violation} in {len(result.trace) - 1} steps; {counts}"
+    return f"ERROR {result.error}"